@@ -1,4 +1,4 @@
-use crate::archive::AndroidArchive;
+use crate::archive::{AndroidArchive, ArchiveEntry};
 use anyhow::Result;
 use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom};
@@ -57,6 +57,37 @@ impl ApkFile {
         file_entry.read_to_end(&mut file_contents)?;
         Ok(file_contents)
     }
+
+    /// Lists every file contained within the APK, sorted by path.
+    ///
+    /// Entries are read from the ZIP central directory without decompressing any data.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<ArchiveEntry>)` describing each file in the APK.
+    /// * `Err` if the APK is not a valid ZIP archive or an I/O error occurs.
+    pub fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>> {
+        // Reset the file cursor to the start so that the ZIP archive is read from the beginning.
+        self.file.seek(SeekFrom::Start(0))?;
+        let mut apk_archive = ZipArchive::new(&mut self.file)?;
+
+        let mut entries = Vec::with_capacity(apk_archive.len());
+        for i in 0..apk_archive.len() {
+            // Raw access only parses the entry headers, which is all a listing needs.
+            let file_entry = apk_archive.by_index_raw(i)?;
+            if file_entry.is_dir() {
+                continue;
+            }
+            entries.push(ArchiveEntry {
+                path: file_entry.name().to_string(),
+                size: file_entry.size(),
+                compressed_size: file_entry.compressed_size(),
+                split_apk: None,
+            });
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(entries)
+    }
 }
 
 /// Implements the AndroidArchive trait for ApkFile.
@@ -78,4 +109,11 @@ impl AndroidArchive for ApkFile {
     fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
         ApkFile::read_internal_file(self, internal_path)
     }
+
+    /// Lists every file contained within the APK archive.
+    ///
+    /// Delegates to the `list_entries` method of `ApkFile`.
+    fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>> {
+        ApkFile::list_entries(self)
+    }
 }
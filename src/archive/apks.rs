@@ -0,0 +1,123 @@
+use crate::archive::split_apks::SplitApkSet;
+use crate::archive::{AndroidArchive, ArchiveEntry};
+use anyhow::{bail, Result};
+use std::fs::File;
+use std::path::Path;
+use zip::read::ZipArchive;
+
+/// The filename of the base APK inside a split APK bundle.
+const BASE_APK_NAME: &str = "base.apk";
+
+/// Encapsulates an open split APK bundle and provides methods for accessing its internal files.
+///
+/// Split APK bundles are produced by tools such as SAI (`.apks`) and APKMirror (`.apkm`). Unlike
+/// XAPK files, they carry no `manifest.json` describing their contents, so the split APKs are
+/// discovered by scanning the bundle for nested `*.apk` entries.
+///
+/// The split APKs are opened once when the bundle is opened and kept open for all subsequent
+/// reads (see [`SplitApkSet`]).
+pub struct ApksFile {
+    /// The split APKs of the bundle, along with the index of the files they provide.
+    split_apks: SplitApkSet,
+}
+
+impl ApksFile {
+    /// Opens a split APK bundle, locates its split APKs and builds an index that maps internal file
+    /// paths to the corresponding split APK filename.
+    ///
+    /// The base APK (`base.apk`) is indexed first, followed by the configuration splits (ABI,
+    /// density, language) in name order. If multiple split APKs contain the same file, the
+    /// configuration splits override the base APK.
+    ///
+    /// # Arguments
+    /// - `path`: A reference to the path of the bundle to open.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened, is not a ZIP archive, contains no split APKs,
+    /// or any IO or parsing error occurs.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        // Open the bundle file.
+        let file = File::open(path)?;
+
+        // Collect every nested APK, ordering the base APK before the configuration splits.
+        let bundle_archive = ZipArchive::new(&file)?;
+        let mut split_files: Vec<String> = bundle_archive
+            .file_names()
+            .filter(|name| name.ends_with(".apk"))
+            .map(String::from)
+            .collect();
+        if split_files.is_empty() {
+            bail!("No split APKs found in bundle");
+        }
+        split_files.sort_by(|a, b| {
+            (a != BASE_APK_NAME)
+                .cmp(&(b != BASE_APK_NAME))
+                .then_with(|| a.cmp(b))
+        });
+
+        // Open the split APKs and build an index mapping internal file paths to the split APK that contains them.
+        let split_apks = SplitApkSet::open(&file, split_files.iter().map(String::as_str))?;
+
+        Ok(Self { split_apks })
+    }
+
+    /// Reads an internal file from one of the split APKs contained within the bundle.
+    ///
+    /// This function looks up which split APK contains the desired internal file using the pre-built file map,
+    /// then extracts and returns the file's contents as a vector of bytes.
+    ///
+    /// # Arguments
+    /// - `internal_path`: The path of the internal file to be read (as it appears within a split APK).
+    ///
+    /// # Errors
+    /// Returns an error if the internal file or its corresponding split APK cannot be found,
+    /// or if any IO or ZIP processing error occurs during the read operation.
+    pub fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
+        self.split_apks.read_internal_file(internal_path)
+    }
+
+    /// Lists every file provided by the split APKs, sorted by path.
+    pub fn list_entries(&self) -> Vec<ArchiveEntry> {
+        self.split_apks.list_entries()
+    }
+
+    /// Reads the binary `AndroidManifest.xml` of the base APK.
+    pub fn read_base_manifest(&mut self) -> Result<Vec<u8>> {
+        self.split_apks.read_base_manifest()
+    }
+}
+
+/// Implements the AndroidArchive trait for ApksFile.
+///
+/// This allows an ApksFile to be used wherever an AndroidArchive is expected.
+impl AndroidArchive for ApksFile {
+    /// Reads an internal file from the split APK bundle.
+    ///
+    /// Delegates to the `read_internal_file` method of `ApksFile`.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_path` - The path of the file within the split APKs.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` containing the file's contents on success.
+    /// * `Err` if an error occurs while reading the file.
+    fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
+        ApksFile::read_internal_file(self, internal_path)
+    }
+
+    /// Lists every file contained within the split APK bundle.
+    ///
+    /// Delegates to the `list_entries` method of `ApksFile`.
+    fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>> {
+        Ok(ApksFile::list_entries(self))
+    }
+
+    /// Reads the manifest of the base APK rather than that of a configuration split.
+    ///
+    /// Delegates to the `read_base_manifest` method of `ApksFile`.
+    fn read_base_manifest(&mut self) -> Result<Vec<u8>> {
+        ApksFile::read_base_manifest(self)
+    }
+}
@@ -0,0 +1,337 @@
+use anyhow::{anyhow, bail, Result};
+
+/// Chunk type of a binary XML document.
+const RES_XML_TYPE: u16 = 0x0003;
+/// Chunk type of a string pool.
+const RES_STRING_POOL_TYPE: u16 = 0x0001;
+/// Chunk type of the resource ID map, which maps attribute name strings to resource IDs.
+const RES_XML_RESOURCE_MAP_TYPE: u16 = 0x0180;
+/// Chunk type of an element start tag.
+const RES_XML_START_ELEMENT_TYPE: u16 = 0x0102;
+/// Chunk type of an element end tag.
+const RES_XML_END_ELEMENT_TYPE: u16 = 0x0103;
+/// String pool flag indicating that strings are encoded as UTF-8 rather than UTF-16.
+const UTF8_FLAG: u32 = 1 << 8;
+/// String index meaning "no string".
+const NO_INDEX: u32 = u32::MAX;
+/// Size of a chunk header (type, header size and chunk size).
+const CHUNK_HEADER_LEN: usize = 8;
+/// Size of an XML node header (chunk header, line number and comment).
+const XML_NODE_HEADER_LEN: usize = 16;
+
+/// Value type of a reference to another resource.
+const TYPE_REFERENCE: u8 = 0x01;
+/// Value type of a string.
+const TYPE_STRING: u8 = 0x03;
+/// Value type of a decimal integer.
+const TYPE_INT_DEC: u8 = 0x10;
+/// Value type of a hexadecimal integer.
+const TYPE_INT_HEX: u8 = 0x11;
+/// Value type of a boolean.
+const TYPE_INT_BOOLEAN: u8 = 0x12;
+
+/// The typed value of an attribute in a binary XML document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    /// A string value.
+    String(String),
+    /// An integer value, written in decimal or hexadecimal in the source XML.
+    Int(u32),
+    /// A boolean value.
+    Bool(bool),
+    /// A reference to another resource (`@type/name`), which cannot be resolved without the
+    /// resource table.
+    Reference(u32),
+    /// Any other value type (dimensions, colors, ...), kept as its raw type and data.
+    Other {
+        /// The `Res_value` data type.
+        data_type: u8,
+        /// The raw data.
+        data: u32,
+    },
+}
+
+impl AttributeValue {
+    /// Returns the value as a string, formatting non-string values the way `aapt` prints them.
+    pub fn to_display_string(&self) -> String {
+        match self {
+            AttributeValue::String(value) => value.clone(),
+            AttributeValue::Int(value) => value.to_string(),
+            AttributeValue::Bool(value) => value.to_string(),
+            AttributeValue::Reference(id) => format!("@0x{:08X}", id),
+            AttributeValue::Other { data_type, data } => {
+                format!("(type 0x{:02X})0x{:X}", data_type, data)
+            }
+        }
+    }
+
+    /// Returns the value as an integer, parsing string values if necessary.
+    pub fn as_int(&self) -> Option<u32> {
+        match self {
+            AttributeValue::Int(value) => Some(*value),
+            AttributeValue::String(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+/// An attribute of an element in a binary XML document.
+#[derive(Debug, Clone)]
+pub struct XmlAttribute {
+    /// The namespace URI of the attribute, if any.
+    pub namespace: Option<String>,
+    /// The attribute name, which may be empty in obfuscated documents.
+    pub name: String,
+    /// The Android resource ID of the attribute (e.g. `0x0101021B` for `android:versionCode`).
+    pub resource_id: Option<u32>,
+    /// The typed value of the attribute.
+    pub value: AttributeValue,
+}
+
+/// An element of a binary XML document.
+#[derive(Debug, Clone)]
+pub struct XmlElement {
+    /// The element name.
+    pub name: String,
+    /// The nesting depth of the element, where the root element has depth 0.
+    pub depth: usize,
+    /// The attributes of the element, in document order.
+    pub attributes: Vec<XmlAttribute>,
+}
+
+impl XmlElement {
+    /// Looks up an attribute by its Android resource ID, falling back to its name.
+    ///
+    /// Resource IDs identify framework attributes even if the attribute names were stripped by
+    /// an obfuscator, so they are preferred over names.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The attribute name without namespace prefix (e.g. `versionCode`).
+    /// * `resource_id` - The resource ID of the attribute, if it is a framework attribute.
+    pub fn attribute(&self, name: &str, resource_id: Option<u32>) -> Option<&AttributeValue> {
+        resource_id
+            .and_then(|id| {
+                self.attributes
+                    .iter()
+                    .find(|attribute| attribute.resource_id == Some(id))
+            })
+            .or_else(|| {
+                self.attributes
+                    .iter()
+                    .find(|attribute| attribute.name == name)
+            })
+            .map(|attribute| &attribute.value)
+    }
+}
+
+/// A decoded binary XML document, such as a compiled `AndroidManifest.xml`.
+///
+/// Only the elements and their attributes are decoded, flattened in document order. Text
+/// content, comments and namespace declarations are skipped.
+#[derive(Debug, Clone)]
+pub struct AxmlDocument {
+    /// The elements of the document in document order.
+    pub elements: Vec<XmlElement>,
+}
+
+impl AxmlDocument {
+    /// Decodes a binary XML document.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The binary XML data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a binary XML document or is truncated.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (chunk_type, header_len, chunk_len) = read_chunk_header(data, 0)?;
+        if chunk_type != RES_XML_TYPE {
+            bail!("Not a binary XML document");
+        }
+        let end = chunk_len.min(data.len());
+
+        let mut strings = Vec::new();
+        let mut resource_ids: &[u8] = &[];
+        let mut elements = Vec::new();
+        let mut depth = 0;
+        let mut offset = header_len;
+        while offset + CHUNK_HEADER_LEN <= end {
+            let (chunk_type, header_len, chunk_len) = read_chunk_header(data, offset)?;
+            if chunk_len < CHUNK_HEADER_LEN || offset + chunk_len > end {
+                bail!("Invalid chunk at offset {:#X}", offset);
+            }
+            let chunk = &data[offset..offset + chunk_len];
+            match chunk_type {
+                RES_STRING_POOL_TYPE => strings = read_string_pool(chunk)?,
+                RES_XML_RESOURCE_MAP_TYPE => resource_ids = &chunk[header_len..],
+                RES_XML_START_ELEMENT_TYPE => {
+                    let resource_id = |index: u32| {
+                        let start = index as usize * 4;
+                        resource_ids.get(start..start + 4).map(read_u32_unchecked)
+                    };
+                    elements.push(read_start_element(chunk, depth, &strings, resource_id)?);
+                    depth += 1;
+                }
+                RES_XML_END_ELEMENT_TYPE => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            offset += chunk_len;
+        }
+
+        Ok(AxmlDocument { elements })
+    }
+
+    /// Returns the root element of the document.
+    pub fn root(&self) -> Option<&XmlElement> {
+        self.elements.first()
+    }
+
+    /// Returns an iterator over all elements with the given name.
+    pub fn find<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> + 'a {
+        self.elements
+            .iter()
+            .filter(move |element| element.name == name)
+    }
+}
+
+/// Reads a chunk header, returning the chunk type, header size and chunk size.
+fn read_chunk_header(data: &[u8], offset: usize) -> Result<(u16, usize, usize)> {
+    let chunk_type = read_u16(data, offset)?;
+    let header_len = read_u16(data, offset + 2)? as usize;
+    let chunk_len = read_u32(data, offset + 4)? as usize;
+    Ok((chunk_type, header_len, chunk_len))
+}
+
+/// Decodes all strings of a string pool chunk.
+fn read_string_pool(chunk: &[u8]) -> Result<Vec<String>> {
+    let string_count = read_u32(chunk, 8)? as usize;
+    let flags = read_u32(chunk, 16)?;
+    let strings_start = read_u32(chunk, 20)? as usize;
+    let header_len = read_u16(chunk, 2)? as usize;
+
+    (0..string_count)
+        .map(|i| {
+            let offset = strings_start + read_u32(chunk, header_len + i * 4)? as usize;
+            if flags & UTF8_FLAG != 0 {
+                read_utf8_string(chunk, offset)
+            } else {
+                read_utf16_string(chunk, offset)
+            }
+        })
+        .collect()
+}
+
+/// Decodes a UTF-8 string pool entry: its UTF-16 length, its byte length and the bytes.
+fn read_utf8_string(chunk: &[u8], offset: usize) -> Result<String> {
+    // Lengths above 0x7F take two bytes, flagged by the high bit of the first one.
+    let read_len = |offset: usize| -> Result<(usize, usize)> {
+        let first = *chunk.get(offset).ok_or(anyhow!("String pool truncated"))? as usize;
+        if first & 0x80 != 0 {
+            let second = *chunk
+                .get(offset + 1)
+                .ok_or(anyhow!("String pool truncated"))?;
+            Ok((((first & 0x7F) << 8) | second as usize, 2))
+        } else {
+            Ok((first, 1))
+        }
+    };
+    let (_, utf16_len_size) = read_len(offset)?;
+    let (byte_len, byte_len_size) = read_len(offset + utf16_len_size)?;
+    let start = offset + utf16_len_size + byte_len_size;
+    let bytes = chunk
+        .get(start..start + byte_len)
+        .ok_or(anyhow!("String pool truncated"))?;
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Decodes a UTF-16 string pool entry: its length in code units and the code units.
+fn read_utf16_string(chunk: &[u8], offset: usize) -> Result<String> {
+    // Lengths above 0x7FFF take two code units, flagged by the high bit of the first one.
+    let first = read_u16(chunk, offset)? as usize;
+    let (len, start) = if first & 0x8000 != 0 {
+        let second = read_u16(chunk, offset + 2)? as usize;
+        (((first & 0x7FFF) << 16) | second, offset + 4)
+    } else {
+        (first, offset + 2)
+    };
+    let units = (0..len)
+        .map(|i| read_u16(chunk, start + i * 2))
+        .collect::<Result<Vec<u16>>>()?;
+    Ok(String::from_utf16_lossy(&units))
+}
+
+/// Decodes an element start chunk with its attributes.
+fn read_start_element(
+    chunk: &[u8],
+    depth: usize,
+    strings: &[String],
+    resource_id: impl Fn(u32) -> Option<u32>,
+) -> Result<XmlElement> {
+    let string = |index: u32| -> Option<String> {
+        (index != NO_INDEX)
+            .then(|| strings.get(index as usize).cloned())
+            .flatten()
+    };
+
+    let ext = XML_NODE_HEADER_LEN;
+    let name = string(read_u32(chunk, ext + 4)?).unwrap_or_default();
+    let attribute_start = read_u16(chunk, ext + 8)? as usize;
+    let attribute_size = read_u16(chunk, ext + 10)? as usize;
+    let attribute_count = read_u16(chunk, ext + 12)? as usize;
+
+    let mut attributes = Vec::with_capacity(attribute_count);
+    for i in 0..attribute_count {
+        let offset = ext + attribute_start + i * attribute_size;
+        let name_index = read_u32(chunk, offset + 4)?;
+        let raw_value = read_u32(chunk, offset + 8)?;
+        let data_type = *chunk
+            .get(offset + 15)
+            .ok_or(anyhow!("Attribute truncated"))?;
+        let data = read_u32(chunk, offset + 16)?;
+
+        let value = match data_type {
+            TYPE_STRING => AttributeValue::String(string(data).unwrap_or_default()),
+            TYPE_INT_DEC | TYPE_INT_HEX => AttributeValue::Int(data),
+            TYPE_INT_BOOLEAN => AttributeValue::Bool(data != 0),
+            TYPE_REFERENCE => AttributeValue::Reference(data),
+            // Some tools only fill in the raw string value.
+            _ if raw_value != NO_INDEX => {
+                AttributeValue::String(string(raw_value).unwrap_or_default())
+            }
+            _ => AttributeValue::Other { data_type, data },
+        };
+        attributes.push(XmlAttribute {
+            namespace: string(read_u32(chunk, offset)?),
+            name: string(name_index).unwrap_or_default(),
+            resource_id: resource_id(name_index),
+            value,
+        });
+    }
+
+    Ok(XmlElement {
+        name,
+        depth,
+        attributes,
+    })
+}
+
+/// Reads a little-endian `u16` at the given offset.
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(anyhow!("Binary XML truncated at offset {:#X}", offset))
+}
+
+/// Reads a little-endian `u32` at the given offset.
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(read_u32_unchecked)
+        .ok_or(anyhow!("Binary XML truncated at offset {:#X}", offset))
+}
+
+/// Reads a little-endian `u32` from a slice of at least four bytes.
+fn read_u32_unchecked(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
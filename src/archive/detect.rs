@@ -0,0 +1,208 @@
+use crate::archive::manifest::ANDROID_MANIFEST_PATH;
+use crate::unity::global_metadata::GLOBAL_METADATA_MAGIC;
+use crate::utils::consts::GLOBAL_METADATA_PATH;
+use anyhow::{bail, Result};
+use log::info;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use zip::read::ZipArchive;
+
+/// Magic bytes at the start of a ZIP local file header.
+const ZIP_MAGIC: &[u8; 4] = b"PK\x03\x04";
+/// Magic bytes at the start of an empty ZIP archive (end of central directory record).
+const ZIP_EMPTY_MAGIC: &[u8; 4] = b"PK\x05\x06";
+/// Magic bytes at the start of an ELF binary.
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+
+/// The kind of an input file, determined by inspecting its contents rather than its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// A plain APK: a ZIP archive with an `AndroidManifest.xml` at its root.
+    Apk,
+    /// An XAPK: a ZIP archive with a `manifest.json` describing nested split APKs.
+    XApk,
+    /// A split APK bundle (`.apks`, `.apkm`): a ZIP archive of nested split APKs without a manifest.
+    SplitApks,
+    /// An APK that has already been unpacked into a directory.
+    Directory,
+    /// A raw IL2CPP binary (`libil2cpp.so`).
+    Il2Cpp,
+    /// A global metadata file (`global-metadata.dat`).
+    GlobalMetadata {
+        /// Whether the metadata is still encrypted.
+        encrypted: bool,
+    },
+}
+
+impl InputKind {
+    /// Returns `true` if this kind is an archive that can be opened as an `AndroidArchive`.
+    pub fn is_archive(&self) -> bool {
+        matches!(
+            self,
+            InputKind::Apk | InputKind::XApk | InputKind::SplitApks | InputKind::Directory
+        )
+    }
+}
+
+impl Display for InputKind {
+    /// Formats the input kind as a human-readable description.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputKind::Apk => write!(f, "APK archive"),
+            InputKind::XApk => write!(f, "XAPK archive"),
+            InputKind::SplitApks => write!(f, "split APK bundle"),
+            InputKind::Directory => write!(f, "unpacked APK directory"),
+            InputKind::Il2Cpp => write!(f, "IL2CPP binary"),
+            InputKind::GlobalMetadata { encrypted: true } => {
+                write!(f, "encrypted global metadata")
+            }
+            InputKind::GlobalMetadata { encrypted: false } => write!(f, "global metadata"),
+        }
+    }
+}
+
+/// Determines the kind of an input file by inspecting its contents.
+///
+/// The detection works as follows:
+/// 1. A directory is an unpacked APK if it contains an `AndroidManifest.xml` or the global
+///    metadata file.
+/// 2. An ELF magic identifies a raw IL2CPP binary.
+/// 3. A ZIP magic identifies an archive, which is then classified by its entries:
+///    - `manifest.json` alongside nested `*.apk` entries is an XAPK,
+///    - nested `*.apk` entries without an `AndroidManifest.xml` is a split APK bundle,
+///    - an `AndroidManifest.xml` is a plain APK.
+/// 4. The global metadata sanity value identifies a plain global metadata file.
+/// 5. A little-endian length prefix matching the remaining file size identifies an encrypted
+///    global metadata file. This is checked last, as any file can match it by chance.
+///
+/// # Arguments
+///
+/// * `path` - The file system path of the input file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or its contents match none of the known kinds.
+pub fn detect_input_kind<P: AsRef<Path>>(path: P) -> Result<InputKind> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        if !path.join(ANDROID_MANIFEST_PATH).is_file() && !path.join(GLOBAL_METADATA_PATH).is_file()
+        {
+            bail!(
+                "{} is a directory but does not look like an unpacked APK",
+                path.display()
+            );
+        }
+        return Ok(InputKind::Directory);
+    }
+
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+
+    // Read the first four bytes, which hold every magic value we recognize.
+    let mut magic = [0u8; 4];
+    if file.read_exact(&mut magic).is_err() {
+        bail!("{} is too small to be a valid input file", path.display());
+    }
+
+    if &magic == ELF_MAGIC {
+        return Ok(InputKind::Il2Cpp);
+    }
+    if &magic == ZIP_MAGIC || &magic == ZIP_EMPTY_MAGIC {
+        return classify_zip(path);
+    }
+    if i32::from_le_bytes(magic) == GLOBAL_METADATA_MAGIC {
+        return Ok(InputKind::GlobalMetadata { encrypted: false });
+    }
+    // Encrypted metadata starts with the length of the ciphertext that follows it.
+    if u32::from_le_bytes(magic) as u64 == file_len - 4 {
+        return Ok(InputKind::GlobalMetadata { encrypted: true });
+    }
+    bail!("{} is not a recognized input file", path.display())
+}
+
+/// Classifies a ZIP archive as an APK, XAPK or split APK bundle by the entries it contains.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read or matches none of these kinds.
+fn classify_zip(path: &Path) -> Result<InputKind> {
+    let archive = ZipArchive::new(File::open(path)?)?;
+    let mut has_manifest_json = false;
+    let mut has_android_manifest = false;
+    let mut has_nested_apks = false;
+    for name in archive.file_names() {
+        match name {
+            "manifest.json" => has_manifest_json = true,
+            "AndroidManifest.xml" => has_android_manifest = true,
+            _ if name.ends_with(".apk") => has_nested_apks = true,
+            _ => {}
+        }
+    }
+
+    match (has_manifest_json, has_android_manifest, has_nested_apks) {
+        (true, _, true) => Ok(InputKind::XApk),
+        (false, false, true) => Ok(InputKind::SplitApks),
+        (_, true, _) => Ok(InputKind::Apk),
+        _ => bail!(
+            "{} is a ZIP archive but does not look like an APK, XAPK or split APK bundle",
+            path.display()
+        ),
+    }
+}
+
+/// Reads a directly provided IL2CPP binary and global metadata file.
+///
+/// Both files are identified by their contents and the result is reported. If the two paths have
+/// been swapped, they are read the other way around instead of failing later with a confusing
+/// parsing error.
+///
+/// # Arguments
+///
+/// * `il2cpp_path` - The path given as the IL2CPP binary.
+/// * `global_metadata_path` - The path given as the global metadata file.
+///
+/// # Returns
+///
+/// A tuple `(il2cpp_data, global_metadata_data)`.
+///
+/// # Errors
+///
+/// Returns an error if either file cannot be read, or if the pair is not one IL2CPP binary and
+/// one global metadata file.
+pub fn read_direct_inputs(
+    il2cpp_path: &Path,
+    global_metadata_path: &Path,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let il2cpp_kind = detect_input_kind(il2cpp_path)?;
+    let global_metadata_kind = detect_input_kind(global_metadata_path)?;
+
+    let (il2cpp_path, global_metadata_path, metadata_kind) = match (
+        il2cpp_kind,
+        global_metadata_kind,
+    ) {
+        (InputKind::Il2Cpp, metadata_kind @ InputKind::GlobalMetadata { .. }) => {
+            (il2cpp_path, global_metadata_path, metadata_kind)
+        }
+        (metadata_kind @ InputKind::GlobalMetadata { .. }, InputKind::Il2Cpp) => {
+            info!("IL2CPP and global metadata inputs are swapped, using them the other way around");
+            (global_metadata_path, il2cpp_path, metadata_kind)
+        }
+        (il2cpp_kind, global_metadata_kind) => bail!(
+            "Expected an IL2CPP binary and a global metadata file, but found {} ({}) and {} ({})",
+            il2cpp_kind,
+            il2cpp_path.display(),
+            global_metadata_kind,
+            global_metadata_path.display()
+        ),
+    };
+
+    info!("Using IL2CPP binary: {}", il2cpp_path.display());
+    info!(
+        "Using {}: {}",
+        metadata_kind,
+        global_metadata_path.display()
+    );
+    Ok((fs::read(il2cpp_path)?, fs::read(global_metadata_path)?))
+}
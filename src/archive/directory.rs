@@ -0,0 +1,114 @@
+use crate::archive::{entry_output_path, AndroidArchive, ArchiveEntry};
+use anyhow::{bail, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Represents an APK that has already been unpacked into a directory.
+///
+/// Internal paths such as [`crate::utils::consts::GLOBAL_METADATA_PATH`] and the IL2CPP binary
+/// path of an ABI are resolved relative to the root directory, exactly as they appear within the
+/// APK. This avoids unzipping the same APK over and over when it is processed repeatedly.
+pub struct ApkDirectory {
+    /// The root directory of the unpacked APK.
+    root: PathBuf,
+}
+
+impl ApkDirectory {
+    /// Opens an unpacked APK directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The root directory of the unpacked APK.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not a directory.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let root = path.as_ref().to_path_buf();
+        if !root.is_dir() {
+            bail!("{} is not a directory", root.display());
+        }
+        Ok(Self { root })
+    }
+
+    /// Reads an internal file (by its path relative to the root directory) into a vector of bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_path` - The path of the file as it appears within the APK (for example,
+    ///   `"AndroidManifest.xml"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path would escape the root directory, or if the file does not
+    /// exist or cannot be read.
+    pub fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
+        let path = entry_output_path(&self.root, internal_path)?;
+        if !path.is_file() {
+            bail!("{} not found in {}", internal_path, self.root.display());
+        }
+        Ok(fs::read(path)?)
+    }
+
+    /// Lists every file beneath the root directory, sorted by path.
+    ///
+    /// Paths are reported relative to the root directory with `/` separators, matching the paths
+    /// within an APK. Files are not compressed, so the compressed size equals the size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory cannot be read.
+    pub fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>> {
+        let mut entries = Vec::new();
+        let mut pending = vec![(self.root.clone(), String::new())];
+        while let Some((dir, prefix)) = pending.pop() {
+            for dir_entry in fs::read_dir(&dir)? {
+                let dir_entry = dir_entry?;
+                let name = dir_entry.file_name().to_string_lossy().into_owned();
+                let path = format!("{prefix}{name}");
+                let file_type = dir_entry.file_type()?;
+                if file_type.is_dir() {
+                    pending.push((dir_entry.path(), format!("{path}/")));
+                } else if file_type.is_file() {
+                    let size = dir_entry.metadata()?.len();
+                    entries.push(ArchiveEntry {
+                        path,
+                        size,
+                        compressed_size: size,
+                        split_apk: None,
+                    });
+                }
+            }
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(entries)
+    }
+}
+
+/// Implements the AndroidArchive trait for ApkDirectory.
+///
+/// This allows an unpacked APK directory to be used wherever an AndroidArchive is expected.
+impl AndroidArchive for ApkDirectory {
+    /// Reads an internal file from the unpacked APK directory.
+    ///
+    /// Delegates to the `read_internal_file` method of `ApkDirectory`.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_path` - The path of the file relative to the root directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` containing the file's contents on success.
+    /// * `Err` if an error occurs while reading the file.
+    fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
+        ApkDirectory::read_internal_file(self, internal_path)
+    }
+
+    /// Lists every file contained within the unpacked APK directory.
+    ///
+    /// Delegates to the `list_entries` method of `ApkDirectory`.
+    fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>> {
+        ApkDirectory::list_entries(self)
+    }
+}
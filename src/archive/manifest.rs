@@ -0,0 +1,114 @@
+use crate::archive::axml::AxmlDocument;
+use crate::binary::abi::Abi;
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use log::info;
+
+/// Path of the binary Android manifest within an APK.
+pub const ANDROID_MANIFEST_PATH: &str = "AndroidManifest.xml";
+
+/// Resource ID of the `android:versionCode` attribute.
+const ATTR_VERSION_CODE: u32 = 0x0101_021B;
+/// Resource ID of the `android:versionName` attribute.
+const ATTR_VERSION_NAME: u32 = 0x0101_021C;
+/// Resource ID of the `android:minSdkVersion` attribute.
+const ATTR_MIN_SDK_VERSION: u32 = 0x0101_020C;
+/// Resource ID of the `android:targetSdkVersion` attribute.
+const ATTR_TARGET_SDK_VERSION: u32 = 0x0101_0270;
+
+/// Identifies an app build, as declared by its `AndroidManifest.xml` and native libraries.
+#[derive(Debug, Clone)]
+pub struct ManifestInfo {
+    /// The application package name (e.g. `jp.pokemon.pokemontcgp`).
+    pub package_name: String,
+    /// The user-visible version (e.g. `1.2.0`), if declared.
+    pub version_name: Option<String>,
+    /// The internal version number, if declared.
+    pub version_code: Option<u32>,
+    /// The minimum supported Android API level, if declared.
+    pub min_sdk_version: Option<u32>,
+    /// The Android API level the app targets, if declared.
+    pub target_sdk_version: Option<u32>,
+    /// The ABIs the archive ships native libraries for, in order of preference.
+    pub abis: Vec<Abi>,
+}
+
+impl ManifestInfo {
+    /// Builds the manifest information from a binary `AndroidManifest.xml`.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The decoded manifest of the base APK.
+    /// * `abis` - The ABIs the archive ships native libraries for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest has no root `<manifest>` element with a package name.
+    pub fn from_manifest(manifest: &AxmlDocument, abis: Vec<Abi>) -> Result<Self> {
+        let root = manifest
+            .root()
+            .filter(|root| root.name == "manifest")
+            .ok_or(anyhow!("AndroidManifest.xml has no <manifest> element"))?;
+        let package_name = root
+            .attribute("package", None)
+            .map(|value| value.to_display_string())
+            .ok_or(anyhow!(
+                "AndroidManifest.xml does not declare a package name"
+            ))?;
+        let uses_sdk = manifest.find("uses-sdk").next();
+
+        Ok(ManifestInfo {
+            package_name,
+            version_name: root
+                .attribute("versionName", Some(ATTR_VERSION_NAME))
+                .map(|value| value.to_display_string()),
+            version_code: root
+                .attribute("versionCode", Some(ATTR_VERSION_CODE))
+                .and_then(|value| value.as_int()),
+            min_sdk_version: uses_sdk
+                .and_then(|element| element.attribute("minSdkVersion", Some(ATTR_MIN_SDK_VERSION)))
+                .and_then(|value| value.as_int()),
+            target_sdk_version: uses_sdk
+                .and_then(|element| {
+                    element.attribute("targetSdkVersion", Some(ATTR_TARGET_SDK_VERSION))
+                })
+                .and_then(|value| value.as_int()),
+            abis,
+        })
+    }
+
+    /// Logs a summary of the build.
+    pub fn log_summary(&self) {
+        let unknown = || "unknown".to_string();
+        info!("Package: {}", self.package_name);
+        info!(
+            "Version: {} (code {})",
+            self.version_name.clone().unwrap_or_else(unknown),
+            self.version_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(unknown)
+        );
+        info!(
+            "SDK: min {}, target {}",
+            self.min_sdk_version
+                .map(|sdk| sdk.to_string())
+                .unwrap_or_else(unknown),
+            self.target_sdk_version
+                .map(|sdk| sdk.to_string())
+                .unwrap_or_else(unknown)
+        );
+        if self.abis.is_empty() {
+            info!("Native ABIs: none");
+        } else {
+            info!("Native ABIs: {}", self.abis.iter().join(", "));
+        }
+    }
+}
+
+/// Returns `true` if the manifest belongs to a configuration or feature split rather than the
+/// base APK, which is indicated by a `split` attribute on the root element.
+pub fn is_split_manifest(manifest: &AxmlDocument) -> bool {
+    manifest
+        .root()
+        .is_some_and(|root| root.attribute("split", None).is_some())
+}
@@ -1,17 +1,49 @@
 #![allow(dead_code)]
 
 use crate::archive::apk::ApkFile;
+use crate::archive::apks::ApksFile;
+use crate::archive::axml::AxmlDocument;
+use crate::archive::detect::{detect_input_kind, InputKind};
+use crate::archive::directory::ApkDirectory;
+use crate::archive::manifest::{ManifestInfo, ANDROID_MANIFEST_PATH};
 use crate::archive::xapk::XApkFile;
+use crate::binary::abi::Abi;
 use anyhow::{bail, Result};
-use std::path::Path;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use log::info;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use strum::IntoEnumIterator;
 
 mod apk;
+mod apks;
+pub mod axml;
+pub mod detect;
+mod directory;
+pub mod manifest;
+pub mod repack;
+pub mod sign;
+mod split_apks;
 mod xapk;
 
+/// Describes a single file contained within an Android archive.
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    /// The relative path of the file within the archive (e.g., `lib/arm64-v8a/libil2cpp.so`).
+    pub path: String,
+    /// The uncompressed size of the file in bytes.
+    pub size: u64,
+    /// The compressed size of the file in bytes, as stored in the archive.
+    pub compressed_size: u64,
+    /// The filename of the split APK that provides the file, if the archive contains split APKs.
+    pub split_apk: Option<String>,
+}
+
 /// Trait representing a generic Android archive.
 ///
-/// This trait abstracts operations common to Android archive formats (e.g., APK and XAPK).
-/// Implementations of this trait are expected to provide functionality to read files contained within the archive.
+/// This trait abstracts operations common to Android archive formats (e.g., APK, XAPK and split APK bundles)
+/// and to APKs that have already been unpacked into a directory.
+/// Implementations of this trait are expected to provide functionality to list and read files contained within the archive.
 pub trait AndroidArchive {
     /// Reads an internal file from the archive, returning its bytes.
     ///
@@ -24,12 +56,198 @@ pub trait AndroidArchive {
     /// A [`Result`] containing a vector of bytes if the file is read successfully,
     /// or an error if the file cannot be read.
     fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>>;
+
+    /// Lists every file contained within the archive, sorted by path.
+    ///
+    /// For archives containing split APKs, the files of all split APKs are listed as a single tree,
+    /// using the same override rules as [`AndroidArchive::read_internal_file`]. Directory entries
+    /// are omitted.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing the list of [`ArchiveEntry`] descriptions, or an error if the archive
+    /// cannot be read.
+    fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>>;
+
+    /// Reads the binary `AndroidManifest.xml` of the base APK.
+    ///
+    /// Archives containing split APKs override this, since every split APK has its own manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be found or read.
+    fn read_base_manifest(&mut self) -> Result<Vec<u8>> {
+        self.read_internal_file(ANDROID_MANIFEST_PATH)
+    }
+
+    /// Lists the ABIs the archive ships native libraries for, in order of preference.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be listed.
+    fn native_abis(&mut self) -> Result<Vec<Abi>> {
+        let entries = self.list_entries()?;
+        Ok(Abi::iter()
+            .filter(|abi| {
+                let lib_dir = format!("lib/{}/", abi.name());
+                entries
+                    .iter()
+                    .any(|entry| entry.path.starts_with(&lib_dir) && entry.path.ends_with(".so"))
+            })
+            .collect())
+    }
+
+    /// Identifies the build contained in the archive from its base `AndroidManifest.xml` and
+    /// native libraries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or decoded.
+    fn manifest_info(&mut self) -> Result<ManifestInfo> {
+        let manifest = AxmlDocument::parse(&self.read_base_manifest()?)?;
+        ManifestInfo::from_manifest(&manifest, self.native_abis()?)
+    }
+
+    /// Lists the files whose paths match any of the given glob patterns.
+    ///
+    /// Patterns follow the usual glob syntax, where `*` does not cross a `/` and `**` matches any
+    /// number of directories. A pattern ending with `/` matches everything beneath that directory,
+    /// so `assets/` is equivalent to `assets/**`. An empty pattern list matches every file.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - The glob patterns to match internal paths against.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is invalid or the archive cannot be listed.
+    fn find_entries(&mut self, patterns: &[String]) -> Result<Vec<ArchiveEntry>> {
+        let entries = self.list_entries()?;
+        if patterns.is_empty() {
+            return Ok(entries);
+        }
+        let glob_set = build_glob_set(patterns)?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| glob_set.is_match(&entry.path))
+            .collect())
+    }
+
+    /// Extracts a single file from the archive into an output directory, preserving its relative path.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The entry to extract, as returned by [`AndroidArchive::list_entries`].
+    /// * `output_dir` - The directory to extract the file into.
+    ///
+    /// # Returns
+    ///
+    /// The path of the written file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry path would escape the output directory, or if reading the
+    /// entry or writing the file fails.
+    fn extract_entry(&mut self, entry: &ArchiveEntry, output_dir: &Path) -> Result<PathBuf> {
+        let output_path = entry_output_path(output_dir, &entry.path)?;
+        let data = self.read_internal_file(&entry.path)?;
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&output_path, data)?;
+        Ok(output_path)
+    }
+
+    /// Selects the ABI whose IL2CPP binary is read from the archive.
+    ///
+    /// For archives containing split APKs, the native libraries usually live in a separate
+    /// configuration split, so all split APKs are considered. If no ABI is requested, the first
+    /// ABI in order of preference (see [`Abi`]) that has an IL2CPP binary is selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `requested` - The ABI explicitly requested by the user, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be listed, the requested ABI has no IL2CPP binary, or
+    /// the archive contains no IL2CPP binary at all.
+    fn resolve_abi(&mut self, requested: Option<Abi>) -> Result<Abi> {
+        let entries = self.list_entries()?;
+        let available: Vec<Abi> = Abi::iter()
+            .filter(|abi| {
+                let il2cpp_path = abi.il2cpp_path();
+                entries.iter().any(|entry| entry.path == il2cpp_path)
+            })
+            .collect();
+
+        match requested {
+            Some(abi) if available.contains(&abi) => Ok(abi),
+            _ if available.is_empty() => bail!("The archive does not contain an IL2CPP binary"),
+            Some(abi) => bail!(
+                "The archive does not contain an IL2CPP binary for {}. Available ABIs: {}",
+                abi,
+                available
+                    .iter()
+                    .map(Abi::name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            None => {
+                let abi = available[0];
+                info!("Detected ABI: {}", abi);
+                Ok(abi)
+            }
+        }
+    }
+}
+
+/// Compiles a list of glob patterns into a single [`GlobSet`].
+///
+/// A trailing `/` is expanded to `/**` so that a bare directory matches all of its contents.
+///
+/// # Errors
+///
+/// Returns an error if any of the patterns is not a valid glob.
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = if pattern.ends_with('/') {
+            format!("{pattern}**")
+        } else {
+            pattern.clone()
+        };
+        builder.add(GlobBuilder::new(&pattern).literal_separator(true).build()?);
+    }
+    Ok(builder.build()?)
 }
 
-/// Opens an Android archive file and returns an object that implements [`AndroidArchive`].
+/// Resolves the output path for an internal archive path beneath an output directory.
 ///
-/// The archive type is determined based on the file extension provided in the path.
-/// Currently, only files with `.apk` and `.xapk` extensions are supported.
+/// Archive paths come from untrusted input, so only plain path components are accepted. Absolute
+/// paths and `..` components are rejected to prevent writing outside the output directory.
+///
+/// # Errors
+///
+/// Returns an error if the internal path contains anything other than plain path components.
+fn entry_output_path(output_dir: &Path, internal_path: &str) -> Result<PathBuf> {
+    let mut output_path = output_dir.to_path_buf();
+    for component in Path::new(internal_path).components() {
+        match component {
+            Component::Normal(part) => output_path.push(part),
+            Component::CurDir => {}
+            _ => bail!("Refusing to extract unsafe archive path: {}", internal_path),
+        }
+    }
+    Ok(output_path)
+}
+
+/// Opens an Android archive file or unpacked APK directory and returns an object that implements
+/// [`AndroidArchive`].
+///
+/// The archive type is determined by inspecting the file contents (see [`detect_input_kind`]),
+/// so renamed files (`.zip`, `.apk.1`, no extension) are opened correctly, and an XAPK saved with
+/// an `.apk` extension is still opened as an XAPK. The detected archive type is reported.
 ///
 /// # Type Parameters
 ///
@@ -37,27 +255,46 @@ pub trait AndroidArchive {
 ///
 /// # Arguments
 ///
-/// * `path` - The file system path to the Android archive.
+/// * `path` - The file system path to the Android archive or unpacked APK directory.
 ///
 /// # Returns
 ///
-/// A boxed trait object implementing [`AndroidArchive`] if the file extension is supported.
+/// A boxed trait object implementing [`AndroidArchive`] if the file is a supported archive.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The file extension is not supported.
+/// - The file is not a supported archive (raw IL2CPP and metadata files must be provided directly).
 /// - There is an issue opening the archive using the corresponding handler.
 pub fn open_archive<P: AsRef<Path>>(path: P) -> Result<Box<dyn AndroidArchive>> {
-    match path.as_ref().extension().and_then(|s| s.to_str()) {
-        Some("apk") => {
+    let path = path.as_ref();
+    let kind = detect_input_kind(path)?;
+    if !kind.is_archive() {
+        bail!(
+            "{} is a {}, not an APK, XAPK, split APK bundle or unpacked APK directory",
+            path.display(),
+            kind
+        );
+    }
+    info!("Detected {}: {}", kind, path.display());
+
+    match kind {
+        InputKind::Apk => {
             let apk = ApkFile::open(path)?;
             Ok(Box::new(apk))
         }
-        Some("xapk") => {
+        InputKind::XApk => {
             let xapk = XApkFile::open(path)?;
             Ok(Box::new(xapk))
         }
-        _ => bail!("Unsupported file extension"),
+        InputKind::SplitApks => {
+            let apks = ApksFile::open(path)?;
+            Ok(Box::new(apks))
+        }
+        InputKind::Directory => {
+            let directory = ApkDirectory::open(path)?;
+            Ok(Box::new(directory))
+        }
+        InputKind::Il2Cpp | InputKind::GlobalMetadata { .. } => unreachable!(),
     }
 }
@@ -0,0 +1,582 @@
+use crate::archive::detect::{detect_input_kind, InputKind};
+use crate::archive::open_archive;
+use anyhow::{anyhow, bail, Result};
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use hashbrown::{HashMap, HashSet};
+use log::{debug, info};
+use std::fs::File;
+use std::io::{self, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use zip::read::ZipArchive;
+use zip::CompressionMethod;
+
+/// Signature of a ZIP local file header.
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+/// Signature of a ZIP central directory file header.
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+/// Signature of a ZIP end of central directory record.
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+/// Optional signature preceding a ZIP data descriptor.
+const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x0807_4b50;
+/// Size of the fixed part of a local file header.
+const LOCAL_HEADER_LEN: usize = 30;
+/// Size of the fixed part of a central directory file header.
+const CENTRAL_HEADER_LEN: usize = 46;
+/// General purpose flag indicating that sizes and CRC are stored in a trailing data descriptor.
+const FLAG_DATA_DESCRIPTOR: u16 = 1 << 3;
+/// Extra field ID used by Android's `zipalign` to pad local headers for alignment.
+const ALIGNMENT_EXTRA_FIELD_ID: u16 = 0xD935;
+/// Size of the alignment extra field without its padding (ID, size and alignment value).
+const ALIGNMENT_EXTRA_FIELD_LEN: usize = 6;
+/// Alignment of stored native libraries, so they can be memory-mapped directly from the APK.
+const NATIVE_LIBRARY_ALIGNMENT: u64 = 4096;
+/// Alignment of all other stored entries, as applied by `zipalign`.
+const DEFAULT_ALIGNMENT: u64 = 4;
+/// ZIP version 2.0, the version needed to extract deflated entries.
+const ZIP_VERSION_DEFLATE: u16 = 20;
+/// MS-DOS date of 1980-01-01, the earliest representable date, used for added entries.
+const DOS_EPOCH_DATE: u16 = (1 << 5) | 1;
+
+/// Changes to apply to a ZIP archive with [`rewrite_zip`].
+#[derive(Default)]
+pub struct ZipEdits {
+    /// New contents for existing entries, keyed by entry name.
+    pub replace: HashMap<String, Vec<u8>>,
+    /// Names of existing entries to leave out.
+    pub remove: HashSet<String>,
+    /// New entries, deflated and appended after the existing ones in the given order.
+    pub add: Vec<(String, Vec<u8>)>,
+}
+
+/// The location of the trailing records of an archive written by [`rewrite_zip`].
+pub struct ZipLayout {
+    /// The offset of the central directory, which is also where the entry data ends.
+    pub central_directory_start: u64,
+    /// The offset of the end of central directory record.
+    pub end_of_central_directory_start: u64,
+}
+
+/// Writes a copy of an APK, XAPK or split APK bundle with one internal file replaced.
+///
+/// For archives containing split APKs, the split APK providing the file is rebuilt with the new
+/// contents and then swapped into the container. The `manifest.json` of an XAPK is updated to
+/// reflect the new size. All other entries are copied byte for byte.
+///
+/// Note that replacing a file invalidates the APK signature, so the result has to be re-signed
+/// before it can be installed.
+///
+/// # Arguments
+///
+/// * `input_path` - The path of the original archive.
+/// * `output_path` - The path to write the repacked archive to.
+/// * `internal_path` - The path of the file to replace, as it appears within the (split) APK.
+/// * `data` - The new contents of the file.
+///
+/// # Errors
+///
+/// Returns an error if the archive does not contain the file, or if reading or writing fails.
+pub fn repack_archive(
+    input_path: &Path,
+    output_path: &Path,
+    internal_path: &str,
+    data: &[u8],
+) -> Result<()> {
+    let kind = detect_input_kind(input_path)?;
+    let input = File::open(input_path)?;
+    let mut output = BufWriter::new(File::create(output_path)?);
+
+    match kind {
+        InputKind::Apk => {
+            let edits = ZipEdits {
+                replace: HashMap::from([(internal_path.to_string(), data.to_vec())]),
+                ..Default::default()
+            };
+            rewrite_zip(input, &mut output, &edits)?;
+        }
+        InputKind::XApk | InputKind::SplitApks => {
+            // Find the split APK that provides the file.
+            let split_apk = open_archive(input_path)?
+                .list_entries()?
+                .into_iter()
+                .find(|entry| entry.path == internal_path)
+                .and_then(|entry| entry.split_apk)
+                .ok_or(anyhow!("{} not found in {}", internal_path, kind))?;
+            debug!("Rebuilding split APK {}...", split_apk);
+
+            let mut container = ZipArchive::new(&input)?;
+            let mut split_data = Vec::new();
+            container
+                .by_name(&split_apk)?
+                .read_to_end(&mut split_data)?;
+
+            // Rebuild the split APK in memory with the file replaced.
+            let mut new_split_data = Vec::new();
+            let split_edits = ZipEdits {
+                replace: HashMap::from([(internal_path.to_string(), data.to_vec())]),
+                ..Default::default()
+            };
+            rewrite_zip(Cursor::new(&split_data), &mut new_split_data, &split_edits)?;
+
+            let mut edits = ZipEdits::default();
+            if kind == InputKind::XApk {
+                let mut manifest = String::new();
+                container
+                    .by_name("manifest.json")?
+                    .read_to_string(&mut manifest)?;
+                let size_delta = new_split_data.len() as i64 - split_data.len() as i64;
+                edits.replace.insert(
+                    "manifest.json".to_string(),
+                    update_manifest_size(&manifest, size_delta)?,
+                );
+            }
+            edits.replace.insert(split_apk, new_split_data);
+            rewrite_zip(&input, &mut output, &edits)?;
+        }
+        InputKind::Directory => {
+            bail!(
+                "{} is an unpacked APK directory, not an archive",
+                input_path.display()
+            )
+        }
+        InputKind::Il2Cpp | InputKind::GlobalMetadata { .. } => {
+            bail!("{} is a {}, not an archive", input_path.display(), kind)
+        }
+    }
+
+    output.flush()?;
+    info!(
+        "The repacked archive must be re-signed with the sign command before it can be installed"
+    );
+    Ok(())
+}
+
+/// Adjusts the `total_size` field of an XAPK `manifest.json`, leaving all other fields untouched.
+///
+/// Manifests without a `total_size` field are returned unchanged.
+///
+/// # Arguments
+///
+/// * `manifest` - The original manifest contents.
+/// * `size_delta` - The change in size of the container contents, in bytes.
+///
+/// # Errors
+///
+/// Returns an error if the manifest is not valid JSON.
+pub(super) fn update_manifest_size(manifest: &str, size_delta: i64) -> Result<Vec<u8>> {
+    let mut manifest: serde_json::Value = serde_json::from_str(manifest)?;
+    if let Some(total_size) = manifest.get_mut("total_size") {
+        if let Some(size) = total_size.as_i64() {
+            *total_size = serde_json::Value::from(size + size_delta);
+        }
+    }
+    Ok(serde_json::to_vec(&manifest)?)
+}
+
+/// Describes where an entry is stored in the source archive.
+struct SourceEntry {
+    /// The entry name.
+    name: String,
+    /// The compression method of the entry data.
+    compression: CompressionMethod,
+    /// The offset of the local file header.
+    header_start: u64,
+    /// The offset of the (compressed) entry data.
+    data_start: u64,
+    /// The length of the (compressed) entry data.
+    compressed_size: u64,
+    /// The offset of the central directory file header.
+    central_header_start: u64,
+}
+
+/// The values of a rewritten entry that have to be reflected in the central directory.
+struct WrittenEntry {
+    /// The offset of the new local file header.
+    header_start: u64,
+    /// The new CRC-32, compressed size and uncompressed size if the data was replaced.
+    replaced: Option<(u32, u32, u32)>,
+}
+
+/// A writer that keeps track of the number of bytes written, which is the current archive offset.
+struct CountingWriter<W: Write> {
+    /// The underlying writer.
+    inner: W,
+    /// The number of bytes written so far.
+    position: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    /// Writes to the underlying writer and advances the position.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.position += written as u64;
+        Ok(written)
+    }
+
+    /// Flushes the underlying writer.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Rewrites a ZIP archive, replacing, removing or adding entries.
+///
+/// Entries are written in their original order. Untouched entries keep their local header,
+/// data and data descriptor byte for byte, unless their header has to be re-padded so that stored
+/// data stays aligned: 4 KiB for native libraries (`lib/**/*.so`) and 4 bytes for every other
+/// stored entry, matching `zipalign -p 4`. Replaced entries keep their compression method.
+/// Added entries are deflated and written last. Anything outside the entries and the central
+/// directory, such as an APK signing block, is dropped.
+///
+/// # Arguments
+///
+/// * `input` - The source archive.
+/// * `output` - The writer receiving the rewritten archive.
+/// * `edits` - The entries to replace, remove and add.
+///
+/// # Returns
+///
+/// The offsets of the central directory and end of central directory record in the output.
+///
+/// # Errors
+///
+/// Returns an error if a replaced or removed entry does not exist, an added entry already
+/// exists, a replaced entry uses an unsupported compression method, the archive requires ZIP64,
+/// or an IO error occurs.
+pub fn rewrite_zip<R: Read + Seek, W: Write>(
+    input: R,
+    output: W,
+    edits: &ZipEdits,
+) -> Result<ZipLayout> {
+    // Collect the location of every entry from the central directory.
+    let mut archive = ZipArchive::new(input)?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        entries.push(SourceEntry {
+            name: entry.name().to_string(),
+            compression: entry.compression(),
+            header_start: entry.header_start(),
+            data_start: entry.data_start(),
+            compressed_size: entry.compressed_size(),
+            central_header_start: entry.central_header_start(),
+        });
+    }
+    for name in edits.replace.keys().chain(edits.remove.iter()) {
+        if !entries.iter().any(|entry| &entry.name == name) {
+            bail!("Entry not found in archive: {}", name);
+        }
+    }
+    for (name, _) in &edits.add {
+        if entries.iter().any(|entry| &entry.name == name) && !edits.remove.contains(name) {
+            bail!("Entry already exists in archive: {}", name);
+        }
+    }
+    entries.retain(|entry| !edits.remove.contains(&entry.name));
+    let comment = archive.comment().to_vec();
+    let mut input = archive.into_inner();
+    let mut output = CountingWriter {
+        inner: output,
+        position: 0,
+    };
+
+    // Write the local headers and data in their original order.
+    let mut file_order: Vec<usize> = (0..entries.len()).collect();
+    file_order.sort_by_key(|&i| entries[i].header_start);
+    let mut written = HashMap::new();
+    for i in file_order {
+        let entry = &entries[i];
+        let written_entry = match edits.replace.get(&entry.name) {
+            Some(data) => write_replaced_entry(&mut input, &mut output, entry, data)?,
+            None => copy_entry(&mut input, &mut output, entry)?,
+        };
+        written.insert(i, written_entry);
+    }
+    let mut added_central_headers = Vec::with_capacity(edits.add.len());
+    for (name, data) in &edits.add {
+        added_central_headers.push(write_new_entry(&mut output, name, data)?);
+    }
+
+    // Write the central directory in its original order, pointing at the new local headers.
+    let central_directory_start = output.position;
+    let mut central_order: Vec<usize> = (0..entries.len()).collect();
+    central_order.sort_by_key(|&i| entries[i].central_header_start);
+    for i in central_order {
+        let mut header = read_central_header(&mut input, entries[i].central_header_start)?;
+        let written_entry = &written[&i];
+        if let Some((crc32, compressed_size, size)) = written_entry.replaced {
+            let flags = u16::from_le_bytes(header[8..10].try_into()?) & !FLAG_DATA_DESCRIPTOR;
+            header[8..10].copy_from_slice(&flags.to_le_bytes());
+            header[16..20].copy_from_slice(&crc32.to_le_bytes());
+            header[20..24].copy_from_slice(&compressed_size.to_le_bytes());
+            header[24..28].copy_from_slice(&size.to_le_bytes());
+        }
+        header[42..46].copy_from_slice(&to_u32(written_entry.header_start)?.to_le_bytes());
+        output.write_all(&header)?;
+    }
+    for header in &added_central_headers {
+        output.write_all(header)?;
+    }
+    let central_directory_size = output.position - central_directory_start;
+
+    // Write the end of central directory record.
+    let end_of_central_directory_start = output.position;
+    let entry_count = u16::try_from(entries.len() + edits.add.len())
+        .map_err(|_| anyhow!("Too many entries, ZIP64 archives are not supported"))?;
+    let comment_len = u16::try_from(comment.len())?;
+    output.write_all(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes())?;
+    output.write_all(&0u16.to_le_bytes())?; // Number of this disk.
+    output.write_all(&0u16.to_le_bytes())?; // Disk where the central directory starts.
+    output.write_all(&entry_count.to_le_bytes())?;
+    output.write_all(&entry_count.to_le_bytes())?;
+    output.write_all(&to_u32(central_directory_size)?.to_le_bytes())?;
+    output.write_all(&to_u32(central_directory_start)?.to_le_bytes())?;
+    output.write_all(&comment_len.to_le_bytes())?;
+    output.write_all(&comment)?;
+    output.flush()?;
+    Ok(ZipLayout {
+        central_directory_start,
+        end_of_central_directory_start,
+    })
+}
+
+/// Copies an untouched entry, re-padding its local header only if needed to keep it aligned.
+fn copy_entry<R: Read + Seek, W: Write>(
+    input: &mut R,
+    output: &mut CountingWriter<W>,
+    entry: &SourceEntry,
+) -> Result<WrittenEntry> {
+    let header_start = output.position;
+    let (mut header, name, extra) = read_local_header(input, entry.header_start)?;
+    let flags = u16::from_le_bytes(header[6..8].try_into()?);
+
+    let extra = match entry_alignment(&entry.name, entry.compression) {
+        Some(alignment)
+            if (header_start + (LOCAL_HEADER_LEN + name.len() + extra.len()) as u64)
+                % alignment
+                != 0 =>
+        {
+            align_extra_field(&extra, header_start, name.len(), alignment)
+        }
+        _ => extra,
+    };
+    header[28..30].copy_from_slice(&u16::try_from(extra.len())?.to_le_bytes());
+    output.write_all(&header)?;
+    output.write_all(&name)?;
+    output.write_all(&extra)?;
+
+    // Copy the data, followed by the data descriptor if there is one.
+    let mut data_len = entry.compressed_size;
+    if flags & FLAG_DATA_DESCRIPTOR != 0 {
+        input.seek(SeekFrom::Start(entry.data_start + entry.compressed_size))?;
+        let mut signature = [0u8; 4];
+        input.read_exact(&mut signature)?;
+        data_len += if u32::from_le_bytes(signature) == DATA_DESCRIPTOR_SIGNATURE {
+            16
+        } else {
+            12
+        };
+    }
+    input.seek(SeekFrom::Start(entry.data_start))?;
+    let copied = io::copy(&mut input.take(data_len), output)?;
+    if copied != data_len {
+        bail!("Unexpected end of archive while copying {}", entry.name);
+    }
+
+    Ok(WrittenEntry {
+        header_start,
+        replaced: None,
+    })
+}
+
+/// Writes an entry with new contents, compressed with the entry's original compression method.
+fn write_replaced_entry<R: Read + Seek, W: Write>(
+    input: &mut R,
+    output: &mut CountingWriter<W>,
+    entry: &SourceEntry,
+    data: &[u8],
+) -> Result<WrittenEntry> {
+    let header_start = output.position;
+    let (mut header, name, extra) = read_local_header(input, entry.header_start)?;
+
+    let compressed_data = match entry.compression {
+        CompressionMethod::Stored => data.to_vec(),
+        CompressionMethod::Deflated => {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        method => bail!(
+            "Unsupported compression method {} for {}",
+            method,
+            entry.name
+        ),
+    };
+    let crc32 = crc32fast::hash(data);
+    let compressed_size = to_u32(compressed_data.len() as u64)?;
+    let size = to_u32(data.len() as u64)?;
+
+    let extra = match entry_alignment(&entry.name, entry.compression) {
+        Some(alignment) => align_extra_field(&extra, header_start, name.len(), alignment),
+        None => extra,
+    };
+
+    // The sizes are known up front, so the data descriptor is no longer needed.
+    let flags = u16::from_le_bytes(header[6..8].try_into()?) & !FLAG_DATA_DESCRIPTOR;
+    header[6..8].copy_from_slice(&flags.to_le_bytes());
+    header[14..18].copy_from_slice(&crc32.to_le_bytes());
+    header[18..22].copy_from_slice(&compressed_size.to_le_bytes());
+    header[22..26].copy_from_slice(&size.to_le_bytes());
+    header[28..30].copy_from_slice(&u16::try_from(extra.len())?.to_le_bytes());
+    output.write_all(&header)?;
+    output.write_all(&name)?;
+    output.write_all(&extra)?;
+    output.write_all(&compressed_data)?;
+
+    Ok(WrittenEntry {
+        header_start,
+        replaced: Some((crc32, compressed_size, size)),
+    })
+}
+
+/// Writes a new deflated entry, returning its central directory file header.
+fn write_new_entry<W: Write>(
+    output: &mut CountingWriter<W>,
+    name: &str,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    let header_start = to_u32(output.position)?;
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    let compressed_data = encoder.finish()?;
+    let crc32 = crc32fast::hash(data);
+    let compressed_size = to_u32(compressed_data.len() as u64)?;
+    let size = to_u32(data.len() as u64)?;
+    let name_len = u16::try_from(name.len())?;
+
+    // The fields shared by the local and central headers, from "version needed" to "name length".
+    let mut common = Vec::with_capacity(24);
+    common.extend_from_slice(&ZIP_VERSION_DEFLATE.to_le_bytes());
+    common.extend_from_slice(&0u16.to_le_bytes()); // Flags.
+    common.extend_from_slice(&8u16.to_le_bytes()); // Compression method: deflate.
+    common.extend_from_slice(&0u16.to_le_bytes()); // Modification time.
+    common.extend_from_slice(&DOS_EPOCH_DATE.to_le_bytes());
+    common.extend_from_slice(&crc32.to_le_bytes());
+    common.extend_from_slice(&compressed_size.to_le_bytes());
+    common.extend_from_slice(&size.to_le_bytes());
+    common.extend_from_slice(&name_len.to_le_bytes());
+
+    output.write_all(&LOCAL_HEADER_SIGNATURE.to_le_bytes())?;
+    output.write_all(&common)?;
+    output.write_all(&0u16.to_le_bytes())?; // Extra field length.
+    output.write_all(name.as_bytes())?;
+    output.write_all(&compressed_data)?;
+
+    let mut central_header = Vec::with_capacity(CENTRAL_HEADER_LEN + name.len());
+    central_header.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+    central_header.extend_from_slice(&ZIP_VERSION_DEFLATE.to_le_bytes()); // Version made by.
+    central_header.extend_from_slice(&common);
+    central_header.extend_from_slice(&0u16.to_le_bytes()); // Extra field length.
+    central_header.extend_from_slice(&0u16.to_le_bytes()); // Comment length.
+    central_header.extend_from_slice(&0u16.to_le_bytes()); // Disk number.
+    central_header.extend_from_slice(&0u16.to_le_bytes()); // Internal attributes.
+    central_header.extend_from_slice(&0u32.to_le_bytes()); // External attributes.
+    central_header.extend_from_slice(&header_start.to_le_bytes());
+    central_header.extend_from_slice(name.as_bytes());
+    Ok(central_header)
+}
+
+/// Returns the alignment required for an entry's data, if any.
+///
+/// Only stored entries are aligned, since compressed data cannot be memory-mapped anyway.
+fn entry_alignment(name: &str, compression: CompressionMethod) -> Option<u64> {
+    if compression != CompressionMethod::Stored {
+        None
+    } else if name.starts_with("lib/") && name.ends_with(".so") {
+        Some(NATIVE_LIBRARY_ALIGNMENT)
+    } else {
+        Some(DEFAULT_ALIGNMENT)
+    }
+}
+
+/// Builds a local header extra field that aligns the entry data to `alignment`.
+///
+/// Existing alignment padding is dropped: both `zipalign`'s alignment extra field and the trailing
+/// zero bytes older versions of `zipalign` appended. A new alignment extra field is then added,
+/// padded so the data starts on an aligned offset.
+fn align_extra_field(extra: &[u8], header_start: u64, name_len: usize, alignment: u64) -> Vec<u8> {
+    // Keep every well-formed extra field except existing alignment fields.
+    let mut aligned_extra = Vec::with_capacity(extra.len() + alignment as usize);
+    let mut offset = 0;
+    while offset + 4 <= extra.len() {
+        let id = u16::from_le_bytes([extra[offset], extra[offset + 1]]);
+        let len = u16::from_le_bytes([extra[offset + 2], extra[offset + 3]]) as usize;
+        let end = offset + 4 + len;
+        if end > extra.len() || (id == 0 && len == 0) {
+            // Anything that doesn't parse as a field is padding.
+            break;
+        }
+        if id != ALIGNMENT_EXTRA_FIELD_ID {
+            aligned_extra.extend_from_slice(&extra[offset..end]);
+        }
+        offset = end;
+    }
+
+    let unpadded_data_start = header_start
+        + (LOCAL_HEADER_LEN + name_len + aligned_extra.len() + ALIGNMENT_EXTRA_FIELD_LEN) as u64;
+    let padding = ((alignment - unpadded_data_start % alignment) % alignment) as usize;
+    aligned_extra.extend_from_slice(&ALIGNMENT_EXTRA_FIELD_ID.to_le_bytes());
+    aligned_extra.extend_from_slice(&((2 + padding) as u16).to_le_bytes());
+    aligned_extra.extend_from_slice(&(alignment as u16).to_le_bytes());
+    aligned_extra.resize(aligned_extra.len() + padding, 0);
+    aligned_extra
+}
+
+/// Reads a local file header, returning its fixed part, file name and extra field.
+fn read_local_header<R: Read + Seek>(
+    input: &mut R,
+    header_start: u64,
+) -> Result<([u8; LOCAL_HEADER_LEN], Vec<u8>, Vec<u8>)> {
+    input.seek(SeekFrom::Start(header_start))?;
+    let mut header = [0u8; LOCAL_HEADER_LEN];
+    input.read_exact(&mut header)?;
+    if u32::from_le_bytes(header[0..4].try_into()?) != LOCAL_HEADER_SIGNATURE {
+        bail!("Invalid local file header at offset {:#X}", header_start);
+    }
+    let name_len = u16::from_le_bytes(header[26..28].try_into()?) as usize;
+    let extra_len = u16::from_le_bytes(header[28..30].try_into()?) as usize;
+    let mut name = vec![0u8; name_len];
+    input.read_exact(&mut name)?;
+    let mut extra = vec![0u8; extra_len];
+    input.read_exact(&mut extra)?;
+    Ok((header, name, extra))
+}
+
+/// Reads a complete central directory file header, including its variable-length fields.
+fn read_central_header<R: Read + Seek>(input: &mut R, header_start: u64) -> Result<Vec<u8>> {
+    input.seek(SeekFrom::Start(header_start))?;
+    let mut header = vec![0u8; CENTRAL_HEADER_LEN];
+    input.read_exact(&mut header)?;
+    if u32::from_le_bytes(header[0..4].try_into()?) != CENTRAL_HEADER_SIGNATURE {
+        bail!(
+            "Invalid central directory header at offset {:#X}",
+            header_start
+        );
+    }
+    let variable_len = u16::from_le_bytes(header[28..30].try_into()?) as usize
+        + u16::from_le_bytes(header[30..32].try_into()?) as usize
+        + u16::from_le_bytes(header[32..34].try_into()?) as usize;
+    header.resize(CENTRAL_HEADER_LEN + variable_len, 0);
+    input.read_exact(&mut header[CENTRAL_HEADER_LEN..])?;
+    Ok(header)
+}
+
+/// Converts an offset or size to the 32-bit field used by non-ZIP64 archives.
+fn to_u32(value: u64) -> Result<u32> {
+    u32::try_from(value)
+        .ok()
+        .filter(|&value| value != u32::MAX)
+        .ok_or(anyhow!(
+            "Archive exceeds 4 GiB, ZIP64 archives are not supported"
+        ))
+}
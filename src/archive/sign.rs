@@ -0,0 +1,470 @@
+use crate::archive::detect::{detect_input_kind, InputKind};
+use crate::archive::open_archive;
+use crate::archive::repack::{rewrite_zip, update_manifest_size, ZipEdits};
+use crate::crypto::signing_key::SigningKey;
+use anyhow::{anyhow, bail, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use cms::cert::{CertificateChoices, IssuerAndSerialNumber};
+use cms::content_info::{CmsVersion, ContentInfo};
+use cms::signed_data::{
+    CertificateSet, EncapsulatedContentInfo, SignedData, SignerIdentifier, SignerInfo, SignerInfos,
+};
+use hashbrown::HashSet;
+use log::info;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{BufWriter, Cursor, Read, Write};
+use std::path::Path;
+use x509_cert::der::asn1::{OctetString, SetOfVec};
+use x509_cert::der::{Any, Encode};
+use x509_cert::spki::{AlgorithmIdentifierOwned, ObjectIdentifier};
+use zip::read::ZipArchive;
+
+/// Name of the JAR manifest listing the digest of every entry.
+const JAR_MANIFEST_NAME: &str = "META-INF/MANIFEST.MF";
+/// Name of the JAR signature file listing the digest of every manifest section.
+const JAR_SIGNATURE_FILE_NAME: &str = "META-INF/CERT.SF";
+/// Name of the PKCS#7 signature block signing the signature file.
+const JAR_SIGNATURE_BLOCK_NAME: &str = "META-INF/CERT.RSA";
+/// Value of the `Created-By` attribute of the JAR manifest and signature file.
+const JAR_CREATED_BY: &str = "1.0 (ptcgp_tool)";
+/// Maximum length in bytes of a line in a JAR manifest, excluding the line break.
+const JAR_MAX_LINE_LEN: usize = 72;
+
+/// OID of the PKCS#7 `data` content type.
+const OID_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.1");
+/// OID of the PKCS#7 `signedData` content type.
+const OID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+/// OID of the SHA-256 digest algorithm.
+const OID_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+/// OID of the `rsaEncryption` signature algorithm.
+const OID_RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+
+/// Magic at the end of an APK signing block.
+const APK_SIGNING_BLOCK_MAGIC: &[u8; 16] = b"APK Sig Block 42";
+/// ID of the APK Signature Scheme v2 block.
+const APK_SIGNATURE_SCHEME_V2_BLOCK_ID: u32 = 0x7109_871a;
+/// ID of the APK Signature Scheme v3 block.
+const APK_SIGNATURE_SCHEME_V3_BLOCK_ID: u32 = 0xf053_68c0;
+/// ID of the v2 signer attribute listing the newer signature schemes the APK is signed with,
+/// which prevents stripping the v3 signature.
+const STRIPPING_PROTECTION_ATTRIBUTE_ID: u32 = 0xbeef_f00d;
+/// Signature scheme number of APK Signature Scheme v3.
+const APK_SIGNATURE_SCHEME_V3: u32 = 3;
+/// Signature algorithm ID of RSASSA-PKCS1-v1_5 with SHA-256 digests of 1 MiB chunks.
+const SIGNATURE_RSA_PKCS1_V1_5_WITH_SHA256: u32 = 0x0103;
+/// Size of the chunks the APK contents are digested in.
+const CONTENT_DIGEST_CHUNK_SIZE: usize = 1024 * 1024;
+/// Android API level that introduced APK Signature Scheme v3 (Android 9).
+const V3_MIN_SDK_VERSION: u32 = 28;
+/// Highest Android API level the v3 signature applies to, i.e. all future versions.
+const V3_MAX_SDK_VERSION: u32 = i32::MAX as u32;
+
+/// Signs an APK, or every APK within an XAPK or split APK bundle, and writes the result.
+///
+/// Every APK is signed with the JAR signature scheme (v1) and the APK Signature Schemes v2 and
+/// v3, replacing any existing signatures. Split APKs must be signed with the same certificate as
+/// the base APK to be installable, so all of them are signed with the same key. The
+/// `manifest.json` of an XAPK is updated to reflect the new sizes.
+///
+/// # Arguments
+///
+/// * `input_path` - The path of the archive to sign.
+/// * `output_path` - The path to write the signed archive to.
+/// * `key` - The key to sign with.
+///
+/// # Errors
+///
+/// Returns an error if the input is not an archive, contains no APKs, or if signing, reading or
+/// writing fails.
+pub fn sign_archive(input_path: &Path, output_path: &Path, key: &SigningKey) -> Result<()> {
+    let kind = detect_input_kind(input_path)?;
+    match kind {
+        InputKind::Apk => {
+            let signed_data = sign_apk(&fs::read(input_path)?, key)?;
+            fs::write(output_path, signed_data)?;
+        }
+        InputKind::XApk | InputKind::SplitApks => {
+            // Sign the split APKs the container declares, which always include the base APK.
+            let apk_names: BTreeSet<String> = open_archive(input_path)?
+                .list_entries()?
+                .into_iter()
+                .filter_map(|entry| entry.split_apk)
+                .collect();
+            if apk_names.is_empty() {
+                bail!("No APKs found in {}", input_path.display());
+            }
+
+            let input = File::open(input_path)?;
+            let mut container = ZipArchive::new(&input)?;
+
+            let mut edits = ZipEdits::default();
+            let mut size_delta = 0i64;
+            for name in apk_names {
+                info!("Signing {}...", name);
+                let mut apk_data = Vec::new();
+                container.by_name(&name)?.read_to_end(&mut apk_data)?;
+                let signed_data = sign_apk(&apk_data, key)?;
+                size_delta += signed_data.len() as i64 - apk_data.len() as i64;
+                edits.replace.insert(name, signed_data);
+            }
+            if kind == InputKind::XApk {
+                let mut manifest = String::new();
+                container
+                    .by_name("manifest.json")?
+                    .read_to_string(&mut manifest)?;
+                edits.replace.insert(
+                    "manifest.json".to_string(),
+                    update_manifest_size(&manifest, size_delta)?,
+                );
+            }
+
+            let mut output = BufWriter::new(File::create(output_path)?);
+            rewrite_zip(&input, &mut output, &edits)?;
+            output.flush()?;
+        }
+        InputKind::Directory => {
+            bail!(
+                "{} is an unpacked APK directory, not an archive",
+                input_path.display()
+            )
+        }
+        InputKind::Il2Cpp | InputKind::GlobalMetadata { .. } => {
+            bail!("{} is a {}, not an archive", input_path.display(), kind)
+        }
+    }
+    Ok(())
+}
+
+/// Signs an APK with the JAR signature scheme (v1) and the APK Signature Schemes v2 and v3.
+///
+/// Existing v1 signature files are replaced and any existing APK signing block is dropped.
+/// The entries are realigned like `zipalign` does, which has to happen before v2/v3 signing.
+///
+/// # Arguments
+///
+/// * `data` - The APK contents.
+/// * `key` - The key to sign with.
+///
+/// # Returns
+///
+/// The contents of the signed APK.
+///
+/// # Errors
+///
+/// Returns an error if the APK cannot be parsed or rewritten, or if encoding the signature fails.
+pub fn sign_apk(data: &[u8], key: &SigningKey) -> Result<Vec<u8>> {
+    // Digest every entry that is covered by the v1 signature.
+    let mut archive = ZipArchive::new(Cursor::new(data))?;
+    let mut signature_files = HashSet::new();
+    let mut entry_digests = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let name = entry.name().to_string();
+        if is_jar_signature_file(&name) {
+            signature_files.insert(name);
+        } else if !entry.is_dir() {
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut entry, &mut hasher)?;
+            entry_digests.push((name, hasher.finalize().to_vec()));
+        }
+    }
+    entry_digests.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    // Replace the v1 signature files, realigning the archive at the same time.
+    let (manifest, signature_file) = build_jar_manifest(&entry_digests);
+    let signature_block = build_jar_signature_block(&signature_file, key)?;
+    let edits = ZipEdits {
+        remove: signature_files,
+        add: vec![
+            (JAR_MANIFEST_NAME.to_string(), manifest),
+            (JAR_SIGNATURE_FILE_NAME.to_string(), signature_file),
+            (JAR_SIGNATURE_BLOCK_NAME.to_string(), signature_block),
+        ],
+        ..Default::default()
+    };
+    let mut unsigned_data = Vec::with_capacity(data.len());
+    let layout = rewrite_zip(archive.into_inner(), &mut unsigned_data, &edits)?;
+    let central_directory_start = layout.central_directory_start as usize;
+    let end_of_central_directory_start = layout.end_of_central_directory_start as usize;
+
+    // The v2 and v3 signatures cover the entries, the central directory and the end of central
+    // directory record, whose central directory offset points at the signing block.
+    let content_digest = compute_content_digest(&[
+        &unsigned_data[..central_directory_start],
+        &unsigned_data[central_directory_start..end_of_central_directory_start],
+        &unsigned_data[end_of_central_directory_start..],
+    ]);
+    let signing_block = build_apk_signing_block(&[
+        (
+            APK_SIGNATURE_SCHEME_V2_BLOCK_ID,
+            build_v2_signature_block(&content_digest, key)?,
+        ),
+        (
+            APK_SIGNATURE_SCHEME_V3_BLOCK_ID,
+            build_v3_signature_block(&content_digest, key)?,
+        ),
+    ]);
+
+    // Insert the signing block in front of the central directory and point the end of central
+    // directory record at the new central directory offset.
+    let mut signed_data = Vec::with_capacity(unsigned_data.len() + signing_block.len());
+    signed_data.extend_from_slice(&unsigned_data[..central_directory_start]);
+    signed_data.extend_from_slice(&signing_block);
+    signed_data.extend_from_slice(&unsigned_data[central_directory_start..]);
+    let central_directory_offset = u32::try_from(central_directory_start + signing_block.len())
+        .map_err(|_| anyhow!("Signed APK exceeds 4 GiB"))?;
+    let offset_field = end_of_central_directory_start + signing_block.len() + 16;
+    signed_data[offset_field..offset_field + 4]
+        .copy_from_slice(&central_directory_offset.to_le_bytes());
+    Ok(signed_data)
+}
+
+/// Returns `true` if the entry is part of a v1 signature, and thus excluded from the manifest.
+fn is_jar_signature_file(name: &str) -> bool {
+    let Some(file_name) = name.strip_prefix("META-INF/") else {
+        return false;
+    };
+    if file_name.contains('/') {
+        return false;
+    }
+    let upper = file_name.to_uppercase();
+    upper == "MANIFEST.MF"
+        || upper.starts_with("SIG-")
+        || [".SF", ".RSA", ".DSA", ".EC"]
+            .iter()
+            .any(|extension| upper.ends_with(extension))
+}
+
+/// Builds the JAR manifest and the signature file for the given entry digests.
+///
+/// # Returns
+///
+/// A tuple of the `MANIFEST.MF` and `CERT.SF` contents.
+fn build_jar_manifest(entry_digests: &[(String, Vec<u8>)]) -> (Vec<u8>, Vec<u8>) {
+    let mut manifest = Vec::new();
+    write_manifest_attribute(&mut manifest, "Manifest-Version", "1.0");
+    write_manifest_attribute(&mut manifest, "Created-By", JAR_CREATED_BY);
+    manifest.extend_from_slice(b"\r\n");
+
+    // The signature file lists the digest of each manifest section.
+    let mut sections = Vec::with_capacity(entry_digests.len());
+    for (name, digest) in entry_digests {
+        let mut section = Vec::new();
+        write_manifest_attribute(&mut section, "Name", name);
+        write_manifest_attribute(&mut section, "SHA-256-Digest", &BASE64.encode(digest));
+        section.extend_from_slice(b"\r\n");
+        manifest.extend_from_slice(&section);
+        sections.push((name, Sha256::digest(&section)));
+    }
+
+    let mut signature_file = Vec::new();
+    write_manifest_attribute(&mut signature_file, "Signature-Version", "1.0");
+    write_manifest_attribute(&mut signature_file, "Created-By", JAR_CREATED_BY);
+    write_manifest_attribute(
+        &mut signature_file,
+        "SHA-256-Digest-Manifest",
+        &BASE64.encode(Sha256::digest(&manifest)),
+    );
+    // Tells v2-aware verifiers to reject the APK if the v2/v3 signatures were stripped.
+    write_manifest_attribute(&mut signature_file, "X-Android-APK-Signed", "2, 3");
+    signature_file.extend_from_slice(b"\r\n");
+    for (name, digest) in sections {
+        write_manifest_attribute(&mut signature_file, "Name", name);
+        write_manifest_attribute(
+            &mut signature_file,
+            "SHA-256-Digest",
+            &BASE64.encode(digest),
+        );
+        signature_file.extend_from_slice(b"\r\n");
+    }
+
+    (manifest, signature_file)
+}
+
+/// Writes a `name: value` manifest line, wrapping it at 72 bytes with continuation lines.
+fn write_manifest_attribute(output: &mut Vec<u8>, name: &str, value: &str) {
+    let line = format!("{}: {}", name, value);
+    let mut bytes = line.as_bytes();
+    let mut max_len = JAR_MAX_LINE_LEN;
+    while bytes.len() > max_len {
+        output.extend_from_slice(&bytes[..max_len]);
+        output.extend_from_slice(b"\r\n ");
+        bytes = &bytes[max_len..];
+        // Continuation lines start with a space, which counts towards the line length.
+        max_len = JAR_MAX_LINE_LEN - 1;
+    }
+    output.extend_from_slice(bytes);
+    output.extend_from_slice(b"\r\n");
+}
+
+/// Builds the PKCS#7 `SignedData` block signing the v1 signature file.
+///
+/// The signature is detached and has no signed attributes, so it is computed over the signature
+/// file itself, like `apksigner` does.
+fn build_jar_signature_block(signature_file: &[u8], key: &SigningKey) -> Result<Vec<u8>> {
+    let certificate = key.certificate();
+    let digest_algorithm = AlgorithmIdentifierOwned {
+        oid: OID_SHA256,
+        parameters: None,
+    };
+    let signer_info = SignerInfo {
+        version: CmsVersion::V1,
+        sid: SignerIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber {
+            issuer: certificate.tbs_certificate.issuer.clone(),
+            serial_number: certificate.tbs_certificate.serial_number.clone(),
+        }),
+        digest_alg: digest_algorithm.clone(),
+        signed_attrs: None,
+        signature_algorithm: AlgorithmIdentifierOwned {
+            oid: OID_RSA_ENCRYPTION,
+            parameters: Some(Any::null()),
+        },
+        signature: OctetString::new(key.sign_sha256(signature_file))?,
+        unsigned_attrs: None,
+    };
+    let signed_data = SignedData {
+        version: CmsVersion::V1,
+        digest_algorithms: SetOfVec::try_from(vec![digest_algorithm])?,
+        encap_content_info: EncapsulatedContentInfo {
+            econtent_type: OID_DATA,
+            econtent: None,
+        },
+        certificates: Some(CertificateSet(SetOfVec::try_from(vec![
+            CertificateChoices::Certificate(certificate.clone()),
+        ])?)),
+        crls: None,
+        signer_infos: SignerInfos(SetOfVec::try_from(vec![signer_info])?),
+    };
+    let content_info = ContentInfo {
+        content_type: OID_SIGNED_DATA,
+        content: Any::encode_from(&signed_data)?,
+    };
+    Ok(content_info.to_der()?)
+}
+
+/// Computes the v2/v3 content digest of the given APK sections.
+///
+/// Each section is split into 1 MiB chunks, and every chunk is digested with a `0xA5` marker and
+/// its length. The top-level digest covers a `0x5A` marker, the chunk count and all chunk digests.
+fn compute_content_digest(sections: &[&[u8]]) -> Vec<u8> {
+    let mut chunk_count = 0u32;
+    let mut chunk_digests = Vec::new();
+    for section in sections {
+        for chunk in section.chunks(CONTENT_DIGEST_CHUNK_SIZE) {
+            let mut hasher = Sha256::new();
+            hasher.update([0xA5]);
+            hasher.update((chunk.len() as u32).to_le_bytes());
+            hasher.update(chunk);
+            chunk_digests.extend_from_slice(&hasher.finalize());
+            chunk_count += 1;
+        }
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update([0x5A]);
+    hasher.update(chunk_count.to_le_bytes());
+    hasher.update(&chunk_digests);
+    hasher.finalize().to_vec()
+}
+
+/// Builds the APK Signature Scheme v2 block with a single signer.
+fn build_v2_signature_block(content_digest: &[u8], key: &SigningKey) -> Result<Vec<u8>> {
+    // Declare the v3 signature so it cannot be stripped to downgrade verification to v2.
+    let mut stripping_protection = STRIPPING_PROTECTION_ATTRIBUTE_ID.to_le_bytes().to_vec();
+    stripping_protection.extend_from_slice(&APK_SIGNATURE_SCHEME_V3.to_le_bytes());
+    let mut attributes = Vec::new();
+    push_length_prefixed(&mut attributes, &stripping_protection);
+
+    let mut signed_data = Vec::new();
+    push_length_prefixed(&mut signed_data, &encode_digests(content_digest));
+    push_length_prefixed(&mut signed_data, &encode_certificates(key)?);
+    push_length_prefixed(&mut signed_data, &attributes);
+
+    let mut signer = Vec::new();
+    push_length_prefixed(&mut signer, &signed_data);
+    push_length_prefixed(&mut signer, &encode_signatures(&signed_data, key));
+    push_length_prefixed(&mut signer, &key.public_key_der()?);
+    Ok(encode_signers(&signer))
+}
+
+/// Builds the APK Signature Scheme v3 block with a single signer and no key rotation.
+fn build_v3_signature_block(content_digest: &[u8], key: &SigningKey) -> Result<Vec<u8>> {
+    let mut signed_data = Vec::new();
+    push_length_prefixed(&mut signed_data, &encode_digests(content_digest));
+    push_length_prefixed(&mut signed_data, &encode_certificates(key)?);
+    signed_data.extend_from_slice(&V3_MIN_SDK_VERSION.to_le_bytes());
+    signed_data.extend_from_slice(&V3_MAX_SDK_VERSION.to_le_bytes());
+    push_length_prefixed(&mut signed_data, &[]); // No additional attributes.
+
+    let mut signer = Vec::new();
+    push_length_prefixed(&mut signer, &signed_data);
+    signer.extend_from_slice(&V3_MIN_SDK_VERSION.to_le_bytes());
+    signer.extend_from_slice(&V3_MAX_SDK_VERSION.to_le_bytes());
+    push_length_prefixed(&mut signer, &encode_signatures(&signed_data, key));
+    push_length_prefixed(&mut signer, &key.public_key_der()?);
+    Ok(encode_signers(&signer))
+}
+
+/// Encodes the sequence of content digests of a v2/v3 signer.
+fn encode_digests(content_digest: &[u8]) -> Vec<u8> {
+    let mut digest = SIGNATURE_RSA_PKCS1_V1_5_WITH_SHA256.to_le_bytes().to_vec();
+    push_length_prefixed(&mut digest, content_digest);
+    let mut digests = Vec::new();
+    push_length_prefixed(&mut digests, &digest);
+    digests
+}
+
+/// Encodes the sequence of certificates of a v2/v3 signer.
+fn encode_certificates(key: &SigningKey) -> Result<Vec<u8>> {
+    let mut certificates = Vec::new();
+    push_length_prefixed(&mut certificates, &key.certificate_der()?);
+    Ok(certificates)
+}
+
+/// Encodes the sequence of signatures of a v2/v3 signer over its signed data.
+fn encode_signatures(signed_data: &[u8], key: &SigningKey) -> Vec<u8> {
+    let mut signature = SIGNATURE_RSA_PKCS1_V1_5_WITH_SHA256.to_le_bytes().to_vec();
+    push_length_prefixed(&mut signature, &key.sign_sha256(signed_data));
+    let mut signatures = Vec::new();
+    push_length_prefixed(&mut signatures, &signature);
+    signatures
+}
+
+/// Encodes the value of a v2/v3 block holding a single signer.
+fn encode_signers(signer: &[u8]) -> Vec<u8> {
+    let mut signers = Vec::new();
+    push_length_prefixed(&mut signers, signer);
+    let mut block = Vec::new();
+    push_length_prefixed(&mut block, &signers);
+    block
+}
+
+/// Appends data preceded by its length as a little-endian `u32`.
+fn push_length_prefixed(output: &mut Vec<u8>, data: &[u8]) {
+    output.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    output.extend_from_slice(data);
+}
+
+/// Builds an APK signing block from ID-value pairs.
+///
+/// The block consists of its size, the length-prefixed pairs, the size again and a magic value.
+/// Both sizes exclude the leading size field itself.
+fn build_apk_signing_block(pairs: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut encoded_pairs = Vec::new();
+    for (id, value) in pairs {
+        encoded_pairs.extend_from_slice(&(4 + value.len() as u64).to_le_bytes());
+        encoded_pairs.extend_from_slice(&id.to_le_bytes());
+        encoded_pairs.extend_from_slice(value);
+    }
+    let block_size = (encoded_pairs.len() + 8 + APK_SIGNING_BLOCK_MAGIC.len()) as u64;
+
+    let mut block = Vec::with_capacity(8 + block_size as usize);
+    block.extend_from_slice(&block_size.to_le_bytes());
+    block.extend_from_slice(&encoded_pairs);
+    block.extend_from_slice(&block_size.to_le_bytes());
+    block.extend_from_slice(APK_SIGNING_BLOCK_MAGIC);
+    block
+}
@@ -0,0 +1,265 @@
+use crate::archive::axml::AxmlDocument;
+use crate::archive::manifest::{is_split_manifest, ANDROID_MANIFEST_PATH};
+use crate::archive::ArchiveEntry;
+use anyhow::{anyhow, Result};
+use hashbrown::HashMap;
+use log::debug;
+use std::fs::File;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use zip::read::ZipArchive;
+use zip::CompressionMethod;
+
+/// A read-only view of a byte range within a file, exposed as its own seekable stream.
+///
+/// Split APKs that are stored (uncompressed) inside an outer container are plain byte ranges of
+/// the container file, so they can be opened as ZIP archives directly through such a window
+/// without copying them into memory.
+struct FileWindow {
+    /// The file handle the window reads from.
+    file: File,
+    /// The absolute offset of the first byte of the window within the file.
+    start: u64,
+    /// The length of the window in bytes.
+    len: u64,
+    /// The current position relative to the start of the window.
+    pos: u64,
+}
+
+impl FileWindow {
+    /// Creates a window over `len` bytes of `file`, starting at absolute offset `start`.
+    fn new(file: File, start: u64, len: u64) -> Self {
+        Self {
+            file,
+            start,
+            len,
+            pos: 0,
+        }
+    }
+}
+
+impl Read for FileWindow {
+    /// Reads from the current window position, never past the end of the window.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.len.saturating_sub(self.pos);
+        if remaining == 0 || buf.is_empty() {
+            return Ok(0);
+        }
+        let max = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+
+        // The handle may be shared with other windows, so always seek before reading.
+        self.file.seek(SeekFrom::Start(self.start + self.pos))?;
+        let read = self.file.read(&mut buf[..max])?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for FileWindow {
+    /// Moves the window position; offsets are relative to the window, not the underlying file.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+        match new_pos {
+            Some(new_pos) => {
+                self.pos = new_pos;
+                Ok(new_pos)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+/// The backing storage of a split APK opened from an outer container.
+enum SplitApkSource {
+    /// A stored split APK, streamed straight from the outer container file.
+    Window(FileWindow),
+    /// A compressed split APK, which has to be inflated into memory once.
+    Memory(Cursor<Vec<u8>>),
+}
+
+impl Read for SplitApkSource {
+    /// Reads from the underlying window or memory buffer.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            SplitApkSource::Window(window) => window.read(buf),
+            SplitApkSource::Memory(cursor) => cursor.read(buf),
+        }
+    }
+}
+
+impl Seek for SplitApkSource {
+    /// Seeks within the underlying window or memory buffer.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            SplitApkSource::Window(window) => window.seek(pos),
+            SplitApkSource::Memory(cursor) => cursor.seek(pos),
+        }
+    }
+}
+
+/// The split APKs of an outer container (XAPK, APKS, APKM), opened once and kept open.
+///
+/// Each split APK is parsed as a ZIP archive a single time when the container is opened. Stored
+/// split APKs, which is how containers normally hold them, are read straight from the container
+/// file through a [`FileWindow`], so memory use stays flat no matter how many files are read.
+/// Compressed split APKs cannot be seeked into, so they are inflated into memory once instead.
+///
+/// The `SplitApkSet` structure holds:
+/// - the opened split APK archives, keyed by their filename within the container,
+/// - the filenames of the opened split APKs, in the order they were indexed,
+/// - an index mapping internal file paths to their entry, including the split APK that provides them.
+pub(super) struct SplitApkSet {
+    /// The opened split APK archives, keyed by their filename within the container.
+    archives: HashMap<String, ZipArchive<SplitApkSource>>,
+    /// The filenames of the opened split APKs, in the order they were indexed.
+    split_order: Vec<String>,
+    /// Index mapping an internal file path to its entry in the split APK that provides it.
+    file_map: HashMap<String, ArchiveEntry>,
+}
+
+impl SplitApkSet {
+    /// Opens the given split APKs of an outer container and builds an index that maps internal
+    /// file paths to the split APK that contains them.
+    ///
+    /// Split APKs are indexed in the order they are yielded, so if multiple split APKs contain the
+    /// same file, entries from later split APKs override earlier ones. Split APKs that are missing
+    /// from the container are skipped.
+    ///
+    /// # Arguments
+    /// - `file`: The open outer container file.
+    /// - `split_files`: The filenames of the split APKs within the container.
+    ///
+    /// # Errors
+    /// Returns an error if the container or a split APK is not a valid ZIP archive, or if any IO
+    /// error occurs.
+    pub fn open<'s>(file: &File, split_files: impl IntoIterator<Item = &'s str>) -> Result<Self> {
+        let mut outer_archive = ZipArchive::new(file.try_clone()?)?;
+        let mut archives = HashMap::new();
+        let mut split_order = Vec::new();
+        let mut file_map = HashMap::new();
+
+        for split_file in split_files {
+            // Attempt to locate the split APK file within the outer archive.
+            let mut apk_entry = match outer_archive.by_name(split_file) {
+                Ok(entry) => entry,
+                Err(_) => continue, // Skip if the split APK file is missing.
+            };
+
+            let source = if apk_entry.compression() == CompressionMethod::Stored {
+                // Stored data is the split APK itself, so it can be read in place.
+                let window =
+                    FileWindow::new(file.try_clone()?, apk_entry.data_start(), apk_entry.size());
+                SplitApkSource::Window(window)
+            } else {
+                debug!(
+                    "Split APK {} is compressed, reading it into memory",
+                    split_file
+                );
+                let mut apk_bytes = Vec::with_capacity(apk_entry.size() as usize);
+                apk_entry.read_to_end(&mut apk_bytes)?;
+                SplitApkSource::Memory(Cursor::new(apk_bytes))
+            };
+            // End the mutable borrow for the split APK entry.
+            drop(apk_entry);
+
+            let mut apk_archive = ZipArchive::new(source)?;
+            // Iterate over all files within the split APK.
+            for i in 0..apk_archive.len() {
+                // Raw access only parses the entry headers, which is all the index needs.
+                let file_entry = apk_archive.by_index_raw(i)?;
+                if file_entry.is_dir() {
+                    continue;
+                }
+                let internal_path = file_entry.name().to_string();
+                // Map the internal file path to its entry. Later entries override earlier ones.
+                file_map.insert(
+                    internal_path.clone(),
+                    ArchiveEntry {
+                        path: internal_path,
+                        size: file_entry.size(),
+                        compressed_size: file_entry.compressed_size(),
+                        split_apk: Some(split_file.to_string()),
+                    },
+                );
+            }
+            archives.insert(split_file.to_string(), apk_archive);
+            split_order.push(split_file.to_string());
+        }
+
+        Ok(Self {
+            archives,
+            split_order,
+            file_map,
+        })
+    }
+
+    /// Reads an internal file from the split APK that provides it.
+    ///
+    /// # Arguments
+    /// - `internal_path`: The path of the internal file to be read (as it appears within a split APK).
+    ///
+    /// # Errors
+    /// Returns an error if the internal file cannot be found, or if any IO or ZIP processing error
+    /// occurs during the read operation.
+    pub fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
+        // Look up which split APK file contains the requested internal file.
+        let split_apk_filename = self
+            .file_map
+            .get(internal_path)
+            .and_then(|entry| entry.split_apk.as_deref())
+            .ok_or(anyhow!("File not found"))?;
+        let apk_archive = self
+            .archives
+            .get_mut(split_apk_filename)
+            .ok_or(anyhow!("Split APK not found"))?;
+
+        // Locate and read the requested internal file from the split APK.
+        let mut file_entry = apk_archive.by_name(internal_path)?;
+        let mut file_contents = Vec::with_capacity(file_entry.size() as usize);
+        file_entry.read_to_end(&mut file_contents)?;
+        Ok(file_contents)
+    }
+
+    /// Reads the binary `AndroidManifest.xml` of the base APK.
+    ///
+    /// Every split APK carries its own manifest, so the file index cannot tell which one belongs
+    /// to the base APK. Instead, the manifests are checked in index order and the first one that
+    /// does not declare a `split` name is returned.
+    ///
+    /// # Errors
+    /// Returns an error if no split APK has a base manifest, or if reading or decoding fails.
+    pub fn read_base_manifest(&mut self) -> Result<Vec<u8>> {
+        for split_file in &self.split_order {
+            let Some(apk_archive) = self.archives.get_mut(split_file) else {
+                continue;
+            };
+            let Ok(mut manifest_entry) = apk_archive.by_name(ANDROID_MANIFEST_PATH) else {
+                continue;
+            };
+            let mut manifest_data = Vec::with_capacity(manifest_entry.size() as usize);
+            manifest_entry.read_to_end(&mut manifest_data)?;
+            if !is_split_manifest(&AxmlDocument::parse(&manifest_data)?) {
+                return Ok(manifest_data);
+            }
+        }
+        Err(anyhow!("No base APK found among the split APKs"))
+    }
+
+    /// Lists every file provided by the split APKs, sorted by path.
+    ///
+    /// The listing is served from the index built when the split APKs were opened, so no split
+    /// APK has to be read again.
+    pub fn list_entries(&self) -> Vec<ArchiveEntry> {
+        let mut entries: Vec<ArchiveEntry> = self.file_map.values().cloned().collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries
+    }
+}
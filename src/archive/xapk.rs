@@ -1,9 +1,9 @@
-use crate::archive::AndroidArchive;
-use anyhow::{anyhow, Result};
-use hashbrown::HashMap;
+use crate::archive::split_apks::SplitApkSet;
+use crate::archive::{AndroidArchive, ArchiveEntry};
+use anyhow::Result;
 use serde::Deserialize;
 use std::fs::File;
-use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::io::{self, Read};
 use std::path::Path;
 use zip::read::ZipArchive;
 
@@ -44,14 +44,11 @@ pub struct SplitApk {
 
 /// Encapsulates an open XAPK file and provides methods for accessing its internal files.
 ///
-/// The `XApkFile` structure holds:
-/// - an open file handle for the XAPK,
-/// - an index mapping internal file paths (found within the APKs inside) to the split APK filename that contains them.
+/// The split APKs listed in the manifest are opened once when the XAPK is opened and kept open
+/// for all subsequent reads (see [`SplitApkSet`]).
 pub struct XApkFile {
-    /// The open XAPK file handle.
-    file: File,
-    /// Index mapping an internal file path to the split APK filename that provides it.
-    file_map: HashMap<String, String>,
+    /// The split APKs of the XAPK, along with the index of the files they provide.
+    split_apks: SplitApkSet,
 }
 
 impl XApkFile {
@@ -67,59 +64,28 @@ impl XApkFile {
     /// Returns an `io::Error` if the file cannot be opened, the manifest cannot be read, or any IO or parsing error occurs.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
         // Open the XAPK file.
-        let mut file = File::open(path)?;
-
-        // Use an inner block to limit the lifetime of mutable borrows from `file`.
-        let file_map = {
-            // Create a ZipArchive for reading the XAPK file's contents.
-            let mut xapk_archive = ZipArchive::new(&mut file)?;
-
-            // Retrieve and read the manifest.json file from the archive.
-            let mut manifest_file = xapk_archive.by_name("manifest.json")?;
-            let mut manifest_contents = String::new();
-            manifest_file.read_to_string(&mut manifest_contents)?;
-            // End the mutable borrow for the manifest file.
-            drop(manifest_file);
-
-            // Deserialize the manifest contents into a Manifest struct.
-            let manifest: Manifest = serde_json::from_str(&manifest_contents)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-
-            // Build an index mapping internal file paths to the split APK filename that contains them.
-            let mut file_map = HashMap::new();
-            for split_apk in &manifest.split_apks {
-                // Attempt to locate the split APK file within the XAPK archive.
-                let mut apk_entry = match xapk_archive.by_name(&split_apk.file) {
-                    Ok(entry) => entry,
-                    Err(_) => continue, // Skip if the split APK file is missing.
-                };
-
-                // Read the entire contents of the split APK file into memory.
-                let mut apk_bytes = Vec::new();
-                apk_entry.read_to_end(&mut apk_bytes)?;
-                // End the mutable borrow for the split APK entry.
-                drop(apk_entry);
-
-                // Open the split APK as a ZIP archive using an in-memory cursor.
-                let cursor = Cursor::new(apk_bytes);
-                let mut apk_archive = ZipArchive::new(cursor)?;
-                // Iterate over all files within the split APK.
-                for i in 0..apk_archive.len() {
-                    let file_entry = apk_archive.by_index(i)?;
-                    let internal_path = file_entry.name().to_string();
-                    // Map the internal file path to the split APK filename. Later entries override earlier ones.
-                    file_map.insert(internal_path, split_apk.file.clone());
-                }
-            }
-
-            // Return the completed file mapping.
-            file_map
-        }; // End inner block; all mutable borrows from xapk_archive have ended.
-
-        // Reset the file cursor to the beginning for subsequent operations.
-        file.seek(SeekFrom::Start(0))?;
-
-        Ok(Self { file, file_map })
+        let file = File::open(path)?;
+
+        // Retrieve and read the manifest.json file from the archive.
+        let mut xapk_archive = ZipArchive::new(&file)?;
+        let mut manifest_file = xapk_archive.by_name("manifest.json")?;
+        let mut manifest_contents = String::new();
+        manifest_file.read_to_string(&mut manifest_contents)?;
+
+        // Deserialize the manifest contents into a Manifest struct.
+        let manifest: Manifest = serde_json::from_str(&manifest_contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        // Open the split APKs and build an index mapping internal file paths to the split APK that contains them.
+        let split_apks = SplitApkSet::open(
+            &file,
+            manifest
+                .split_apks
+                .iter()
+                .map(|split_apk| split_apk.file.as_str()),
+        )?;
+
+        Ok(Self { split_apks })
     }
 
     /// Reads an internal file from one of the split APKs contained within the XAPK.
@@ -134,42 +100,27 @@ impl XApkFile {
     /// Returns an `io::Error` if the internal file or its corresponding split APK cannot be found,
     /// or if any IO or ZIP processing error occurs during the read operation.
     pub fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
-        // Look up which split APK file contains the requested internal file.
-        let split_apk_filename = self
-            .file_map
-            .get(internal_path)
-            .ok_or(anyhow!("File not found"))?;
-
-        // Reset the XAPK file's cursor to the beginning.
-        self.file.seek(SeekFrom::Start(0))?;
-        let mut xapk_archive = ZipArchive::new(&mut self.file)?;
-
-        // Locate and read the split APK file entry from the XAPK archive.
-        let mut split_apk_entry = xapk_archive.by_name(split_apk_filename)?;
-        let mut split_apk_bytes = Vec::new();
-        split_apk_entry.read_to_end(&mut split_apk_bytes)?;
-        // End the mutable borrow for the split APK entry.
-        drop(split_apk_entry);
+        self.split_apks.read_internal_file(internal_path)
+    }
 
-        // Open the split APK as a ZIP archive from the in-memory buffer.
-        let cursor = Cursor::new(split_apk_bytes);
-        let mut apk_archive = ZipArchive::new(cursor)?;
+    /// Lists every file provided by the split APKs, sorted by path.
+    pub fn list_entries(&self) -> Vec<ArchiveEntry> {
+        self.split_apks.list_entries()
+    }
 
-        // Locate and read the requested internal file from the split APK.
-        let mut file_entry = apk_archive.by_name(internal_path)?;
-        let mut file_contents = Vec::new();
-        file_entry.read_to_end(&mut file_contents)?;
-        Ok(file_contents)
+    /// Reads the binary `AndroidManifest.xml` of the base APK.
+    pub fn read_base_manifest(&mut self) -> Result<Vec<u8>> {
+        self.split_apks.read_base_manifest()
     }
 }
 
-/// Implements the AndroidArchive trait for ApkFile.
+/// Implements the AndroidArchive trait for XApkFile.
 ///
-/// This allows an ApkFile to be used wherever an AndroidArchive is expected.
+/// This allows an XApkFile to be used wherever an AndroidArchive is expected.
 impl AndroidArchive for XApkFile {
-    /// Reads an internal file from the APK archive.
+    /// Reads an internal file from the XAPK archive.
     ///
-    /// Delegates to the `read_internal_file` method of `ApkFile`.
+    /// Delegates to the `read_internal_file` method of `XApkFile`.
     ///
     /// # Arguments
     ///
@@ -182,4 +133,18 @@ impl AndroidArchive for XApkFile {
     fn read_internal_file(&mut self, internal_path: &str) -> Result<Vec<u8>> {
         XApkFile::read_internal_file(self, internal_path)
     }
+
+    /// Lists every file contained within the XAPK archive.
+    ///
+    /// Delegates to the `list_entries` method of `XApkFile`.
+    fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>> {
+        Ok(XApkFile::list_entries(self))
+    }
+
+    /// Reads the manifest of the base APK rather than that of a configuration split.
+    ///
+    /// Delegates to the `read_base_manifest` method of `XApkFile`.
+    fn read_base_manifest(&mut self) -> Result<Vec<u8>> {
+        XApkFile::read_base_manifest(self)
+    }
 }
@@ -0,0 +1,81 @@
+use clap::ValueEnum;
+use goblin::elf::header::{EM_386, EM_AARCH64, EM_ARM, EM_X86_64};
+use std::fmt::{Display, Formatter};
+use strum::EnumIter;
+
+/// The filename of the IL2CPP binary inside each ABI-specific library directory.
+pub const IL2CPP_FILENAME: &str = "libil2cpp.so";
+
+/// Represents an Android ABI (application binary interface) that native libraries are built for.
+///
+/// The variants are declared in order of preference: when an archive ships libraries for several
+/// ABIs, the first one present is selected. 64-bit ABIs come first, since they are the only ones
+/// that can be fully analyzed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, EnumIter)]
+pub enum Abi {
+    /// 64-bit ARM, used by virtually all modern devices.
+    #[value(name = "arm64-v8a")]
+    Arm64V8a,
+    /// 64-bit x86, used by emulators.
+    #[value(name = "x86_64")]
+    X86_64,
+    /// 32-bit ARM, used by older devices.
+    #[value(name = "armeabi-v7a")]
+    ArmeabiV7a,
+    /// 32-bit x86, used by older emulators.
+    #[value(name = "x86")]
+    X86,
+}
+
+impl Abi {
+    /// Returns the Android name of the ABI, which is also its library directory name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Abi::Arm64V8a => "arm64-v8a",
+            Abi::X86_64 => "x86_64",
+            Abi::ArmeabiV7a => "armeabi-v7a",
+            Abi::X86 => "x86",
+        }
+    }
+
+    /// Returns `true` if binaries for this ABI use 64-bit pointers.
+    pub fn is_64_bit(&self) -> bool {
+        matches!(self, Abi::Arm64V8a | Abi::X86_64)
+    }
+
+    /// Returns the size in bytes of a pointer for this ABI.
+    pub fn pointer_size(&self) -> usize {
+        if self.is_64_bit() {
+            8
+        } else {
+            4
+        }
+    }
+
+    /// Returns the path of the IL2CPP binary for this ABI within an APK.
+    pub fn il2cpp_path(&self) -> String {
+        format!("lib/{}/{}", self.name(), IL2CPP_FILENAME)
+    }
+
+    /// Determines the ABI from the `e_machine` field of an ELF header.
+    ///
+    /// # Returns
+    /// - `Some(Abi)` if the machine type belongs to a supported Android ABI.
+    /// - `None` otherwise.
+    pub fn from_elf_machine(e_machine: u16) -> Option<Abi> {
+        match e_machine {
+            EM_AARCH64 => Some(Abi::Arm64V8a),
+            EM_X86_64 => Some(Abi::X86_64),
+            EM_ARM => Some(Abi::ArmeabiV7a),
+            EM_386 => Some(Abi::X86),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Abi {
+    /// Formats the ABI using its Android name.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
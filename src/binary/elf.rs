@@ -1,8 +1,9 @@
+use crate::binary::abi::Abi;
 use crate::binary::arm64::SIZEOF_ARM64_INSTRUCTION;
 use crate::binary::search::find_pattern;
 use anyhow::{anyhow, bail, Result};
+use goblin::elf::reloc;
 use goblin::elf32::program_header::{PF_W, PF_X, PT_LOAD};
-use goblin::elf64::reloc;
 use hashbrown::HashMap;
 use log::debug;
 use nohash_hasher::IntMap;
@@ -16,10 +17,50 @@ type RelocMap = IntMap<i64, Vec<u64>>;
 // Define the pointer size for a 64-bit target.
 pub const POINTER_SIZE: usize = size_of::<u64>();
 
+/// How a dynamic relocation computes the value it writes, independent of the architecture.
+enum RelocKind {
+    /// Base address + addend. The base address is assumed to be zero.
+    Relative,
+    /// The address of the referenced symbol.
+    Symbol,
+    /// The address of the referenced symbol + addend.
+    SymbolAddend,
+}
+
+impl RelocKind {
+    /// Maps an architecture-specific relocation type to its kind.
+    ///
+    /// # Returns
+    /// - `Some(RelocKind)` if the relocation type is handled for the given ABI.
+    /// - `None` otherwise.
+    fn from_type(abi: Abi, r_type: u32) -> Option<Self> {
+        match (abi, r_type) {
+            (Abi::Arm64V8a, reloc::R_AARCH64_RELATIVE)
+            | (Abi::X86_64, reloc::R_X86_64_RELATIVE)
+            | (Abi::ArmeabiV7a, reloc::R_ARM_RELATIVE)
+            | (Abi::X86, reloc::R_386_RELATIVE) => Some(RelocKind::Relative),
+
+            (Abi::Arm64V8a, reloc::R_AARCH64_GLOB_DAT | reloc::R_AARCH64_JUMP_SLOT)
+            | (Abi::X86_64, reloc::R_X86_64_GLOB_DAT | reloc::R_X86_64_JUMP_SLOT)
+            | (Abi::ArmeabiV7a, reloc::R_ARM_GLOB_DAT | reloc::R_ARM_JUMP_SLOT)
+            | (Abi::X86, reloc::R_386_GLOB_DAT | reloc::R_386_JMP_SLOT) => Some(RelocKind::Symbol),
+
+            (Abi::Arm64V8a, reloc::R_AARCH64_ABS64)
+            | (Abi::X86_64, reloc::R_X86_64_64)
+            | (Abi::ArmeabiV7a, reloc::R_ARM_ABS32)
+            | (Abi::X86, reloc::R_386_32) => Some(RelocKind::SymbolAddend),
+
+            _ => None,
+        }
+    }
+}
+
 /// Represents an ELF file with both its original and modified data, as well as metadata
 /// (like section ranges and applied relocations). The `inner` field holds the parsed ELF.
 pub struct Elf<'a> {
     pub inner: goblin::elf::Elf<'a>,
+    // The ABI the binary was built for, determined from the ELF machine type.
+    pub abi: Abi,
     // Modified file data (e.g. after relocations have been applied)
     pub data: Vec<u8>,
     // The original file data, before any modifications.
@@ -37,28 +78,39 @@ impl<'a> Elf<'a> {
     ///
     /// This function:
     /// - Parses the raw ELF data.
-    /// - Ensures that the ELF is 64-bit.
+    /// - Determines the ABI from the ELF machine type.
     /// - Applies dynamic relocations to update the file data.
     /// - Builds a map of section names to file ranges.
     /// - Transmutes the lifetime of the parsed ELF for internal use.
     pub fn new(data: Vec<u8>) -> Result<Elf<'a>> {
-        debug!("Loading IL2CPP as ELF64...");
+        debug!("Loading IL2CPP as ELF...");
 
         let original_data = data;
         let elf = goblin::elf::Elf::parse(&original_data)?;
 
-        if !elf.is_64 {
-            bail!("Only 64-bit ELF files are supported");
+        let abi = Abi::from_elf_machine(elf.header.e_machine).ok_or(anyhow!(
+            "Unsupported ELF machine type: {}",
+            elf.header.e_machine
+        ))?;
+        if abi.is_64_bit() != elf.is_64 {
+            bail!("ELF class does not match the {} machine type", abi);
         }
+        debug!("ELF ABI: {}", abi);
 
         debug!("Applying ELF dynamic relocations...");
-        let (data, relocations) = Self::apply_dynamic_relocations(&elf, original_data.clone())?;
+        let (data, relocations) =
+            Self::apply_dynamic_relocations(&elf, abi, original_data.clone())?;
 
         debug!("Building ELF section mapping...");
         let sections = Self::get_section_slices(&elf);
 
-        debug!("Building ELF section instructions...");
-        let instructions = Self::get_instruction_chunks(&elf, &data);
+        // Fixed-size instruction chunks only make sense for ARM64 code.
+        let instructions = if abi == Abi::Arm64V8a {
+            debug!("Building ELF section instructions...");
+            Self::get_instruction_chunks(&elf, &data)
+        } else {
+            HashMap::new()
+        };
 
         // SAFETY: We transmute the lifetime of 'elf' to '`a'
         // because `original_data` will remain owned by this struct and will not be moved.
@@ -66,6 +118,7 @@ impl<'a> Elf<'a> {
 
         Ok(Elf {
             inner,
+            abi,
             data,
             original_data,
             relocations,
@@ -78,66 +131,74 @@ impl<'a> Elf<'a> {
     /// and updating the in-memory file data.
     ///
     /// For each relocation, the code converts the target virtual address to a file offset,
-    /// then writes the correct relocated value depending on the relocation type.
+    /// then writes the correct relocated value depending on the relocation type. Relocation types
+    /// are handled for every supported ABI. 64-bit ABIs use RELA entries with explicit addends,
+    /// while 32-bit ABIs use REL entries whose addend is the value already stored at the target.
     pub fn apply_dynamic_relocations(
         elf: &goblin::elf::Elf,
+        abi: Abi,
         mut data: Vec<u8>,
     ) -> Result<(Vec<u8>, RelocMap)> {
+        let pointer_size = abi.pointer_size();
         let mut relocs = RelocMap::default();
-        for rela in &elf.dynrelas {
-            let target_va = rela.r_offset;
-            let addend = match rela.r_addend {
+        for rel in elf.dynrelas.iter().chain(elf.dynrels.iter()) {
+            let target_va = rel.r_offset;
+
+            // Convert the target virtual address (VA) to a file offset.
+            let Some(file_offset) = Self::inner_va_to_file_offset(elf, target_va) else {
+                // Zero-initialized memory (e.g. `.bss`) has no file data to update.
+                if Self::inner_is_zero_fill_va(elf, target_va) {
+                    continue;
+                }
+                bail!(
+                    "Could not find file offset for relocation at VA 0x{:x}",
+                    target_va
+                );
+            };
+            let target = file_offset as usize..file_offset as usize + pointer_size;
+            if target.end > data.len() {
+                bail!("Relocation at VA 0x{:x} is out of bounds", target_va);
+            }
+
+            let Some(kind) = RelocKind::from_type(abi, rel.r_type) else {
+                bail!(
+                    "Unhandled {} relocation type: {} at 0x{:x}",
+                    abi,
+                    rel.r_type,
+                    target_va
+                );
+            };
+
+            let addend = match rel.r_addend {
                 Some(addend) => {
                     // Record the addend with its target address for later reference.
                     relocs.entry(addend).or_default().push(target_va);
                     addend
                 }
+                // REL entries store the addend in place of the relocated value.
+                None if pointer_size == 4 => {
+                    let addend =
+                        u32::from_le_bytes(data[target.clone()].try_into().unwrap()) as i64;
+                    if matches!(kind, RelocKind::Relative) {
+                        relocs.entry(addend).or_default().push(target_va);
+                    }
+                    addend
+                }
                 None => 0,
             };
 
-            // Convert the target virtual address (VA) to a file offset.
-            if let Some(file_offset) = Self::inner_va_to_file_offset(elf, target_va) {
-                match rela.r_type {
-                    reloc::R_AARCH64_RELATIVE => {
-                        // For R_AARCH64_RELATIVE, the new value is (base address + addend).
-                        // Here the base address is assumed to be zero so the relocation value equals the addend.
-                        let bytes = (addend as u64).to_le_bytes();
-                        data[file_offset as usize..file_offset as usize + 8]
-                            .copy_from_slice(&bytes);
-                    }
-
-                    reloc::R_AARCH64_GLOB_DAT | reloc::R_AARCH64_JUMP_SLOT => {
-                        // These relocations update the pointer to point to the resolved symbol.
-                        let symbol_addr = Self::inner_resolve_symbol(elf, rela.r_sym)?;
-                        let bytes = symbol_addr.to_le_bytes();
-                        data[file_offset as usize..file_offset as usize + 8]
-                            .copy_from_slice(&bytes);
-                    }
-
-                    reloc::R_AARCH64_ABS64 => {
-                        // Absolute relocation: value = symbol address + addend.
-                        let symbol_addr = Self::inner_resolve_symbol(elf, rela.r_sym)?;
-                        let relocation_value = symbol_addr + addend as u64;
-                        let bytes = relocation_value.to_le_bytes();
-
-                        data[file_offset as usize..file_offset as usize + 8]
-                            .copy_from_slice(&bytes);
-                    }
-
-                    _ => {
-                        bail!(
-                            "Unhandled relocation type: {} at 0x{:x}",
-                            rela.r_type,
-                            target_va
-                        );
-                    }
+            let value = match kind {
+                // The base address is assumed to be zero so the relocation value equals the addend.
+                RelocKind::Relative => addend as u64,
+                // These relocations update the pointer to point to the resolved symbol.
+                RelocKind::Symbol => Self::inner_resolve_symbol(elf, rel.r_sym)?,
+                // Absolute relocation: value = symbol address + addend.
+                RelocKind::SymbolAddend => {
+                    Self::inner_resolve_symbol(elf, rel.r_sym)?.wrapping_add(addend as u64)
                 }
-            } else {
-                bail!(
-                    "Could not find file offset for relocation at VA 0x{:x}",
-                    target_va
-                );
-            }
+            };
+
+            data[target].copy_from_slice(&value.to_le_bytes()[..pointer_size]);
         }
 
         Ok((data, relocs))
@@ -234,6 +295,14 @@ impl<'a> Elf<'a> {
         None
     }
 
+    /// Checks whether `va` lies in the zero-filled part of a loadable segment, i.e. beyond the
+    /// data stored in the file but within the segment's memory size.
+    fn inner_is_zero_fill_va(elf: &goblin::elf::Elf, va: u64) -> bool {
+        elf.program_headers.iter().any(|ph| {
+            ph.p_type == PT_LOAD && ph.p_vaddr + ph.p_filesz <= va && va < ph.p_vaddr + ph.p_memsz
+        })
+    }
+
     /// Converts a file offset back to its corresponding virtual address.
     pub fn file_offset_to_va(&self, file_offset: u64) -> Option<u64> {
         Self::inner_file_offset_to_va(&self.inner, file_offset)
@@ -0,0 +1,281 @@
+use anyhow::{anyhow, bail, Result};
+use goblin::elf::section_header::{
+    SectionHeader, SHF_ALLOC, SHF_EXECINSTR, SHN_LORESERVE, SHN_UNDEF, SHT_STRTAB, SHT_SYMTAB,
+};
+use goblin::elf::sym::{STB_GLOBAL, STT_FUNC, STV_DEFAULT};
+
+// Sizes of the 64-bit ELF structures written by the writer.
+const SIZEOF_SECTION_HEADER: usize = 64;
+const SIZEOF_SYMBOL: usize = 24;
+
+// Offsets of the section header table fields in the 64-bit ELF header.
+const E_SHOFF_OFFSET: usize = 0x28;
+const E_SHENTSIZE_OFFSET: usize = 0x3A;
+const E_SHNUM_OFFSET: usize = 0x3C;
+const E_SHSTRNDX_OFFSET: usize = 0x3E;
+
+/// Appends sections to a 64-bit little-endian ELF file.
+///
+/// Sections that are not loaded at runtime, such as a symbol table, can be added to a finished
+/// binary without moving any of its code or data: the data of the new sections is appended to
+/// the end of the file, followed by a rebuilt section header string table and a new section
+/// header table. The ELF header is then pointed at the new table. The old table and string table
+/// stay in the file, unreferenced, so every existing file offset remains valid.
+pub struct ElfWriter {
+    /// The file data, with the data of every added section appended.
+    data: Vec<u8>,
+    /// The section headers, starting with those of the original file.
+    headers: Vec<SectionHeader>,
+    /// The names of all sections, as stored in the section header string table.
+    shstrtab: Vec<u8>,
+    /// The index of the section header string table.
+    shstrndx: usize,
+}
+
+impl ElfWriter {
+    /// Prepares an ELF file for adding sections.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a 64-bit little-endian ELF file, or if its section
+    /// header string table cannot be read.
+    pub fn new(data: &[u8]) -> Result<Self> {
+        let elf = goblin::elf::Elf::parse(data)?;
+        if !elf.is_64 || !elf.little_endian {
+            bail!("Only 64-bit little-endian ELF files can be written");
+        }
+
+        let mut headers = elf.section_headers.clone();
+        if headers.is_empty() {
+            // A file without sections still needs the null section at index 0.
+            headers.push(SectionHeader::default());
+        }
+        let shstrndx = elf.header.e_shstrndx as usize;
+        let (shstrtab, shstrndx) = if shstrndx == SHN_UNDEF as usize {
+            // Without a section header string table, all existing sections are unnamed, and the
+            // new table gets its own section rather than overwriting the null section.
+            (vec![0], None)
+        } else {
+            let header = headers.get(shstrndx).ok_or_else(|| {
+                anyhow!("Section header string table {} does not exist", shstrndx)
+            })?;
+            let range = header.file_range().unwrap_or_default();
+            let shstrtab = data
+                .get(range)
+                .ok_or_else(|| anyhow!("Section header string table is out of bounds"))?
+                .to_vec();
+            (shstrtab, Some(shstrndx))
+        };
+
+        let mut writer = ElfWriter {
+            data: data.to_vec(),
+            headers,
+            shstrtab,
+            shstrndx: shstrndx.unwrap_or(0),
+        };
+        if shstrndx.is_none() {
+            writer.shstrndx = writer.push_header(".shstrtab", SHT_STRTAB, 1, 0);
+        }
+        Ok(writer)
+    }
+
+    /// Returns the index of the first section with the given name.
+    pub fn section_index(&self, name: &str) -> Option<usize> {
+        self.headers
+            .iter()
+            .position(|header| self.section_name(header) == Some(name))
+    }
+
+    /// Returns the name of a section from the section header string table.
+    fn section_name(&self, header: &SectionHeader) -> Option<&str> {
+        let name = self.shstrtab.get(header.sh_name..)?;
+        let end = name.iter().position(|&c| c == 0)?;
+        std::str::from_utf8(&name[..end]).ok()
+    }
+
+    /// Appends a section that is not loaded at runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the section, such as `.symtab`.
+    /// * `sh_type` - The section type, such as `SHT_SYMTAB`.
+    /// * `link` - The index of a section this section refers to, or 0.
+    /// * `info` - Extra information whose meaning depends on the section type.
+    /// * `align` - The alignment of the section data in the file.
+    /// * `entry_size` - The size of each entry, for sections that hold a table.
+    /// * `section_data` - The contents of the section.
+    ///
+    /// # Returns
+    ///
+    /// The index of the new section.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_section(
+        &mut self,
+        name: &str,
+        sh_type: u32,
+        link: u32,
+        info: u32,
+        align: u64,
+        entry_size: u64,
+        section_data: &[u8],
+    ) -> usize {
+        self.align_data(align as usize);
+        let offset = self.data.len();
+        self.data.extend_from_slice(section_data);
+
+        let index = self.push_header(name, sh_type, align, entry_size);
+        let header = &mut self.headers[index];
+        header.sh_offset = offset as u64;
+        header.sh_size = section_data.len() as u64;
+        header.sh_link = link;
+        header.sh_info = info;
+        index
+    }
+
+    /// Adds a `.symtab` and `.strtab` section with one global `STT_FUNC` symbol per function.
+    ///
+    /// Each function is assigned to the executable section containing its address, and is sized
+    /// up to the start of the next function, or the end of its section for the last function of
+    /// a section. Functions outside of every executable section are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `functions` - The address and name of each function.
+    ///
+    /// # Returns
+    ///
+    /// The number of symbols written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file already has a symbol table, or has too many sections for a
+    /// symbol to refer to its section directly.
+    pub fn add_function_symbols(&mut self, functions: &[(u64, &str)]) -> Result<usize> {
+        if self.section_index(".symtab").is_some() {
+            bail!("ELF file already has a .symtab section");
+        }
+        if self.headers.len() >= SHN_LORESERVE as usize {
+            bail!("ELF file has too many sections for a symbol table");
+        }
+
+        let code_sections = self
+            .headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| {
+                let flags = (SHF_ALLOC | SHF_EXECINSTR) as u64;
+                header.sh_flags & flags == flags
+            })
+            .map(|(index, header)| (index, header.sh_addr..header.sh_addr + header.sh_size))
+            .collect::<Vec<_>>();
+
+        let mut functions = functions.to_vec();
+        functions.sort_unstable_by_key(|&(address, _)| address);
+
+        let mut strtab = vec![0u8];
+        // The symbol table always starts with the null symbol.
+        let mut symtab = vec![0u8; SIZEOF_SYMBOL];
+        let mut count = 0;
+        for (position, &(address, name)) in functions.iter().enumerate() {
+            let Some((section_index, section_range)) = code_sections
+                .iter()
+                .find(|(_, range)| range.contains(&address))
+            else {
+                continue;
+            };
+            let end = functions
+                .get(position + 1)
+                .map_or(section_range.end, |&(next, _)| next.min(section_range.end));
+
+            let name_offset = strtab.len() as u32;
+            strtab.extend_from_slice(name.as_bytes());
+            strtab.push(0);
+
+            symtab.extend_from_slice(&name_offset.to_le_bytes());
+            symtab.push((STB_GLOBAL << 4) | STT_FUNC);
+            symtab.push(STV_DEFAULT);
+            symtab.extend_from_slice(&(*section_index as u16).to_le_bytes());
+            symtab.extend_from_slice(&address.to_le_bytes());
+            symtab.extend_from_slice(&(end - address).to_le_bytes());
+            count += 1;
+        }
+
+        let strtab_index = self.add_section(".strtab", SHT_STRTAB, 0, 0, 1, 0, &strtab);
+        // The info field of a symbol table holds the index of its first global symbol.
+        self.add_section(
+            ".symtab",
+            SHT_SYMTAB,
+            strtab_index as u32,
+            1,
+            8,
+            SIZEOF_SYMBOL as u64,
+            &symtab,
+        );
+        Ok(count)
+    }
+
+    /// Writes the section header string table and the section header table, and returns the
+    /// finished file.
+    pub fn finish(mut self) -> Vec<u8> {
+        let shstrtab = std::mem::take(&mut self.shstrtab);
+        let shstrtab_offset = self.data.len();
+        self.data.extend_from_slice(&shstrtab);
+        let header = &mut self.headers[self.shstrndx];
+        header.sh_offset = shstrtab_offset as u64;
+        header.sh_size = shstrtab.len() as u64;
+
+        self.align_data(8);
+        let shoff = self.data.len();
+        for header in &self.headers {
+            self.data.extend_from_slice(&encode_section_header(header));
+        }
+
+        let shnum = self.headers.len() as u16;
+        let shstrndx = self.shstrndx as u16;
+        let data = &mut self.data;
+        data[E_SHOFF_OFFSET..E_SHOFF_OFFSET + 8].copy_from_slice(&(shoff as u64).to_le_bytes());
+        data[E_SHENTSIZE_OFFSET..E_SHENTSIZE_OFFSET + 2]
+            .copy_from_slice(&(SIZEOF_SECTION_HEADER as u16).to_le_bytes());
+        data[E_SHNUM_OFFSET..E_SHNUM_OFFSET + 2].copy_from_slice(&shnum.to_le_bytes());
+        data[E_SHSTRNDX_OFFSET..E_SHSTRNDX_OFFSET + 2].copy_from_slice(&shstrndx.to_le_bytes());
+        self.data
+    }
+
+    /// Adds a section header with the given name and returns its index. The data of the section
+    /// is filled in by the caller.
+    fn push_header(&mut self, name: &str, sh_type: u32, align: u64, entry_size: u64) -> usize {
+        let sh_name = self.shstrtab.len();
+        self.shstrtab.extend_from_slice(name.as_bytes());
+        self.shstrtab.push(0);
+        self.headers.push(SectionHeader {
+            sh_name,
+            sh_type,
+            sh_addralign: align,
+            sh_entsize: entry_size,
+            ..Default::default()
+        });
+        self.headers.len() - 1
+    }
+
+    /// Pads the file data with zeros to a multiple of `align`.
+    fn align_data(&mut self, align: usize) {
+        let len = self.data.len().next_multiple_of(align.max(1));
+        self.data.resize(len, 0);
+    }
+}
+
+/// Encodes a section header in the 64-bit little-endian layout.
+fn encode_section_header(header: &SectionHeader) -> [u8; SIZEOF_SECTION_HEADER] {
+    let mut out = [0u8; SIZEOF_SECTION_HEADER];
+    out[0..4].copy_from_slice(&(header.sh_name as u32).to_le_bytes());
+    out[4..8].copy_from_slice(&header.sh_type.to_le_bytes());
+    out[8..16].copy_from_slice(&header.sh_flags.to_le_bytes());
+    out[16..24].copy_from_slice(&header.sh_addr.to_le_bytes());
+    out[24..32].copy_from_slice(&header.sh_offset.to_le_bytes());
+    out[32..40].copy_from_slice(&header.sh_size.to_le_bytes());
+    out[40..44].copy_from_slice(&header.sh_link.to_le_bytes());
+    out[44..48].copy_from_slice(&header.sh_info.to_le_bytes());
+    out[48..56].copy_from_slice(&header.sh_addralign.to_le_bytes());
+    out[56..64].copy_from_slice(&header.sh_entsize.to_le_bytes());
+    out
+}
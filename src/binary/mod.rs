@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
+pub mod abi;
 pub mod arm64;
 pub mod elf;
+pub mod elf_writer;
 pub mod hex_pattern;
 pub mod search;
+pub mod x86_64;
@@ -0,0 +1,153 @@
+#![allow(dead_code)]
+
+// The REX prefix with only the W bit set, selecting a 64-bit operand size.
+const REX_W: u8 = 0x48;
+// The REX prefix with the W and R bits set, selecting a 64-bit operand and an extended ModRM.reg register.
+const REX_WR: u8 = 0x4C;
+// The base opcode of `MOV r64, imm64` (the register number is added to it).
+const MOV_IMM64_OPCODE: u8 = 0xB8;
+// The opcode of `CALL rel32`.
+const CALL_REL32_OPCODE: u8 = 0xE8;
+// The opcode of `LEA r64, m`.
+const LEA_OPCODE: u8 = 0x8D;
+
+/// Represents an x86_64 general-purpose register, numbered as in the instruction encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    Rax,
+    Rcx,
+    Rdx,
+    Rbx,
+    Rsp,
+    Rbp,
+    Rsi,
+    Rdi,
+    R8,
+    R9,
+    R10,
+    R11,
+    R12,
+    R13,
+    R14,
+    R15,
+}
+
+impl TryFrom<u8> for Register {
+    type Error = ();
+
+    /// Attempts to convert a 4-bit register number (including the REX extension bit) to a [`Register`].
+    ///
+    /// # Returns
+    /// - `Ok(Register)` if the input is within the valid range (0–15).
+    /// - `Err(())` otherwise.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use Register::*;
+        const REGISTERS: [Register; 16] = [
+            Rax, Rcx, Rdx, Rbx, Rsp, Rbp, Rsi, Rdi, R8, R9, R10, R11, R12, R13, R14, R15,
+        ];
+        REGISTERS.get(value as usize).copied().ok_or(())
+    }
+}
+
+/// Represents a `MOV r64, imm64` instruction (also known as `MOVABS`).
+///
+/// Encoding: `REX.W + B8+rd io`, 10 bytes in total.
+#[derive(Debug)]
+pub struct MovImm64 {
+    /// The destination register.
+    pub rd: Register,
+    /// The 64-bit immediate value.
+    pub imm64: u64,
+}
+
+impl MovImm64 {
+    /// The encoded length of the instruction in bytes.
+    pub const LEN: usize = 10;
+}
+
+/// Attempts to parse the bytes at the start of `code` as a `MOV r64, imm64` instruction.
+///
+/// Only the legacy registers (RAX–RDI) are recognized, which covers the argument registers used
+/// by the System V calling convention.
+///
+/// # Returns
+/// - `Some(MovImm64)` if the bytes match the encoding.
+/// - `None` otherwise.
+pub fn parse_mov_imm64(code: &[u8]) -> Option<MovImm64> {
+    let bytes = code.get(..MovImm64::LEN)?;
+    if bytes[0] != REX_W || !(MOV_IMM64_OPCODE..MOV_IMM64_OPCODE + 8).contains(&bytes[1]) {
+        return None;
+    }
+    Some(MovImm64 {
+        rd: Register::try_from(bytes[1] - MOV_IMM64_OPCODE).ok()?,
+        imm64: u64::from_le_bytes(bytes[2..10].try_into().ok()?),
+    })
+}
+
+/// Represents a `CALL rel32` instruction.
+///
+/// Encoding: `E8 cd`, 5 bytes in total. The target is relative to the end of the instruction.
+#[derive(Debug)]
+pub struct CallRel32 {
+    /// The signed displacement from the end of the instruction to the call target.
+    pub rel32: i32,
+}
+
+impl CallRel32 {
+    /// The encoded length of the instruction in bytes.
+    pub const LEN: usize = 5;
+}
+
+/// Attempts to parse the bytes at the start of `code` as a `CALL rel32` instruction.
+///
+/// # Returns
+/// - `Some(CallRel32)` if the bytes match the encoding.
+/// - `None` otherwise.
+pub fn parse_call_rel32(code: &[u8]) -> Option<CallRel32> {
+    let bytes = code.get(..CallRel32::LEN)?;
+    if bytes[0] != CALL_REL32_OPCODE {
+        return None;
+    }
+    Some(CallRel32 {
+        rel32: i32::from_le_bytes(bytes[1..5].try_into().ok()?),
+    })
+}
+
+/// Represents a RIP-relative `LEA r64, [rip + disp32]` instruction.
+///
+/// Encoding: `REX.W(R) 8D /r` with ModRM `mod = 00, rm = 101`, 7 bytes in total. The effective
+/// address is relative to the end of the instruction.
+#[derive(Debug)]
+pub struct LeaRip {
+    /// The destination register.
+    pub rd: Register,
+    /// The signed displacement from the end of the instruction to the effective address.
+    pub disp32: i32,
+}
+
+impl LeaRip {
+    /// The encoded length of the instruction in bytes.
+    pub const LEN: usize = 7;
+}
+
+/// Attempts to parse the bytes at the start of `code` as a RIP-relative `LEA r64` instruction.
+///
+/// # Returns
+/// - `Some(LeaRip)` if the bytes match the encoding.
+/// - `None` otherwise.
+pub fn parse_lea_rip(code: &[u8]) -> Option<LeaRip> {
+    let bytes = code.get(..LeaRip::LEN)?;
+    if (bytes[0] != REX_W && bytes[0] != REX_WR) || bytes[1] != LEA_OPCODE {
+        return None;
+    }
+    // mod = 00 and rm = 101 select RIP-relative addressing with a 32-bit displacement.
+    let modrm = bytes[2];
+    if modrm & 0b1100_0111 != 0b0000_0101 {
+        return None;
+    }
+    let reg_ext = if bytes[0] == REX_WR { 8 } else { 0 };
+    Some(LeaRip {
+        rd: Register::try_from(((modrm >> 3) & 0b111) + reg_ext).ok()?,
+        disp32: i32::from_le_bytes(bytes[3..7].try_into().ok()?),
+    })
+}
@@ -15,12 +15,12 @@ use log::{info, LevelFilter};
 /// # Arguments
 ///
 /// * `args` - An `AppArgs` struct containing command line parameters, including
-///            a verbosity flag and a command to run.
+///   a verbosity flag and a command to run.
 ///
 /// # Returns
 ///
 /// * `Result<()>` - Returns `Ok(())` if the command executes successfully, or an
-///                  error if any step fails.
+///   error if any step fails.
 pub fn run_cli_headless(args: AppArgs) -> Result<()> {
     // Determine the logging level:
     // Use Debug level if verbose mode is enabled, otherwise default to Info.
@@ -0,0 +1,272 @@
+use crate::unity::blob_value::{BlobValue, BlobValueData};
+use crate::unity::field_layout::FIELD_ATTRIBUTE_LITERAL;
+use crate::unity::generated::CIl2Cpp::{TypeDefinitionIndex, TypeIndex};
+use crate::unity::il2cpp::Il2Cpp;
+use crate::utils::read_only::ReadOnly;
+use anyhow::{anyhow, Result};
+use log::warn;
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+/// The constants of the metadata, as exported to JSON.
+///
+/// Every list is sorted by name, so that exports of different releases can be compared with a
+/// plain text diff even if types and methods moved within the metadata.
+#[derive(Debug, Serialize)]
+pub struct ConstantsExport {
+    /// The `const` fields of all types except enums.
+    pub constants: Vec<ConstantField>,
+    /// All enum types with their members.
+    pub enums: Vec<EnumType>,
+    /// The default values of optional parameters.
+    pub parameter_defaults: Vec<ParameterDefault>,
+}
+
+/// A `const` field with its value.
+#[derive(Debug, Serialize)]
+pub struct ConstantField {
+    /// Full name of the declaring type.
+    pub declaring_type: String,
+    /// Name of the field.
+    pub name: String,
+    /// C# name of the field type.
+    pub field_type: String,
+    /// The decoded value, or `null` if the value is a null reference or cannot be decoded.
+    pub value: Value,
+    /// The reason the value cannot be decoded, if it cannot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// An enum type with its members, in declaration order.
+#[derive(Debug, Serialize)]
+pub struct EnumType {
+    /// Full name of the enum type.
+    pub name: String,
+    /// C# name of the underlying integer type, such as `int`.
+    pub underlying_type: String,
+    /// The members of the enum.
+    pub members: Vec<EnumMember>,
+}
+
+/// A member of an enum type.
+#[derive(Debug, Serialize)]
+pub struct EnumMember {
+    /// Name of the member.
+    pub name: String,
+    /// The value of the member, or `null` if it cannot be decoded.
+    pub value: Value,
+    /// The reason the value cannot be decoded, if it cannot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The default value of an optional parameter.
+#[derive(Debug, Serialize)]
+pub struct ParameterDefault {
+    /// Full name of the method, in the form `Namespace.Type$$Method`.
+    pub method: String,
+    /// Parameter list of the method, such as `(int count, string name)`, to tell overloads apart.
+    pub signature: String,
+    /// Name of the parameter.
+    pub parameter: String,
+    /// Position of the parameter in the parameter list, starting at 0.
+    pub position: i32,
+    /// C# name of the parameter type.
+    pub parameter_type: String,
+    /// The decoded value, or `null` if the value is a null reference or cannot be decoded.
+    pub value: Value,
+    /// The reason the value cannot be decoded, if it cannot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Collects the `const` fields, enums and parameter default values of all types.
+///
+/// Values that cannot be decoded are exported as `null` with the reason in an `error` key and
+/// logged, so one corrupt value does not prevent the export of everything else.
+///
+/// # Errors
+///
+/// Returns an error if the name of a type, field, method or parameter cannot be resolved.
+pub fn collect_constants<'a>(il2cpp: &'a Il2Cpp<'a>) -> Result<ConstantsExport> {
+    let metadata = &il2cpp.metadata;
+    let mut constants = Vec::new();
+    let mut enums = Vec::new();
+
+    for (index, ty_def) in metadata.type_definitions.iter().enumerate() {
+        let type_name = il2cpp.type_definition_name(index as TypeDefinitionIndex)?;
+        let mut members = Vec::new();
+        for field_index in ty_def.get_field_range() {
+            let field = &metadata.fields[field_index];
+            let ty = &il2cpp.types[field.typeIndex as usize];
+            if ty.attrs() & FIELD_ATTRIBUTE_LITERAL == 0 {
+                continue;
+            }
+            let name = metadata.get_string_by_index(field.nameIndex);
+            let (value, error) = match metadata.field_default_values_map.get(&(field_index as i32))
+            {
+                Some(default_value) => decode_default_value(
+                    il2cpp,
+                    default_value.typeIndex,
+                    default_value.dataIndex,
+                    &format!("{}.{}", type_name, name),
+                ),
+                None => (Value::Null, Some("No default value".to_string())),
+            };
+            if ty_def.is_enum_type() {
+                members.push(EnumMember { name, value, error });
+            } else {
+                constants.push(ConstantField {
+                    declaring_type: type_name.clone(),
+                    name,
+                    field_type: ty.get_complex_type(il2cpp)?.to_string(),
+                    value,
+                    error,
+                });
+            }
+        }
+        if ty_def.is_enum_type() {
+            enums.push(EnumType {
+                name: type_name,
+                underlying_type: il2cpp.types[ty_def.elementTypeIndex as usize]
+                    .get_complex_type(il2cpp)?
+                    .to_string(),
+                members,
+            });
+        }
+    }
+
+    // The method of each parameter, as parameter default values only refer to the parameter.
+    let mut parameter_methods = vec![None; metadata.parameters.len()];
+    for (method_index, method) in metadata.methods.iter().enumerate() {
+        for i in 0..method.parameter_count as i32 {
+            if let Some(slot) = parameter_methods.get_mut((method.parameter_start + i) as usize) {
+                *slot = Some(method_index);
+            }
+        }
+    }
+
+    let mut parameter_defaults = Vec::with_capacity(metadata.parameter_default_values.len());
+    for default_value in &metadata.parameter_default_values {
+        let method_index = parameter_methods
+            .get(default_value.parameterIndex as usize)
+            .copied()
+            .flatten()
+            .ok_or_else(|| anyhow!("Parameter {} has no method", default_value.parameterIndex))?;
+        parameter_defaults.push(parameter_default(
+            il2cpp,
+            method_index,
+            default_value.parameterIndex,
+            default_value.typeIndex,
+            default_value.dataIndex,
+        )?);
+    }
+
+    constants.sort_by(|a, b| (&a.declaring_type, &a.name).cmp(&(&b.declaring_type, &b.name)));
+    enums.sort_by(|a, b| a.name.cmp(&b.name));
+    parameter_defaults.sort_by(|a, b| {
+        (&a.method, &a.signature, a.position).cmp(&(&b.method, &b.signature, b.position))
+    });
+    Ok(ConstantsExport {
+        constants,
+        enums,
+        parameter_defaults,
+    })
+}
+
+/// Resolves the method and parameter of a parameter default value and decodes the value.
+fn parameter_default<'a>(
+    il2cpp: &'a Il2Cpp<'a>,
+    method_index: usize,
+    parameter_index: i32,
+    type_index: TypeIndex,
+    data_index: i32,
+) -> Result<ParameterDefault> {
+    let metadata = &il2cpp.metadata;
+    let method = metadata.methods.get(method_index);
+
+    let mut parameters = Vec::with_capacity(method.parameter_count as usize);
+    for i in 0..method.parameter_count as i32 {
+        let parameter = &metadata.parameters[(method.parameter_start + i) as usize];
+        parameters.push(format!(
+            "{} {}",
+            il2cpp.types[parameter.typeIndex as usize].get_complex_type(il2cpp)?,
+            metadata.get_string_by_index(parameter.nameIndex)
+        ));
+    }
+
+    let parameter = &metadata.parameters[parameter_index as usize];
+    let method_name = il2cpp.method_full_name(method_index)?;
+    let parameter_name = metadata.get_string_by_index(parameter.nameIndex);
+    let (value, error) = decode_default_value(
+        il2cpp,
+        type_index,
+        data_index,
+        &format!("{}({})", method_name, parameter_name),
+    );
+    Ok(ParameterDefault {
+        method: method_name,
+        signature: format!("({})", parameters.join(", ")),
+        parameter: parameter_name,
+        position: parameter_index - method.parameter_start,
+        parameter_type: il2cpp.types[parameter.typeIndex as usize]
+            .get_complex_type(il2cpp)?
+            .to_string(),
+        value,
+        error,
+    })
+}
+
+/// Decodes a default value to JSON, returning `null` and the reason if it cannot be decoded.
+fn decode_default_value<'a>(
+    il2cpp: &'a Il2Cpp<'a>,
+    type_index: TypeIndex,
+    data_index: i32,
+    owner: &str,
+) -> (Value, Option<String>) {
+    match il2cpp.read_default_value(type_index, data_index) {
+        Ok(Some(value)) => (json_value(il2cpp, &value), None),
+        Ok(None) => (Value::Null, None),
+        Err(err) => {
+            warn!("Could not decode the default value of {}: {}", owner, err);
+            (Value::Null, Some(err.to_string()))
+        }
+    }
+}
+
+/// Converts a blob value to JSON.
+///
+/// Numbers and booleans map to their JSON counterparts, while characters, non-finite floats and
+/// `typeof` values are exported as strings, since JSON has no type for them.
+fn json_value<'a>(il2cpp: &'a Il2Cpp<'a>, value: &BlobValue) -> Value {
+    match &value.value {
+        BlobValueData::Boolean(b) => Value::Bool(*b),
+        BlobValueData::U1(v) => Value::from(*v),
+        BlobValueData::I1(v) => Value::from(*v),
+        BlobValueData::Char(c) => Value::String(c.to_string()),
+        BlobValueData::U2(v) => Value::from(*v),
+        BlobValueData::I2(v) => Value::from(*v),
+        BlobValueData::U4(v) => Value::from(*v),
+        BlobValueData::I4(v) => Value::from(*v),
+        BlobValueData::U8(v) => Value::from(*v),
+        BlobValueData::I8(v) => Value::from(*v),
+        // Go through the shortest representation of the f32, so that `0.1f` exports as `0.1`
+        // rather than as the nearest f64.
+        BlobValueData::R4(v) => float_value(v.to_string().parse().unwrap_or(f64::NAN)),
+        BlobValueData::R8(v) => float_value(*v),
+        BlobValueData::String(s) => Value::String(s.clone()),
+        BlobValueData::Array(values) => values.iter().map(|v| json_value(il2cpp, v)).collect(),
+        BlobValueData::TypeIndex(None) => Value::Null,
+        BlobValueData::TypeIndex(Some(ty)) => match ReadOnly::new(ty).get_complex_type(il2cpp) {
+            Ok(complex) => Value::String(format!("typeof({})", complex)),
+            Err(err) => Value::String(format!("typeof(/* {} */)", err)),
+        },
+    }
+}
+
+/// Converts a float to a JSON number, or to a string such as `NaN` or `Infinity` if it is not
+/// finite.
+fn float_value(v: f64) -> Value {
+    Number::from_f64(v).map_or_else(|| Value::String(v.to_string()), Value::Number)
+}
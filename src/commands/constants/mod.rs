@@ -0,0 +1,181 @@
+use crate::commands::constants::export::collect_constants;
+use crate::commands::input::InputArgs;
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{get_required_path, make_path_input, BrowseType};
+use crate::commands::AppCommand;
+use crate::unity::unity_loader::load_il2cpp;
+use crate::utils::consts::CONSTANTS_FILTER;
+use anyhow::Result;
+use clap::Args;
+use cursive::traits::Resizable;
+use cursive::utils::markup::markdown;
+use cursive::views::{Dialog, DummyView, LinearLayout};
+use cursive::Cursive;
+use log::info;
+use parking_lot::Mutex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod export;
+
+/// Command line arguments for the constants export.
+///
+/// This struct holds the input files and the output path:
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
+/// - `output`: Required path to write the JSON export to.
+#[derive(Args, Default, Clone)]
+pub struct ConstantsArgs {
+    /// The IL2CPP binary and global metadata to work on.
+    #[command(flatten)]
+    pub source: InputArgs,
+    /// Path to write the JSON file with the constants, enums and parameter default values to.
+    #[clap(long)]
+    pub output: PathBuf,
+}
+
+impl IntoTui for ConstantsArgs {
+    /// Converts the constants arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with two input methods: one for an APK/XAPK file or an
+    /// unpacked APK folder, and another for IL2CPP and Global Metadata files, followed by the
+    /// output file. It sets up a callback that validates and processes the user input before
+    /// invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the constants arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Export Constants Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(InputArgs::make_tui_inputs())
+                    .child(DummyView.fixed_height(1))
+                    // Output file for the export
+                    .child(make_path_input(
+                        "Output File (*): ",
+                        "output",
+                        BrowseType::SaveFile,
+                        Some(CONSTANTS_FILTER),
+                    )),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our ConstantsArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.output = get_required_path(s, "output");
+
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::Constants(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the constants arguments.
+    ///
+    /// Checks that:
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - An output file is provided, is not a directory and differs from the input files.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        self.source.validate()?;
+
+        if self.output.as_os_str().is_empty() {
+            return Err("Output file must not be empty".into());
+        }
+        if self.output.is_dir() {
+            return Err("Output file is a directory".into());
+        }
+        if self.source.paths().any(|input| *input == self.output) {
+            return Err("Output file must differ from the input files".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the constants export.
+///
+/// This function performs the following steps:
+/// 1. Loads input data (il2cpp and global metadata) from either an archive or individual files.
+/// 2. Decrypts the global metadata, unless it is already decrypted, and loads the il2cpp binary.
+/// 3. Decodes the value of every `const` field, the members of every enum and the default value
+///    of every optional parameter, and writes them to the output file as JSON, sorted by name.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - Missing or unreadable input files.
+/// - Failure to decrypt the global metadata or load the il2cpp binary.
+/// - A type, method or parameter that cannot be resolved.
+/// - File I/O errors while writing the output file.
+pub fn execute(args: ConstantsArgs) -> Result<()> {
+    info!("Running constants command...");
+    info!(progress = 0, max = 3; "");
+
+    info!("Loading input data...");
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    info!(progress_tick = 1; "");
+
+    info!("Loading global metadata and il2cpp...");
+    let il2cpp = load_il2cpp(
+        il2cpp_data,
+        global_metadata_data,
+        args.source.metadata_key(),
+    )?;
+    info!(progress_tick = 1; "");
+
+    info!("Decoding constants...");
+    let export = collect_constants(&il2cpp)?;
+    fs::write(&args.output, serde_json::to_string_pretty(&export)? + "\n")?;
+    info!(progress_tick = 1; "");
+
+    info!(
+        "Done! Exported {} constants, {} enums and {} parameter default values to {}",
+        export.constants.len(),
+        export.enums.len(),
+        export.parameter_defaults.len(),
+        args.output.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use crate::binary::elf::Elf;
+use crate::commands::input::InputArgs;
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{get_required_path, make_path_input, BrowseType};
+use crate::commands::AppCommand;
+use crate::unity::generated::UnityVersion;
+use crate::unity::unity_loader::{
+    decrypt_global_metadata, format_encryption_key, format_encryption_key_xor,
+};
+use crate::utils::consts::METADATA_FILTER;
+use anyhow::Result;
+use clap::Args;
+use cursive::traits::Resizable;
+use cursive::utils::markup::markdown;
+use cursive::views::{Dialog, DummyView, LinearLayout};
+use cursive::Cursive;
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Command line arguments for the metadata decryption process.
+///
+/// This struct holds the input files and the output path:
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
+/// - `output`: Required path to write the decrypted global metadata file to.
+#[derive(Args, Default, Clone)]
+pub struct DecryptMetadataArgs {
+    /// The IL2CPP binary and global metadata to work on.
+    #[command(flatten)]
+    pub source: InputArgs,
+    /// Path to write the decrypted global-metadata.dat file to.
+    #[clap(long)]
+    pub output: PathBuf,
+}
+
+impl IntoTui for DecryptMetadataArgs {
+    /// Converts the decryption arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with two input methods: one for an APK/XAPK file or an
+    /// unpacked APK folder, and another for IL2CPP and Global Metadata files, followed by the
+    /// output file. It sets up a callback that validates and processes the user input before
+    /// invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the decryption arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Decrypt Metadata Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(InputArgs::make_tui_inputs())
+                    .child(DummyView.fixed_height(1))
+                    // Output file for the decrypted metadata
+                    .child(make_path_input(
+                        "Decrypted Metadata File (*): ",
+                        "output",
+                        BrowseType::SaveFile,
+                        Some(METADATA_FILTER),
+                    )),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our DecryptMetadataArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.output = get_required_path(s, "output");
+
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::DecryptMetadata(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the decryption arguments.
+    ///
+    /// Checks that:
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - An output file is provided, is not a directory and differs from the input files.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        self.source.validate()?;
+
+        if self.output.as_os_str().is_empty() {
+            return Err("Decrypted metadata file must not be empty".into());
+        }
+        if self.output.is_dir() {
+            return Err("Decrypted metadata file is a directory".into());
+        }
+        if self.source.paths().any(|input| *input == self.output) {
+            return Err("Decrypted metadata file must differ from the input files".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the metadata decryption process.
+///
+/// This function performs the following steps:
+/// 1. Loads input data (il2cpp and global metadata) from either an archive or individual files.
+/// 2. Recovers the metadata key and key xor from the il2cpp binary and reports them.
+/// 3. Decrypts the global metadata and checks its sanity value.
+/// 4. Writes the decrypted global metadata to the output path, so it can be read by other tools.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - Missing or unreadable input files.
+/// - Failure to recover the keys, or a decrypted sanity value that does not match.
+/// - File I/O errors while writing the output file.
+pub fn execute(args: DecryptMetadataArgs) -> Result<()> {
+    info!("Running metadata decryption command...");
+    info!(progress = 0, max = 3; "");
+
+    info!("Loading input data...");
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    info!(progress_tick = 1; "");
+
+    info!("Recovering metadata key and decrypting global metadata...");
+    let elf = Elf::new(il2cpp_data)?;
+    let decrypted =
+        decrypt_global_metadata(&elf, &global_metadata_data, args.source.metadata_key())?;
+    info!("Metadata key: {}", format_encryption_key(&decrypted.key));
+    info!(
+        "Metadata key xor: {}",
+        format_encryption_key_xor(decrypted.key_xor)
+    );
+
+    // The sanity value was checked while decrypting, report the version that follows it.
+    let version = i32::from_le_bytes(decrypted.data[4..8].try_into()?);
+    match UnityVersion::from_metadata_version(version) {
+        Some(unity_version) => info!("Global metadata v{} (Unity {})", version, unity_version),
+        None => warn!(
+            "Global metadata v{} is not supported by the other commands of this tool",
+            version
+        ),
+    }
+    info!(progress_tick = 1; "");
+
+    info!("Writing decrypted global metadata...");
+    fs::write(&args.output, &decrypted.data)?;
+    info!(progress_tick = 1; "");
+
+    info!(
+        "Done! Decrypted global metadata written to {}",
+        args.output.display()
+    );
+    Ok(())
+}
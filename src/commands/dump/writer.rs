@@ -0,0 +1,680 @@
+use crate::unity::blob_value::{BlobValue, BlobValueData};
+use crate::unity::custom_attributes::{AttributeTarget, CustomAttribute};
+use crate::unity::field_layout::{FieldOffset, FIELD_ATTRIBUTE_LITERAL, FIELD_ATTRIBUTE_STATIC};
+use crate::unity::generated::CIl2Cpp::{
+    Il2CppParameterDefaultValue, Il2CppParameterDefinition, Il2CppType, Il2CppTypeDefinition,
+    ParameterIndex, TypeDefinitionIndex, TypeIndex,
+};
+use crate::unity::il2cpp::Il2Cpp;
+use crate::unity::method_definitions::{
+    MethodDefinition, METHOD_ATTRIBUTE_ABSTRACT, METHOD_ATTRIBUTE_STATIC, METHOD_ATTRIBUTE_VIRTUAL,
+};
+use crate::utils::read_only::ReadOnly;
+use anyhow::{anyhow, Result};
+use log::debug;
+use nohash_hasher::IntMap;
+use std::io::Write;
+
+/// Mask of the visibility bits in the flags of a type definition.
+const TYPE_ATTRIBUTE_VISIBILITY_MASK: u32 = 0x7;
+/// The type is abstract.
+const TYPE_ATTRIBUTE_ABSTRACT: u32 = 0x80;
+/// The type is sealed.
+const TYPE_ATTRIBUTE_SEALED: u32 = 0x100;
+/// The type is serializable.
+const TYPE_ATTRIBUTE_SERIALIZABLE: u32 = 0x2000;
+
+/// Mask of the access bits in the attributes of a field type.
+const FIELD_ATTRIBUTE_FIELD_ACCESS_MASK: u32 = 0x7;
+/// The field can only be set in a constructor.
+const FIELD_ATTRIBUTE_INIT_ONLY: u32 = 0x20;
+
+/// Mask of the access bits in the flags of a method definition.
+const METHOD_ATTRIBUTE_MEMBER_ACCESS_MASK: u16 = 0x7;
+/// The method cannot be overridden.
+const METHOD_ATTRIBUTE_FINAL: u16 = 0x20;
+/// The method gets a new slot in the vtable, rather than overriding a base method.
+const METHOD_ATTRIBUTE_NEW_SLOT: u16 = 0x100;
+/// The method is implemented through platform invoke.
+const METHOD_ATTRIBUTE_PINVOKE_IMPL: u16 = 0x2000;
+
+/// The parameter is passed in.
+const PARAM_ATTRIBUTE_IN: u32 = 0x1;
+/// The parameter is passed out.
+const PARAM_ATTRIBUTE_OUT: u32 = 0x2;
+
+/// Slot value of methods that have no vtable slot.
+const NO_SLOT: u16 = 0xFFFF;
+
+/// Writes a C# declaration listing of all types in the style of Il2CppDumper's `dump.cs`.
+///
+/// The listing starts with the images, followed by every type definition with its attributes,
+/// instance and static sizes, fields with their default values and offsets, properties and
+/// methods. Each method is preceded by its
+/// address: the RVA and VA (equal for shared objects, which are linked at base 0) and the file
+/// offset in the IL2CPP binary, or `-1` for methods without code.
+///
+/// # Arguments
+/// * `il2cpp` - Reference to the IL2CPP context.
+/// * `out` - The writer to write the listing to.
+///
+/// # Returns
+/// * `Result<usize>` - The number of types written, or an error if a type cannot be resolved or
+///   writing fails.
+pub fn write_dump<'a, W: Write>(il2cpp: &'a Il2Cpp<'a>, out: &mut W) -> Result<usize> {
+    let metadata = &il2cpp.metadata;
+
+    debug!("Resolving method pointers...");
+    let method_pointers = il2cpp.method_pointers()?;
+    let parameter_default_values: IntMap<ParameterIndex, Il2CppParameterDefaultValue> = metadata
+        .parameter_default_values
+        .iter()
+        .map(|value| (value.parameterIndex, *value))
+        .collect();
+    let dumper = Dumper {
+        il2cpp,
+        method_pointers,
+        parameter_default_values,
+    };
+
+    for (index, image) in metadata.images.iter().enumerate() {
+        let image_name = metadata.get_string_by_index(image.nameIndex);
+        writeln!(
+            out,
+            "// Image {}: {} - {}",
+            index, image_name, image.typeStart
+        )?;
+    }
+
+    let mut type_count = 0;
+    debug!(progress = 0, max = metadata.images.len(); "");
+    for image in &metadata.images {
+        let image_name = metadata.get_string_by_index(image.nameIndex);
+        debug!("Dumping IL2CPP image: {image_name}");
+        let type_start = image.typeStart as usize;
+        for type_index in type_start..type_start + image.typeCount as usize {
+            writeln!(out)?;
+            dumper.write_type(out, type_index as TypeDefinitionIndex)?;
+            type_count += 1;
+        }
+        debug!(progress_tick = 1; "");
+    }
+    Ok(type_count)
+}
+
+/// State shared while writing the declarations of all types.
+struct Dumper<'a> {
+    /// The IL2CPP context.
+    il2cpp: &'a Il2Cpp<'a>,
+    /// The code address of each method definition, see [`Il2Cpp::method_pointers`].
+    method_pointers: Vec<Option<u64>>,
+    /// The default values of parameters, by parameter index.
+    parameter_default_values: IntMap<ParameterIndex, Il2CppParameterDefaultValue>,
+}
+
+impl<'a> Dumper<'a> {
+    /// Writes the declaration of a type definition with all of its members.
+    fn write_type<W: Write>(&self, out: &mut W, type_index: TypeDefinitionIndex) -> Result<()> {
+        let ty_def = self.type_definition(type_index)?;
+
+        writeln!(out, "// Namespace: {}", self.namespace_of(ty_def)?)?;
+        self.write_attributes(out, AttributeTarget::Type(type_index), "")?;
+        if ty_def.flags & TYPE_ATTRIBUTE_SERIALIZABLE != 0 {
+            writeln!(out, "[Serializable]")?;
+        }
+
+        let is_interface = ty_def.is_interface();
+        let mut declaration = type_visibility(ty_def.flags).to_string();
+        if ty_def.flags & TYPE_ATTRIBUTE_ABSTRACT != 0 && ty_def.flags & TYPE_ATTRIBUTE_SEALED != 0
+        {
+            declaration.push_str("static ");
+        } else if !is_interface && ty_def.flags & TYPE_ATTRIBUTE_ABSTRACT != 0 {
+            declaration.push_str("abstract ");
+        } else if !ty_def.is_value_type()
+            && !ty_def.is_enum_type()
+            && ty_def.flags & TYPE_ATTRIBUTE_SEALED != 0
+        {
+            declaration.push_str("sealed ");
+        }
+        declaration.push_str(if is_interface {
+            "interface "
+        } else if ty_def.is_enum_type() {
+            "enum "
+        } else if ty_def.is_value_type() {
+            "struct "
+        } else {
+            "class "
+        });
+        declaration.push_str(&self.type_name(ty_def.byvalTypeIndex)?);
+
+        // Value types and enums always derive from System.ValueType or System.Enum.
+        let mut bases = Vec::new();
+        if !ty_def.is_value_type() && !ty_def.is_enum_type() && ty_def.parentIndex >= 0 {
+            let parent = self.type_name(ty_def.parentIndex)?;
+            if parent != "object" {
+                bases.push(parent);
+            }
+        }
+        for &interface in self.il2cpp.interfaces(type_index)? {
+            bases.push(self.type_name(interface)?);
+        }
+        if !bases.is_empty() {
+            declaration.push_str(" : ");
+            declaration.push_str(&bases.join(", "));
+        }
+        let mut comment = format!("TypeDefIndex: {}", type_index);
+        if let Some(sizes) = self.il2cpp.type_sizes(type_index)? {
+            comment.push_str(&format!(", Size: 0x{:X}", sizes.instance_size));
+            if sizes.static_fields_size > 0 {
+                comment.push_str(&format!(", Static Size: 0x{:X}", sizes.static_fields_size));
+            }
+            if sizes.thread_static_fields_size > 0 {
+                comment.push_str(&format!(
+                    ", Thread Static Size: 0x{:X}",
+                    sizes.thread_static_fields_size
+                ));
+            }
+        }
+        writeln!(out, "{} // {}", declaration, comment)?;
+        writeln!(out, "{{")?;
+
+        if ty_def.field_count > 0 {
+            writeln!(out, "\t// Fields")?;
+            for field_index in ty_def.get_field_range() {
+                self.write_field(out, type_index, field_index)?;
+            }
+        }
+
+        if ty_def.property_count > 0 {
+            writeln!(out)?;
+            writeln!(out, "\t// Properties")?;
+            let property_start = ty_def.propertyStart as usize;
+            for property_index in property_start..property_start + ty_def.property_count as usize {
+                self.write_property(out, ty_def, property_index)?;
+            }
+        }
+
+        if ty_def.method_count > 0 {
+            writeln!(out)?;
+            writeln!(out, "\t// Methods")?;
+            let method_start = ty_def.methodStart as usize;
+            for method_index in method_start..method_start + ty_def.method_count as usize {
+                writeln!(out)?;
+                self.write_method(out, method_index)?;
+            }
+        }
+
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    /// Writes a field declaration, with its value if it is a constant and its offset if it has
+    /// storage.
+    fn write_field<W: Write>(
+        &self,
+        out: &mut W,
+        type_index: TypeDefinitionIndex,
+        field_index: usize,
+    ) -> Result<()> {
+        let metadata = &self.il2cpp.metadata;
+        let field = metadata
+            .fields
+            .get(field_index)
+            .ok_or_else(|| anyhow!("Field definition {} does not exist", field_index))?;
+        let target = AttributeTarget::Field {
+            declaring_type: type_index,
+            field: field_index as i32,
+        };
+        self.write_attributes(out, target, "\t")?;
+
+        let attrs = self.il2cpp_type(field.typeIndex)?.attrs();
+        let mut declaration = String::from("\t");
+        declaration.push_str(field_visibility(attrs));
+        if attrs & FIELD_ATTRIBUTE_LITERAL != 0 {
+            declaration.push_str("const ");
+        } else {
+            if attrs & FIELD_ATTRIBUTE_STATIC != 0 {
+                declaration.push_str("static ");
+            }
+            if attrs & FIELD_ATTRIBUTE_INIT_ONLY != 0 {
+                declaration.push_str("readonly ");
+            }
+        }
+        declaration.push_str(&self.type_name(field.typeIndex)?);
+        declaration.push(' ');
+        declaration.push_str(&metadata.get_string_by_index(field.nameIndex));
+
+        if let Some(default_value) = metadata.field_default_values_map.get(&(field_index as i32)) {
+            let value = self.default_value(default_value.typeIndex, default_value.dataIndex);
+            declaration.push_str(" = ");
+            declaration.push_str(&value);
+        }
+        declaration.push(';');
+        match self.il2cpp.field_offset(type_index, field_index as i32)? {
+            Some(FieldOffset::Instance(offset) | FieldOffset::Static(offset)) => {
+                declaration.push_str(&format!(" // 0x{:X}", offset));
+            }
+            Some(FieldOffset::ThreadStatic(offset)) => {
+                declaration.push_str(&format!(" // 0x{:X} (thread static)", offset));
+            }
+            None => {}
+        }
+        writeln!(out, "{}", declaration)?;
+        Ok(())
+    }
+
+    /// Writes a property declaration with its accessors.
+    fn write_property<W: Write>(
+        &self,
+        out: &mut W,
+        ty_def: &Il2CppTypeDefinition,
+        property_index: usize,
+    ) -> Result<()> {
+        let metadata = &self.il2cpp.metadata;
+        let property = metadata
+            .properties
+            .get(property_index)
+            .ok_or_else(|| anyhow!("Property definition {} does not exist", property_index))?;
+        // Accessors are stored relative to the first method of the declaring type.
+        let accessor = |index: i32| -> Result<Option<MethodDefinition>> {
+            if index < 0 {
+                return Ok(None);
+            }
+            self.method(ty_def.methodStart as i64 + index as i64)
+                .map(Some)
+        };
+        let getter = accessor(property.get)?;
+        let setter = accessor(property.set)?;
+
+        let (method, property_type) = match (getter, setter) {
+            (Some(getter), _) => (getter, self.type_name(getter.return_type)?),
+            // The value is the last parameter of the setter. Without one, the type is unknown,
+            // which is noted in a comment so the rest of the listing is still written.
+            (None, Some(setter)) if setter.parameter_count == 0 => {
+                (setter, "/* Setter has no value parameter */".to_string())
+            }
+            (None, Some(setter)) => {
+                let value_index = setter.parameter_start + setter.parameter_count as i32 - 1;
+                let value_type = self.parameter_definition(value_index)?.typeIndex;
+                (setter, self.type_name(value_type)?)
+            }
+            (None, None) => return Ok(()),
+        };
+
+        let mut accessors = String::new();
+        if getter.is_some() {
+            accessors.push_str("get; ");
+        }
+        if setter.is_some() {
+            accessors.push_str("set; ");
+        }
+        writeln!(
+            out,
+            "\t{}{} {} {{ {}}}",
+            method_modifiers(&method),
+            property_type,
+            metadata.get_string_by_index(property.nameIndex),
+            accessors
+        )?;
+        Ok(())
+    }
+
+    /// Writes a method declaration, preceded by its address and attributes.
+    fn write_method<W: Write>(&self, out: &mut W, method_index: usize) -> Result<()> {
+        let il2cpp = self.il2cpp;
+        let metadata = &il2cpp.metadata;
+        let method = self.method(method_index as i64)?;
+
+        let method_pointer = self
+            .method_pointers
+            .get(method_index)
+            .ok_or_else(|| anyhow!("Method definition {} has no code address", method_index))?;
+        let mut address = match *method_pointer {
+            Some(va) => {
+                let offset = il2cpp
+                    .elf
+                    .va_to_file_offset(va)
+                    .map_or_else(|| "-1".to_string(), |offset| format!("0x{:X}", offset));
+                format!("RVA: 0x{:X} Offset: {} VA: 0x{:X}", va, offset, va)
+            }
+            None => "RVA: -1 Offset: -1".to_string(),
+        };
+        if method.slot != NO_SLOT {
+            address.push_str(&format!(" Slot: {}", method.slot));
+        }
+        writeln!(out, "\t// {}", address)?;
+        self.write_attributes(out, AttributeTarget::Method(method_index as i32), "\t")?;
+
+        let return_type = self.il2cpp_type(method.return_type)?;
+        let return_prefix = if return_type.byref() != 0 { "ref " } else { "" };
+        let mut parameters = Vec::with_capacity(method.parameter_count as usize);
+        for i in 0..method.parameter_count as i32 {
+            let parameter_index = method.parameter_start + i;
+            parameters.push(self.parameter(parameter_index)?);
+        }
+        writeln!(
+            out,
+            "\t{}{}{} {}{}({}) {{ }}",
+            method_modifiers(&method),
+            return_prefix,
+            self.type_name(method.return_type)?,
+            metadata.get_string_by_index(method.name_index),
+            self.generic_parameters(method.generic_container_index)?,
+            parameters.join(", ")
+        )?;
+        Ok(())
+    }
+
+    /// Returns a parameter declaration, with its default value if it has one.
+    fn parameter(&self, parameter_index: ParameterIndex) -> Result<String> {
+        let il2cpp = self.il2cpp;
+        let metadata = &il2cpp.metadata;
+        let parameter = self.parameter_definition(parameter_index)?;
+        let ty = self.il2cpp_type(parameter.typeIndex)?;
+
+        let attrs = ty.attrs();
+        let modifier = if ty.byref() == 0 {
+            ""
+        } else if attrs & PARAM_ATTRIBUTE_OUT != 0 && attrs & PARAM_ATTRIBUTE_IN == 0 {
+            "out "
+        } else if attrs & PARAM_ATTRIBUTE_IN != 0 && attrs & PARAM_ATTRIBUTE_OUT == 0 {
+            "in "
+        } else {
+            "ref "
+        };
+        let mut declaration = format!(
+            "{}{} {}",
+            modifier,
+            self.type_name(parameter.typeIndex)?,
+            metadata.get_string_by_index(parameter.nameIndex)
+        );
+        if let Some(default_value) = self.parameter_default_values.get(&parameter_index) {
+            declaration.push_str(" = ");
+            declaration
+                .push_str(&self.default_value(default_value.typeIndex, default_value.dataIndex));
+        }
+        Ok(declaration)
+    }
+
+    /// Returns the generic parameter list of a method, such as `<T, U>`, or an empty string for
+    /// methods that are not generic.
+    ///
+    /// # Errors
+    /// Returns an error if the generic container or one of its parameters does not exist.
+    fn generic_parameters(&self, generic_container_index: i32) -> Result<String> {
+        let metadata = &self.il2cpp.metadata;
+        if generic_container_index < 0 {
+            return Ok(String::new());
+        }
+        let container = metadata
+            .generic_containers
+            .get(generic_container_index as usize)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Generic container {} does not exist",
+                    generic_container_index
+                )
+            })?;
+        let names = (0..container.type_argc)
+            .map(|i| {
+                let index = container.genericParameterStart as i64 + i as i64;
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|index| metadata.generic_parameters.get(index))
+                    .map(|parameter| metadata.get_string_by_index(parameter.nameIndex))
+                    .ok_or_else(|| anyhow!("Generic parameter {} does not exist", index))
+            })
+            .collect::<Result<Vec<String>>>()?;
+        Ok(format!("<{}>", names.join(", ")))
+    }
+
+    /// Writes the custom attributes of a type or member, one per line.
+    ///
+    /// Attributes that cannot be decoded are noted in a comment instead, so the rest of the
+    /// listing is still written.
+    fn write_attributes<W: Write>(
+        &self,
+        out: &mut W,
+        target: AttributeTarget,
+        indent: &str,
+    ) -> Result<()> {
+        match self.il2cpp.attributes_of(target) {
+            Ok(attributes) => {
+                for attribute in &attributes {
+                    writeln!(out, "{}[{}]", indent, self.format_attribute(attribute))?;
+                }
+            }
+            Err(err) => writeln!(out, "{}// Failed to decode attributes: {}", indent, err)?,
+        }
+        Ok(())
+    }
+
+    /// Formats an attribute in C# syntax, such as `OriginalName("FOO", PreferredAlias = false)`.
+    fn format_attribute(&self, attribute: &CustomAttribute) -> String {
+        let name = attribute
+            .name
+            .strip_suffix("Attribute")
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&attribute.name);
+        let arguments: Vec<String> = attribute
+            .arguments
+            .iter()
+            .map(|value| self.format_value(value))
+            .chain(
+                attribute
+                    .fields
+                    .iter()
+                    .chain(&attribute.properties)
+                    .map(|named| format!("{} = {}", named.name, self.format_value(&named.value))),
+            )
+            .collect();
+        if arguments.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}({})", name, arguments.join(", "))
+        }
+    }
+
+    /// Decodes a field or parameter default value and formats it in C# syntax.
+    ///
+    /// Values that cannot be decoded are formatted as a comment with the reason.
+    fn default_value(&self, type_index: TypeIndex, data_index: i32) -> String {
+        match self.il2cpp.read_default_value(type_index, data_index) {
+            Ok(Some(value)) => self.format_value(&value),
+            Ok(None) => "null".to_string(),
+            Err(err) => format!("/* {} */", err),
+        }
+    }
+
+    /// Formats a blob value as a C# literal.
+    fn format_value(&self, value: &BlobValue) -> String {
+        match &value.value {
+            BlobValueData::Boolean(b) => b.to_string(),
+            BlobValueData::U1(v) => v.to_string(),
+            BlobValueData::I1(v) => v.to_string(),
+            BlobValueData::Char(c) => format!("'{}'", escape_csharp(&c.to_string(), '\'')),
+            BlobValueData::U2(v) => v.to_string(),
+            BlobValueData::I2(v) => v.to_string(),
+            BlobValueData::U4(v) => v.to_string(),
+            BlobValueData::I4(v) => v.to_string(),
+            BlobValueData::U8(v) => v.to_string(),
+            BlobValueData::I8(v) => v.to_string(),
+            BlobValueData::R4(v) => format!("{:?}f", v),
+            BlobValueData::R8(v) => format!("{:?}", v),
+            BlobValueData::String(s) => format!("\"{}\"", escape_csharp(s, '"')),
+            BlobValueData::Array(values) => {
+                let values: Vec<String> = values.iter().map(|v| self.format_value(v)).collect();
+                format!("new[] {{ {} }}", values.join(", "))
+            }
+            BlobValueData::TypeIndex(None) => "null".to_string(),
+            BlobValueData::TypeIndex(Some(ty)) => {
+                let ty = ReadOnly::new(ty);
+                match ty.get_complex_type(self.il2cpp) {
+                    Ok(complex) => format!("typeof({})", complex),
+                    Err(err) => format!("typeof(/* {} */)", err),
+                }
+            }
+        }
+    }
+
+    /// Returns the C# name of a type, such as `int`, `List<string>` or `Outer.Inner`.
+    fn type_name(&self, type_index: TypeIndex) -> Result<String> {
+        let ty = self.il2cpp_type(type_index)?;
+        Ok(ty.get_complex_type(self.il2cpp)?.to_string())
+    }
+
+    /// Returns the namespace of a type definition, which nested types take from their outermost
+    /// declaring type.
+    ///
+    /// # Errors
+    /// Returns an error if a declaring type does not exist.
+    fn namespace_of(&self, ty_def: &'a Il2CppTypeDefinition) -> Result<String> {
+        let mut ty_def = ty_def;
+        while ty_def.declaringTypeIndex >= 0 {
+            let declaring_type = self.il2cpp_type(ty_def.declaringTypeIndex)?;
+            let klass_index = unsafe { declaring_type.data.__klassIndex };
+            ty_def = self.type_definition(klass_index)?;
+        }
+        Ok(self
+            .il2cpp
+            .metadata
+            .get_string_by_index(ty_def.namespaceIndex))
+    }
+
+    /// Returns a type definition by index.
+    ///
+    /// # Errors
+    /// Returns an error if the index is out of bounds, as indices read from the metadata may be
+    /// corrupt.
+    fn type_definition(&self, index: TypeDefinitionIndex) -> Result<&'a Il2CppTypeDefinition> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.il2cpp.metadata.type_definitions.get(index))
+            .ok_or_else(|| anyhow!("Type definition {} does not exist", index))
+    }
+
+    /// Returns a type by index.
+    ///
+    /// # Errors
+    /// Returns an error if the index is out of bounds.
+    fn il2cpp_type(&self, index: TypeIndex) -> Result<&'a ReadOnly<&'a Il2CppType>> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.il2cpp.types.get(index))
+            .ok_or_else(|| anyhow!("Type {} does not exist", index))
+    }
+
+    /// Returns a method definition by index.
+    ///
+    /// # Errors
+    /// Returns an error if the index is out of bounds.
+    fn method(&self, index: i64) -> Result<MethodDefinition> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.il2cpp.metadata.methods.try_get(index))
+            .ok_or_else(|| anyhow!("Method definition {} does not exist", index))
+    }
+
+    /// Returns a parameter definition by index.
+    ///
+    /// # Errors
+    /// Returns an error if the index is out of bounds.
+    fn parameter_definition(&self, index: ParameterIndex) -> Result<&'a Il2CppParameterDefinition> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.il2cpp.metadata.parameters.get(index))
+            .ok_or_else(|| anyhow!("Parameter definition {} does not exist", index))
+    }
+}
+
+/// Returns the C# visibility keyword of a type definition, followed by a space.
+fn type_visibility(flags: u32) -> &'static str {
+    match flags & TYPE_ATTRIBUTE_VISIBILITY_MASK {
+        // Public and nested public.
+        1 | 2 => "public ",
+        // Nested private.
+        3 => "private ",
+        // Nested family.
+        4 => "protected ",
+        // Nested family and assembly.
+        6 => "private protected ",
+        // Nested family or assembly.
+        7 => "protected internal ",
+        // Not public and nested assembly.
+        _ => "internal ",
+    }
+}
+
+/// Returns the C# visibility keyword of a field, followed by a space.
+fn field_visibility(attrs: u32) -> &'static str {
+    member_visibility(attrs & FIELD_ATTRIBUTE_FIELD_ACCESS_MASK)
+}
+
+/// Returns the C# visibility keyword of a member access value, followed by a space.
+fn member_visibility(access: u32) -> &'static str {
+    match access {
+        // Family and assembly.
+        2 => "private protected ",
+        // Assembly.
+        3 => "internal ",
+        // Family.
+        4 => "protected ",
+        // Family or assembly.
+        5 => "protected internal ",
+        // Public.
+        6 => "public ",
+        // Private and compiler controlled.
+        _ => "private ",
+    }
+}
+
+/// Returns the C# modifiers of a method, such as `public static ` or `protected override `.
+fn method_modifiers(method: &MethodDefinition) -> String {
+    let flags = method.flags;
+    let mut modifiers =
+        member_visibility((flags & METHOD_ATTRIBUTE_MEMBER_ACCESS_MASK) as u32).to_string();
+    if flags & METHOD_ATTRIBUTE_STATIC != 0 {
+        modifiers.push_str("static ");
+    }
+    if flags & METHOD_ATTRIBUTE_ABSTRACT != 0 {
+        modifiers.push_str("abstract ");
+        if flags & METHOD_ATTRIBUTE_NEW_SLOT == 0 {
+            modifiers.push_str("override ");
+        }
+    } else if flags & METHOD_ATTRIBUTE_FINAL != 0 {
+        if flags & METHOD_ATTRIBUTE_NEW_SLOT == 0 {
+            modifiers.push_str("sealed override ");
+        }
+    } else if flags & METHOD_ATTRIBUTE_VIRTUAL != 0 {
+        if flags & METHOD_ATTRIBUTE_NEW_SLOT != 0 {
+            modifiers.push_str("virtual ");
+        } else {
+            modifiers.push_str("override ");
+        }
+    }
+    if flags & METHOD_ATTRIBUTE_PINVOKE_IMPL != 0 {
+        modifiers.push_str("extern ");
+    }
+    modifiers
+}
+
+/// Escapes a string for a C# string or character literal delimited by `quote`.
+fn escape_csharp(s: &str, quote: char) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            c if c == quote => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
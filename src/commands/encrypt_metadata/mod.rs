@@ -0,0 +1,282 @@
+use crate::binary::elf::Elf;
+use crate::commands::input::InputArgs;
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{
+    get_checkbox_value, get_optional_path, make_path_input, BrowseType,
+};
+use crate::commands::AppCommand;
+use crate::crypto::global_metadata;
+use crate::unity::generated::UnityVersion;
+use crate::unity::global_metadata::{is_plain_global_metadata, Metadata};
+use crate::unity::unity_loader::{cache_global_metadata_key, decrypt_global_metadata};
+use crate::utils::consts::METADATA_FILTER;
+use anyhow::{anyhow, bail, Result};
+use clap::Args;
+use cursive::traits::{Nameable, Resizable};
+use cursive::utils::markup::markdown;
+use cursive::views::{Checkbox, Dialog, DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Command line arguments for the metadata encryption process.
+///
+/// This struct holds the original game files the key is recovered from, the plain metadata to
+/// encrypt and the output path:
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
+/// - `input`: Path to the plain global metadata file to encrypt, optional for a round trip check.
+/// - `output`: Path to write the encrypted global metadata file to, required unless checking.
+/// - `round_trip`: Flag to only check that encrypting and decrypting, and loading and saving the
+///   metadata, gives back the input.
+#[derive(Args, Default, Clone)]
+pub struct EncryptMetadataArgs {
+    /// The original IL2CPP binary and encrypted global metadata to recover the key from.
+    #[command(flatten)]
+    pub source: InputArgs,
+    /// Path to the plain global-metadata.dat file to encrypt. With --round-trip, defaults to the
+    /// decrypted original global metadata.
+    #[clap(long)]
+    pub input: Option<PathBuf>,
+    /// Path to write the encrypted global-metadata file to.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+    /// Only check that decrypting the encrypted input, and loading and saving the metadata, gives
+    /// back the input, without writing anything.
+    #[clap(long)]
+    pub round_trip: bool,
+}
+
+impl IntoTui for EncryptMetadataArgs {
+    /// Converts the encryption arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with two input methods for the original game files: one for an
+    /// APK/XAPK file or an unpacked APK folder, and another for IL2CPP and Global Metadata files.
+    /// These are followed by the plain metadata to encrypt, the output file and the round trip
+    /// option. It sets up a callback that validates and processes the user input before invoking
+    /// the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the encryption arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Encrypt Metadata Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    // Input methods for the original game files
+                    .child(InputArgs::make_tui_inputs())
+                    .child(DummyView.fixed_height(1))
+                    // Plain metadata to encrypt and the encrypted output file
+                    .child(make_path_input(
+                        "Plain Metadata File: ",
+                        "input",
+                        BrowseType::File,
+                        Some(METADATA_FILTER),
+                    ))
+                    .child(make_path_input(
+                        "Encrypted Metadata File: ",
+                        "output",
+                        BrowseType::SaveFile,
+                        Some(METADATA_FILTER),
+                    ))
+                    .child(DummyView.fixed_height(1))
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(TextView::new("Round trip check only: "))
+                            .child(Checkbox::new().with_checked(false).with_name("round_trip")),
+                    ),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our EncryptMetadataArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.input = get_optional_path(s, "input");
+                    cmd_inner.output = get_optional_path(s, "output");
+                    cmd_inner.round_trip = get_checkbox_value(s, "round_trip");
+
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::EncryptMetadata(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the encryption arguments.
+    ///
+    /// Checks that:
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - A plain metadata file and an output file are provided unless only checking the round trip.
+    /// - The output file is not a directory and differs from the input files.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        self.source.validate()?;
+
+        match &self.input {
+            Some(input) if !input.is_file() => {
+                return Err("Plain metadata file does not exist".into());
+            }
+            None if !self.round_trip => {
+                return Err(
+                    "Plain metadata file is required unless only checking the round trip".into(),
+                );
+            }
+            _ => {}
+        }
+
+        // The round trip check does not write anything, so the output file is irrelevant.
+        if self.round_trip {
+            return Ok(());
+        }
+
+        let Some(output) = &self.output else {
+            return Err(
+                "Encrypted metadata file is required unless only checking the round trip".into(),
+            );
+        };
+        if output.is_dir() {
+            return Err("Encrypted metadata file is a directory".into());
+        }
+        if self
+            .source
+            .paths()
+            .chain(&self.input)
+            .any(|input| input == output)
+        {
+            return Err("Encrypted metadata file must differ from the input files".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the metadata encryption process.
+///
+/// This function performs the following steps:
+/// 1. Loads input data (il2cpp and the original global metadata) from either an archive or
+///    individual files.
+/// 2. Recovers the metadata key and key xor, from the key cache or the il2cpp binary, by
+///    decrypting the original global metadata.
+/// 3. Encrypts the plain global metadata with the same key, in the format the game expects.
+/// 4. Either checks that decrypting the result, and loading and saving the plain metadata with the
+///    metadata writer, gives back the plain metadata, or writes the encrypted global metadata to
+///    the output path and records its key in the key cache.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - Missing or unreadable input files.
+/// - Failure to recover the keys, or a plain metadata file that is not decrypted global metadata.
+/// - A round trip that does not give back the plain metadata, or metadata the writer cannot
+///   reproduce.
+/// - File I/O errors while writing the output file.
+pub fn execute(args: EncryptMetadataArgs) -> Result<()> {
+    info!("Running metadata encryption command...");
+    info!(progress = 0, max = 3; "");
+
+    info!("Loading input data...");
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    let plain_data = args.input.as_ref().map(fs::read).transpose()?;
+    info!(progress_tick = 1; "");
+
+    info!("Recovering metadata key from the original global metadata...");
+    let elf = Elf::new(il2cpp_data)?;
+    let decrypted =
+        decrypt_global_metadata(&elf, &global_metadata_data, args.source.metadata_key())?;
+    let (key, key_xor) = (decrypted.key, decrypted.key_xor);
+
+    // Without a plain metadata file, the round trip is checked on the original global metadata.
+    let plain_data = plain_data.unwrap_or(decrypted.data);
+    if !is_plain_global_metadata(&plain_data) || plain_data.len() < 8 {
+        bail!("The plain metadata file is not decrypted global metadata");
+    }
+
+    info!("Encrypting global metadata...");
+    let encrypted = global_metadata::encrypt(&plain_data, key, key_xor);
+    info!(progress_tick = 1; "");
+
+    if args.round_trip {
+        info!("Checking round trip...");
+        if global_metadata::decrypt(&encrypted, key, key_xor) != plain_data {
+            bail!("Round trip failed: decrypting the encrypted metadata did not give back the plain metadata");
+        }
+        // Re-encrypting the original metadata must reproduce the original file exactly.
+        if args.input.is_none() && encrypted != global_metadata_data {
+            bail!("Round trip failed: re-encrypting the original global metadata did not reproduce it");
+        }
+        // Loading and saving the metadata must not change it either, so edits made with the
+        // metadata writer only change what was edited.
+        let version = i32::from_le_bytes(plain_data[4..8].try_into()?);
+        if UnityVersion::from_metadata_version(version).is_some() {
+            info!("Checking metadata writer round trip...");
+            Metadata::check_round_trip(&plain_data)
+                .map_err(|err| anyhow!("Metadata writer round trip failed: {}", err))?;
+        } else {
+            warn!(
+                "Global metadata v{} is not supported, skipping the metadata writer round trip",
+                version
+            );
+        }
+        info!(progress_tick = 1; "");
+        info!(
+            "Done! Round trip succeeded for {} bytes of global metadata",
+            plain_data.len()
+        );
+        return Ok(());
+    }
+
+    let output = args
+        .output
+        .as_ref()
+        .ok_or_else(|| anyhow!("Please provide --output or --round-trip."))?;
+    info!("Writing encrypted global metadata...");
+    fs::write(output, &encrypted)?;
+    cache_global_metadata_key(&encrypted, key, key_xor)?;
+    info!(progress_tick = 1; "");
+
+    info!(
+        "Done! Encrypted global metadata written to {}",
+        output.display()
+    );
+    Ok(())
+}
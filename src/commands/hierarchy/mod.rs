@@ -0,0 +1,331 @@
+use crate::commands::hierarchy::tree::TreeNode;
+use crate::commands::input::InputArgs;
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{
+    get_checkbox_value, get_optional_path, get_text_value, make_path_input, make_text_input,
+    BrowseType,
+};
+use crate::commands::AppCommand;
+use crate::unity::generated::CIl2Cpp::TypeDefinitionIndex;
+use crate::unity::hierarchy::TypeHierarchy;
+use crate::unity::il2cpp::Il2Cpp;
+use crate::unity::unity_loader::load_il2cpp;
+use crate::utils::consts::TEXT_FILTER;
+use anyhow::{bail, Result};
+use clap::Args;
+use cursive::traits::{Nameable, Resizable};
+use cursive::utils::markup::markdown;
+use cursive::views::{Checkbox, Dialog, DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use hashbrown::HashSet;
+use log::info;
+use parking_lot::Mutex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod tree;
+
+/// Command line arguments for the type hierarchy query.
+///
+/// This struct holds the input files, the type to show and the output options:
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
+/// - `type_name`: Required name of the type to show the hierarchy of.
+/// - `vtable`: Flag to also list the method in each vtable slot.
+/// - `output`: Optional path to also write the tree to.
+#[derive(Args, Default, Clone)]
+pub struct HierarchyArgs {
+    /// The IL2CPP binary and global metadata to work on.
+    #[command(flatten)]
+    pub source: InputArgs,
+    /// Name of the type to show, with or without its namespace and generic parameters.
+    #[clap(long = "type")]
+    pub type_name: String,
+    /// Also list the method in each vtable slot.
+    #[clap(long)]
+    pub vtable: bool,
+    /// Path to also write the tree to, as a text file.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+impl IntoTui for HierarchyArgs {
+    /// Converts the hierarchy arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with two input methods: one for an APK/XAPK file or an
+    /// unpacked APK folder, and another for IL2CPP and Global Metadata files, followed by the
+    /// type name, the vtable option and the optional output file. It sets up a callback that validates and processes the user input before
+    /// invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the hierarchy arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Type Hierarchy Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(InputArgs::make_tui_inputs())
+                    .child(DummyView.fixed_height(1))
+                    // Type to show and what to show of it
+                    .child(make_text_input("Type Name (*): ", "type_name"))
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(TextView::new("List vtable slots: "))
+                            .child(Checkbox::new().with_checked(false).with_name("vtable")),
+                    )
+                    .child(make_path_input(
+                        "Output File: ",
+                        "output",
+                        BrowseType::SaveFile,
+                        Some(TEXT_FILTER),
+                    )),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our HierarchyArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.type_name = get_text_value(s, "type_name").trim().to_string();
+                    cmd_inner.vtable = get_checkbox_value(s, "vtable");
+                    cmd_inner.output = get_optional_path(s, "output");
+
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::Hierarchy(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the hierarchy arguments.
+    ///
+    /// Checks that:
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - A type name is provided.
+    /// - An output file, if provided, is not a directory and differs from the input files.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        self.source.validate()?;
+
+        if self.type_name.trim().is_empty() {
+            return Err("Type name must not be empty".into());
+        }
+
+        if let Some(output) = &self.output {
+            if output.is_dir() {
+                return Err("Output file is a directory".into());
+            }
+            if self.source.paths().any(|input| input == output) {
+                return Err("Output file must differ from the input files".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the type hierarchy query.
+///
+/// This function performs the following steps:
+/// 1. Loads input data (il2cpp and global metadata) from either an archive or individual files.
+/// 2. Decrypts the global metadata, unless it is already decrypted, and loads the il2cpp binary.
+/// 3. Finds the type definitions with the given name and builds the subclass and interface
+///    relations of all types.
+/// 4. Prints a tree for each matching type with its base types, interfaces, nested types,
+///    subclasses, the implementors of interfaces and optionally the vtable, and writes it to the
+///    output file if one is given.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - Missing or unreadable input files.
+/// - Failure to decrypt the global metadata or load the il2cpp binary.
+/// - No type with the given name, or a type that cannot be resolved.
+/// - File I/O errors while writing the output file.
+pub fn execute(args: HierarchyArgs) -> Result<()> {
+    info!("Running hierarchy command...");
+    info!(progress = 0, max = 4; "");
+
+    info!("Loading input data...");
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    info!(progress_tick = 1; "");
+
+    info!("Loading global metadata and il2cpp...");
+    let il2cpp = load_il2cpp(
+        il2cpp_data,
+        global_metadata_data,
+        args.source.metadata_key(),
+    )?;
+    info!(progress_tick = 1; "");
+
+    info!("Resolving type hierarchy...");
+    let type_indices = il2cpp.find_type_definitions(&args.type_name)?;
+    if type_indices.is_empty() {
+        bail!("No type named {} was found", args.type_name);
+    }
+    let hierarchy = TypeHierarchy::new(&il2cpp)?;
+    info!(progress_tick = 1; "");
+
+    let mut lines = Vec::new();
+    for type_index in type_indices {
+        let tree = type_tree(&il2cpp, &hierarchy, type_index, args.vtable)?;
+        lines.extend(tree.render());
+    }
+    for line in &lines {
+        info!("{}", line);
+    }
+    if let Some(output) = &args.output {
+        fs::write(output, lines.join("\n") + "\n")?;
+        info!("Wrote the hierarchy to {}", output.display());
+    }
+    info!(progress_tick = 1; "");
+
+    info!("Done!");
+    Ok(())
+}
+
+/// Builds the tree of a type definition with a branch for each non-empty relation.
+fn type_tree<'a>(
+    il2cpp: &'a Il2Cpp<'a>,
+    hierarchy: &TypeHierarchy,
+    type_index: TypeDefinitionIndex,
+    vtable: bool,
+) -> Result<TreeNode> {
+    let ty_def = il2cpp.type_definition(type_index)?;
+    let mut root = TreeNode::new(format!(
+        "{} {} (TypeDefIndex: {})",
+        ty_def.kind_name(),
+        il2cpp.type_definition_name(type_index)?,
+        type_index
+    ));
+
+    let mut base_types = TreeNode::new("Base types");
+    for base_type in il2cpp.base_types(type_index)? {
+        base_types.push(TreeNode::new(il2cpp.type_full_name(base_type)?));
+    }
+    root.push_branch(base_types);
+
+    let mut interfaces = TreeNode::new("Interfaces");
+    for &interface in il2cpp.interfaces(type_index)? {
+        interfaces.push(TreeNode::new(il2cpp.type_full_name(interface)?));
+    }
+    root.push_branch(interfaces);
+
+    let mut nested_types = TreeNode::new("Nested types");
+    for &nested_type in il2cpp.nested_types(type_index)? {
+        nested_types.push(TreeNode::new(il2cpp.type_definition_name(nested_type)?));
+    }
+    root.push_branch(nested_types);
+
+    let mut subclasses = TreeNode::new("Subclasses");
+    let mut visited = HashSet::new();
+    for &subclass in hierarchy.subclasses(type_index) {
+        subclasses.push(subclass_tree(il2cpp, hierarchy, subclass, &mut visited)?);
+    }
+    root.push_branch(subclasses);
+
+    if ty_def.is_interface() {
+        let mut visited = HashSet::new();
+        root.push_branch(implementor_tree(
+            il2cpp,
+            hierarchy,
+            type_index,
+            TreeNode::new("Implementors"),
+            &mut visited,
+        )?);
+    }
+
+    if vtable {
+        let mut slots = TreeNode::new("VTable");
+        for (slot, method) in il2cpp.vtable(type_index)?.into_iter().enumerate() {
+            let name = match method {
+                Some(method) => method.full_name(il2cpp)?,
+                None => "-".to_string(),
+            };
+            slots.push(TreeNode::new(format!("[{}] {}", slot, name)));
+        }
+        root.push_branch(slots);
+    }
+    Ok(root)
+}
+
+/// Builds the tree of a type definition and everything deriving from it.
+fn subclass_tree<'a>(
+    il2cpp: &'a Il2Cpp<'a>,
+    hierarchy: &TypeHierarchy,
+    type_index: TypeDefinitionIndex,
+    visited: &mut HashSet<TypeDefinitionIndex>,
+) -> Result<TreeNode> {
+    let mut node = TreeNode::new(il2cpp.type_definition_name(type_index)?);
+    if visited.insert(type_index) {
+        for &subclass in hierarchy.subclasses(type_index) {
+            node.push(subclass_tree(il2cpp, hierarchy, subclass, visited)?);
+        }
+    }
+    Ok(node)
+}
+
+/// Adds the types implementing an interface to a node: the classes and value types declaring it
+/// with their subclasses, and the interfaces extending it with their own implementors.
+fn implementor_tree<'a>(
+    il2cpp: &'a Il2Cpp<'a>,
+    hierarchy: &TypeHierarchy,
+    interface: TypeDefinitionIndex,
+    mut node: TreeNode,
+    visited: &mut HashSet<TypeDefinitionIndex>,
+) -> Result<TreeNode> {
+    if !visited.insert(interface) {
+        return Ok(node);
+    }
+    for &implementor in hierarchy.implementors(interface) {
+        node.push(subclass_tree(il2cpp, hierarchy, implementor, visited)?);
+    }
+    for &derived in hierarchy.derived_interfaces(interface) {
+        let label = format!("{} (interface)", il2cpp.type_definition_name(derived)?);
+        node.push(implementor_tree(
+            il2cpp,
+            hierarchy,
+            derived,
+            TreeNode::new(label),
+            visited,
+        )?);
+    }
+    Ok(node)
+}
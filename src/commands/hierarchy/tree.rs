@@ -0,0 +1,50 @@
+/// A node of a tree that is printed with box drawing characters, one line per node.
+pub struct TreeNode {
+    /// The text of the node.
+    label: String,
+    /// The child nodes, printed below the node and indented one level.
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    /// Creates a node without children.
+    pub fn new(label: impl Into<String>) -> Self {
+        TreeNode {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child node.
+    pub fn push(&mut self, child: TreeNode) {
+        self.children.push(child);
+    }
+
+    /// Adds a child node that groups other nodes under a heading, unless it has no children.
+    pub fn push_branch(&mut self, branch: TreeNode) {
+        if !branch.children.is_empty() {
+            self.push(branch);
+        }
+    }
+
+    /// Returns the lines of the tree, starting with this node.
+    pub fn render(&self) -> Vec<String> {
+        let mut lines = vec![self.label.clone()];
+        self.render_children("", &mut lines);
+        lines
+    }
+
+    /// Appends the lines of the children, each prefixed with the connectors of its ancestors.
+    fn render_children(&self, prefix: &str, lines: &mut Vec<String>) {
+        for (index, child) in self.children.iter().enumerate() {
+            let is_last = index + 1 == self.children.len();
+            let (connector, indent) = if is_last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            lines.push(format!("{}{}{}", prefix, connector, child.label));
+            child.render_children(&format!("{}{}", prefix, indent), lines);
+        }
+    }
+}
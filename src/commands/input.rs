@@ -0,0 +1,274 @@
+use crate::archive::detect::{detect_input_kind, read_direct_inputs, InputKind};
+use crate::archive::open_archive;
+use crate::binary::abi::Abi;
+use crate::commands::tui::utils::{
+    get_metadata_key_inputs, get_optional_path, make_metadata_key_inputs, make_path_input,
+    BrowseType,
+};
+use crate::unity::profile::log_archive_build;
+use crate::unity::unity_loader::{
+    parse_encryption_key, parse_encryption_key_xor, EncryptionKey, EncryptionKeyXor,
+};
+use crate::utils::consts::{APK_FILTER, GLOBAL_METADATA_PATH, IL2CPP_FILTER, METADATA_FILTER};
+use anyhow::{anyhow, bail, Result};
+use clap::Args;
+use cursive::traits::Resizable;
+use cursive::utils::markup::markdown;
+use cursive::views::{DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use std::path::PathBuf;
+
+/// Command line arguments selecting the IL2CPP binary and global metadata to work on.
+///
+/// Shared by every command that loads the game code, through `#[command(flatten)]`:
+/// - `apk`: Optional path to an APK archive or unpacked APK directory, or to a raw IL2CPP binary or
+///   global metadata file paired with the other one.
+/// - `il2cpp`: Optional path to the il2cpp file.
+/// - `global_metadata`: Optional path to the global metadata file.
+/// - `abi`: Optional ABI of the IL2CPP binary to read from the APK archive.
+/// - `metadata_key`, `metadata_key_xor`: Optional global metadata key to use instead of extracting it.
+#[derive(Args, Default, Clone)]
+pub struct InputArgs {
+    /// Path to an APK file, or to a directory containing an unpacked APK. A raw IL2CPP binary or
+    /// global metadata file is also accepted, together with the other one given with --il2cpp or
+    /// --global-metadata.
+    #[clap(long)]
+    pub apk: Option<PathBuf>,
+    /// Path to the il2cpp file.
+    #[clap(long)]
+    pub il2cpp: Option<PathBuf>,
+    /// Path to the global-metadata file.
+    #[clap(long)]
+    pub global_metadata: Option<PathBuf>,
+    /// ABI of the IL2CPP binary to read from the APK. Detected automatically if omitted.
+    #[clap(long, value_enum)]
+    pub abi: Option<Abi>,
+    /// Global metadata key as 32 hexadecimal digits, to use instead of extracting it from the il2cpp file.
+    #[clap(long, value_parser = parse_encryption_key, requires = "metadata_key_xor")]
+    pub metadata_key: Option<EncryptionKey>,
+    /// Global metadata key xor as a hexadecimal (0x...) or decimal number, used with --metadata-key.
+    #[clap(long, value_parser = parse_encryption_key_xor, requires = "metadata_key")]
+    pub metadata_key_xor: Option<EncryptionKeyXor>,
+}
+
+impl InputArgs {
+    /// Creates the TUI fields for the input arguments, read back with
+    /// [`InputArgs::read_tui_inputs`].
+    ///
+    /// The layout offers two input methods: an APK/XAPK file or an unpacked APK folder, or IL2CPP
+    /// and Global Metadata files, followed by the optional metadata key.
+    pub fn make_tui_inputs() -> LinearLayout {
+        LinearLayout::vertical()
+            // Instruction text for choosing an input method
+            .child(TextView::new("Choose one input method:").center())
+            .child(DummyView.fixed_height(1))
+            // Method 1: APK/XAPK file or unpacked APK folder
+            .child(TextView::new(markdown::parse(
+                "**Method 1: Provide an APK/XAPK file or unpacked APK folder:**",
+            )))
+            .child(make_path_input(
+                "(X)APK File: ",
+                "apk",
+                BrowseType::File,
+                Some(APK_FILTER),
+            ))
+            .child(make_path_input(
+                "Or Unpacked APK Folder: ",
+                "apk_dir",
+                BrowseType::Folder,
+                None,
+            ))
+            .child(DummyView.fixed_height(1))
+            // Method 2: IL2CPP and Global Metadata files
+            .child(TextView::new(markdown::parse(
+                "**Method 2: Provide IL2CPP and Global Metadata files:**",
+            )))
+            .child(make_path_input(
+                "IL2CPP File: ",
+                "il2cpp",
+                BrowseType::File,
+                Some(IL2CPP_FILTER),
+            ))
+            .child(make_path_input(
+                "Global Metadata File: ",
+                "global_metadata",
+                BrowseType::File,
+                Some(METADATA_FILTER),
+            ))
+            .child(DummyView.fixed_height(1))
+            // Optional metadata key, for builds the key extraction does not support yet
+            .child(make_metadata_key_inputs())
+    }
+
+    /// Fills in the input arguments from the TUI fields created by
+    /// [`InputArgs::make_tui_inputs`], preferring an archive file over an unpacked folder if both
+    /// are given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if the metadata key or key xor cannot be parsed.
+    pub fn read_tui_inputs(&mut self, s: &mut Cursive) -> Result<(), String> {
+        self.apk = get_optional_path(s, "apk").or_else(|| get_optional_path(s, "apk_dir"));
+        self.il2cpp = get_optional_path(s, "il2cpp");
+        self.global_metadata = get_optional_path(s, "global_metadata");
+        let (key, key_xor) = get_metadata_key_inputs(s)?;
+        self.metadata_key = key;
+        self.metadata_key_xor = key_xor;
+        Ok(())
+    }
+
+    /// Validates the input arguments.
+    ///
+    /// Checks that:
+    /// - Either an APK file or directory is provided, or both IL2CPP and Global Metadata files are
+    ///   given, or `--apk` is a raw IL2CPP binary or global metadata file and exactly one of the
+    ///   IL2CPP and Global Metadata files is given alongside it.
+    /// - The provided file paths exist.
+    /// - A metadata key and key xor are either both provided or both omitted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    pub fn validate(&self) -> Result<(), String> {
+        // Validate input file combinations.
+        match (&self.apk, &self.il2cpp, &self.global_metadata) {
+            // Valid: An APK, or a raw input file paired with at most one of the direct inputs.
+            (Some(apk_path), il2cpp_path, global_path) => {
+                if !apk_path.exists() {
+                    return Err("APK file or directory does not exist".into());
+                }
+                let kind = detect_input_kind(apk_path).map_err(|err| err.to_string())?;
+                let direct_path = match (il2cpp_path, global_path) {
+                    (None, None) => None,
+                    (Some(path), None) | (None, Some(path)) => Some(path),
+                    (Some(_), Some(_)) => {
+                        return Err(
+                            "Either provide an APK file or both IL2CPP and global-metadata files"
+                                .into(),
+                        );
+                    }
+                };
+                match direct_path {
+                    None if !kind.is_archive() => {
+                        return Err(format!(
+                            "{} is a {}, provide the {} with --il2cpp or --global-metadata",
+                            apk_path.display(),
+                            kind,
+                            missing_direct_input(kind)
+                        ));
+                    }
+                    Some(_) if kind.is_archive() => {
+                        return Err(
+                            "Either provide an APK file or both IL2CPP and global-metadata files"
+                                .into(),
+                        );
+                    }
+                    Some(path) if !path.exists() => {
+                        return Err(format!("{} does not exist", path.display()));
+                    }
+                    _ => {}
+                }
+            }
+            // Valid: Both IL2CPP and global-metadata are provided.
+            (None, Some(il2cpp_path), Some(global_path)) => {
+                if !il2cpp_path.exists() {
+                    return Err("IL2CPP file does not exist".into());
+                }
+                if !global_path.exists() {
+                    return Err("Global metadata file does not exist".into());
+                }
+            }
+            // Any other combination is invalid.
+            _ => {
+                return Err(
+                    "Either provide an APK file or both IL2CPP and global-metadata files".into(),
+                );
+            }
+        }
+
+        // The key and key xor are only useful together.
+        if self.metadata_key.is_some() != self.metadata_key_xor.is_some() {
+            return Err("Metadata key and metadata key xor must be provided together".into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the paths of the provided input files, so commands can check that their output
+    /// does not overwrite one of them.
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        [&self.apk, &self.il2cpp, &self.global_metadata]
+            .into_iter()
+            .flatten()
+    }
+
+    /// Returns the manually provided metadata key and key xor, if both are given.
+    pub fn metadata_key(&self) -> Option<(EncryptionKey, EncryptionKeyXor)> {
+        self.metadata_key.zip(self.metadata_key_xor)
+    }
+
+    /// Retrieves il2cpp and global metadata data from the provided input sources.
+    ///
+    /// Depending on the provided arguments, this function will attempt to:
+    /// - Open an archive or unpacked APK directory and read files at predefined paths, using the
+    ///   IL2CPP binary of the selected or detected ABI.
+    /// - Read a raw IL2CPP binary or global metadata file given with `--apk` together with the
+    ///   other file given with `--il2cpp` or `--global-metadata`.
+    /// - Read individual il2cpp and global metadata files from the file system.
+    ///
+    /// # Returns
+    ///
+    /// A tuple `(il2cpp_data, global_metadata_data)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Neither archive nor individual file paths are provided.
+    /// - A raw input file is given with `--apk` without the other file.
+    /// - Reading from the archive or file system fails.
+    pub fn load_inputs(&self) -> Result<(Vec<u8>, Vec<u8>)> {
+        if let Some(ref apk_path) = self.apk {
+            let kind = detect_input_kind(apk_path)?;
+            if !kind.is_archive() {
+                let other_path = self
+                    .il2cpp
+                    .as_ref()
+                    .or(self.global_metadata.as_ref())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "{} is a {}, provide the {} with --il2cpp or --global-metadata",
+                            apk_path.display(),
+                            kind,
+                            missing_direct_input(kind)
+                        )
+                    })?;
+                return match kind {
+                    InputKind::Il2Cpp => read_direct_inputs(apk_path, other_path),
+                    _ => read_direct_inputs(other_path, apk_path),
+                };
+            }
+            let mut archive = open_archive(apk_path)?;
+            log_archive_build(archive.as_mut());
+            let gm_data = archive.read_internal_file(GLOBAL_METADATA_PATH)?;
+            let abi = archive.resolve_abi(self.abi)?;
+            let il2cpp_data = archive.read_internal_file(&abi.il2cpp_path())?;
+            Ok((il2cpp_data, gm_data))
+        } else if let (Some(il2cpp_path), Some(global_metadata_path)) =
+            (&self.il2cpp, &self.global_metadata)
+        {
+            read_direct_inputs(il2cpp_path, global_metadata_path)
+        } else {
+            bail!("Please provide either --apk or both --il2cpp and --global-metadata.")
+        }
+    }
+}
+
+/// Returns a description of the direct input that has to be given alongside a raw input file of
+/// the given kind.
+fn missing_direct_input(kind: InputKind) -> &'static str {
+    match kind {
+        InputKind::Il2Cpp => "global metadata file",
+        _ => "IL2CPP binary",
+    }
+}
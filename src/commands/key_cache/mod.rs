@@ -0,0 +1,180 @@
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{get_optional_path, make_path_input, BrowseType};
+use crate::commands::AppCommand;
+use crate::unity::unity_loader::{export_metadata_keys, import_metadata_keys, ExportedMetadataKey};
+use crate::utils::consts::KEY_CACHE_FILTER;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Args};
+use cursive::traits::Resizable;
+use cursive::utils::markup::markdown;
+use cursive::views::{Dialog, DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use log::info;
+use parking_lot::Mutex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Command line arguments for exporting or importing the metadata key cache.
+///
+/// Exactly one of the two paths must be given:
+/// - `export`: Optional path to write the cached metadata keys to.
+/// - `import`: Optional path to read metadata keys from and add to the cache.
+#[derive(Args, Default, Clone)]
+#[clap(group(ArgGroup::new("action").required(true).args(["export", "import"])))]
+pub struct KeyCacheArgs {
+    /// Path to write the cached metadata keys to as JSON.
+    #[clap(long)]
+    pub export: Option<PathBuf>,
+    /// Path to a JSON file of metadata keys, as written by --export, to add to the cache.
+    #[clap(long)]
+    pub import: Option<PathBuf>,
+}
+
+impl IntoTui for KeyCacheArgs {
+    /// Converts the key cache arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with an export file and an import file, of which one must be
+    /// given. It sets up a callback that validates and processes the user input before invoking
+    /// the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the key cache arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Metadata Key Cache Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(TextView::new("Choose one action:").center())
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new(markdown::parse(
+                        "**Export the cached metadata keys:**",
+                    )))
+                    .child(make_path_input(
+                        "Export To File: ",
+                        "export",
+                        BrowseType::SaveFile,
+                        Some(KEY_CACHE_FILTER),
+                    ))
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new(markdown::parse(
+                        "**Import metadata keys into the cache:**",
+                    )))
+                    .child(make_path_input(
+                        "Import From File: ",
+                        "import",
+                        BrowseType::File,
+                        Some(KEY_CACHE_FILTER),
+                    )),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our KeyCacheArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.export = get_optional_path(s, "export");
+                    cmd_inner.import = get_optional_path(s, "import");
+
+                    if let Err(err) = cmd_inner.validate() {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::KeyCache(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the key cache arguments.
+    ///
+    /// Checks that:
+    /// - Exactly one of the export and import files is provided.
+    /// - The export file is not a directory.
+    /// - The import file exists.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        match (&self.export, &self.import) {
+            (Some(export), None) => {
+                if export.is_dir() {
+                    return Err("Export file is a directory".into());
+                }
+            }
+            (None, Some(import)) => {
+                if !import.is_file() {
+                    return Err("Import file does not exist".into());
+                }
+            }
+            _ => return Err("Either provide an export file or an import file".into()),
+        }
+        Ok(())
+    }
+}
+
+/// Executes the key cache export or import.
+///
+/// Exporting writes every cached metadata key, along with the foldhash of the encrypted global
+/// metadata it belongs to, as a JSON array. Importing reads such a file and adds its entries to
+/// the cache, so keys recovered on one machine can be used on another.
+///
+/// # Errors
+///
+/// Returns an error if the files cannot be read or written, or if the imported file is not a
+/// valid key cache export.
+pub fn execute(args: KeyCacheArgs) -> Result<()> {
+    info!("Running metadata key cache command...");
+    info!(progress = 0, max = 1; "");
+
+    if let Some(export) = &args.export {
+        let entries = export_metadata_keys();
+        fs::write(export, serde_json::to_string_pretty(&entries)?)?;
+        info!(progress_tick = 1; "");
+        info!(
+            "Done! Exported {} metadata keys to {}",
+            entries.len(),
+            export.display()
+        );
+    } else if let Some(import) = &args.import {
+        let entries: Vec<ExportedMetadataKey> = serde_json::from_slice(&fs::read(import)?)
+            .map_err(|err| anyhow!("{} is not a metadata key export: {}", import.display(), err))?;
+        let new_entries = import_metadata_keys(&entries)?;
+        info!(progress_tick = 1; "");
+        info!(
+            "Done! Imported {} metadata keys ({} new) from {}",
+            entries.len(),
+            new_entries,
+            import.display()
+        );
+    }
+    Ok(())
+}
@@ -7,9 +7,22 @@ use strum::EnumIter;
 use tui::commands::IntoTui;
 
 pub mod cli;
+pub mod constants;
+pub mod decrypt_metadata;
+pub mod dump;
+pub mod encrypt_metadata;
+pub mod hierarchy;
+pub mod input;
+pub mod key_cache;
 pub mod patch;
 pub mod proto;
+pub mod query;
+pub mod sign;
+pub mod string_literals;
+pub mod symbols;
+pub mod symtab;
 pub mod tui;
+pub mod unpack;
 
 /// Command-line arguments for the application.
 ///
@@ -44,6 +57,30 @@ pub enum AppCommand {
     ExtractProto(proto::ExtractArgs),
     /// Patch the IL2CPP file to remove modification detection by updating code hashes.
     Patch(patch::PatchArgs),
+    /// List or extract files from an APK, XAPK, APKS or APKM archive, including nested split APKs.
+    Unpack(unpack::UnpackArgs),
+    /// Sign an APK, or every split APK in an XAPK, APKS or APKM archive, with the v1, v2 and v3 schemes.
+    Sign(sign::SignArgs),
+    /// Decrypt the global metadata with the key embedded in the IL2CPP binary and write it as a plain file.
+    DecryptMetadata(decrypt_metadata::DecryptMetadataArgs),
+    /// Encrypt a plain global metadata file with the key of the original game files, or check the round trip.
+    EncryptMetadata(encrypt_metadata::EncryptMetadataArgs),
+    /// Export the cached metadata keys to a JSON file, or import keys from one.
+    KeyCache(key_cache::KeyCacheArgs),
+    /// Export the string literals of the global metadata, or import edited ones into a new metadata file.
+    StringLiterals(string_literals::StringLiteralsArgs),
+    /// Write a dump.cs style listing of all types, fields, properties and methods in C# syntax.
+    Dump(dump::DumpArgs),
+    /// Write IDA, Ghidra, Binary Ninja and radare2 scripts that name the methods, metadata usages and string literals of the IL2CPP binary.
+    Symbols(symbols::SymbolsArgs),
+    /// Write a copy of the IL2CPP binary with a symbol table naming every method, for use with objdump, perf, gdb and crash symbolizers.
+    Symtab(symtab::SymtabArgs),
+    /// Show the base types, interfaces, nested types, subclasses, implementors and vtable of a type as a tree.
+    Hierarchy(hierarchy::HierarchyArgs),
+    /// Query types, fields, methods, parameters and string literals with a filter language, once or as a REPL.
+    Query(query::QueryArgs),
+    /// Export every const field, enum and parameter default value to JSON.
+    Constants(constants::ConstantsArgs),
 }
 
 impl AppCommand {
@@ -63,6 +100,18 @@ impl AppCommand {
         match self.clone() {
             AppCommand::ExtractProto(args) => proto::execute(args),
             AppCommand::Patch(args) => patch::execute(args),
+            AppCommand::Unpack(args) => unpack::execute(args),
+            AppCommand::Sign(args) => sign::execute(args),
+            AppCommand::DecryptMetadata(args) => decrypt_metadata::execute(args),
+            AppCommand::EncryptMetadata(args) => encrypt_metadata::execute(args),
+            AppCommand::KeyCache(args) => key_cache::execute(args),
+            AppCommand::StringLiterals(args) => string_literals::execute(args),
+            AppCommand::Dump(args) => dump::execute(args),
+            AppCommand::Symbols(args) => symbols::execute(args),
+            AppCommand::Symtab(args) => symtab::execute(args),
+            AppCommand::Hierarchy(args) => hierarchy::execute(args),
+            AppCommand::Query(args) => query::execute(args),
+            AppCommand::Constants(args) => constants::execute(args),
         }
     }
 
@@ -77,8 +126,26 @@ impl AppCommand {
         match self {
             AppCommand::ExtractProto(_) => "Extract Protobuf",
             AppCommand::Patch(_) => "Patch IL2CPP",
+            AppCommand::Unpack(_) => "Unpack Archive",
+            AppCommand::Sign(_) => "Sign Archive",
+            AppCommand::DecryptMetadata(_) => "Decrypt Metadata",
+            AppCommand::EncryptMetadata(_) => "Encrypt Metadata",
+            AppCommand::KeyCache(_) => "Metadata Key Cache",
+            AppCommand::StringLiterals(_) => "String Literals",
+            AppCommand::Dump(_) => "Dump C# Declarations",
+            AppCommand::Symbols(_) => "Export Symbol Scripts",
+            AppCommand::Symtab(_) => "Add Symbol Table",
+            AppCommand::Hierarchy(_) => "Type Hierarchy",
+            AppCommand::Query(_) => "Query Metadata",
+            AppCommand::Constants(_) => "Export Constants",
         }
     }
+
+    /// Checks whether the command reads from the standard input, which the TUI takes over, so
+    /// that it always runs in headless mode.
+    pub fn reads_stdin(&self) -> bool {
+        matches!(self, AppCommand::Query(args) if args.repl)
+    }
 }
 
 impl IntoTui for AppCommand {
@@ -99,6 +166,18 @@ impl IntoTui for AppCommand {
         match self {
             AppCommand::ExtractProto(cmd) => cmd.into_tui(siv, next_fn),
             AppCommand::Patch(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Unpack(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Sign(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::DecryptMetadata(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::EncryptMetadata(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::KeyCache(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::StringLiterals(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Dump(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Symbols(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Symtab(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Hierarchy(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Query(cmd) => cmd.into_tui(siv, next_fn),
+            AppCommand::Constants(cmd) => cmd.into_tui(siv, next_fn),
         }
     }
 
@@ -115,6 +194,18 @@ impl IntoTui for AppCommand {
         match self {
             AppCommand::ExtractProto(cmd) => cmd.validate(),
             AppCommand::Patch(cmd) => cmd.validate(),
+            AppCommand::Unpack(cmd) => cmd.validate(),
+            AppCommand::Sign(cmd) => cmd.validate(),
+            AppCommand::DecryptMetadata(cmd) => cmd.validate(),
+            AppCommand::EncryptMetadata(cmd) => cmd.validate(),
+            AppCommand::KeyCache(cmd) => cmd.validate(),
+            AppCommand::StringLiterals(cmd) => cmd.validate(),
+            AppCommand::Dump(cmd) => cmd.validate(),
+            AppCommand::Symbols(cmd) => cmd.validate(),
+            AppCommand::Symtab(cmd) => cmd.validate(),
+            AppCommand::Hierarchy(cmd) => cmd.validate(),
+            AppCommand::Query(cmd) => cmd.validate(),
+            AppCommand::Constants(cmd) => cmd.validate(),
         }
     }
 }
@@ -123,6 +214,7 @@ impl IntoTui for AppCommand {
 ///
 /// This function attempts to parse command-line arguments using `clap`. Based on the parsed
 /// arguments, it either executes the subcommand in headless mode or launches a text user interface (TUI).
+/// Commands that read from the standard input always run in headless mode.
 /// If no arguments are provided, the TUI is launched by default.
 ///
 /// # Returns
@@ -132,7 +224,7 @@ impl IntoTui for AppCommand {
 pub fn run() -> Result<()> {
     match AppArgs::try_parse() {
         Ok(args) => {
-            if args.headless {
+            if args.headless || args.command.reads_stdin() {
                 run_cli_headless(args)
             } else {
                 run_tui(Some(args))
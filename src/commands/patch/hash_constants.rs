@@ -1,6 +1,6 @@
 use crate::binary::arm64::{
-    parse_madd, parse_mov, parse_movk, parse_movn, parse_movz, Madd, Mov,
-    Register, RET_INSTRUCTION_BYTES, SIZEOF_ARM64_INSTRUCTION,
+    parse_madd, parse_mov, parse_movk, parse_movn, parse_movz, Madd, Mov, Register,
+    RET_INSTRUCTION_BYTES, SIZEOF_ARM64_INSTRUCTION,
 };
 use crate::binary::hex_pattern::HexPattern;
 use crate::unity::il2cpp::Il2Cpp;
@@ -217,32 +217,26 @@ where
     instructions
         .par_iter()
         .enumerate()
-        .fold(
-            IntSet::default,
-            |mut local_set, (i, &inst)| {
-                // Check if the instruction is a MADD.
-                if let Some(madd) = parse_madd(inst) {
-                    // Use the provided callback to validate the MADD instruction.
-                    if validate_madd(&madd) {
-                        // Attempt to reconstruct the constant from the register used in the MADD.
-                        if let Some(imm) = extract_reg_value(instructions, i, madd.rm) {
-                            // Use the provided callback to validate the immediate value.
-                            if validate_imm(imm) {
-                                local_set.insert(imm);
-                            }
+        .fold(IntSet::default, |mut local_set, (i, &inst)| {
+            // Check if the instruction is a MADD.
+            if let Some(madd) = parse_madd(inst) {
+                // Use the provided callback to validate the MADD instruction.
+                if validate_madd(&madd) {
+                    // Attempt to reconstruct the constant from the register used in the MADD.
+                    if let Some(imm) = extract_reg_value(instructions, i, madd.rm) {
+                        // Use the provided callback to validate the immediate value.
+                        if validate_imm(imm) {
+                            local_set.insert(imm);
                         }
                     }
                 }
-                local_set
-            },
-        )
-        .reduce(
-            IntSet::default,
-            |mut a, b| {
-                a.extend(b);
-                a
-            },
-        )
+            }
+            local_set
+        })
+        .reduce(IntSet::default, |mut a, b| {
+            a.extend(b);
+            a
+        })
 }
 
 /// Searches executable LOAD segments of an IL2Cpp binary for potential function hash constants.
@@ -1,5 +1,8 @@
-use crate::archive::open_archive;
+use crate::archive::detect::detect_input_kind;
+use crate::archive::repack::repack_archive;
+use crate::binary::abi::Abi;
 use crate::binary::elf::Elf;
+use crate::commands::input::InputArgs;
 use crate::commands::patch::hash_constants::{find_function_hash_constants, find_segment_hash};
 use crate::commands::patch::section_hashes::update_section_hash;
 use crate::commands::tui::commands::IntoTui;
@@ -7,15 +10,13 @@ use crate::commands::tui::utils::{
     get_optional_path, get_required_path, make_path_input, BrowseType,
 };
 use crate::commands::AppCommand;
-use crate::unity::unity_loader::load_encrypted_il2cpp;
-use crate::utils::consts::{
-    APK_FILTER, GLOBAL_METADATA_PATH, IL2CPP_FILTER, IL2CPP_PATH, METADATA_FILTER,
-};
+use crate::unity::unity_loader::load_il2cpp;
+use crate::utils::consts::{APK_FILTER, IL2CPP_FILTER};
 use anyhow::{bail, Result};
 use clap::Args;
 use cursive::traits::Resizable;
 use cursive::utils::markup::markdown;
-use cursive::views::{Dialog, DummyView, LinearLayout, TextView};
+use cursive::views::{Dialog, DummyView, LinearLayout};
 use cursive::Cursive;
 use function_hashes::update_fn_hashes;
 use log::info;
@@ -32,34 +33,31 @@ mod section_hashes;
 ///
 /// This struct holds the file paths for both the original and modified data sources.
 /// Users can supply input in one of two ways:
-/// 1. A single APK/XAPK archive that contains the IL2CPP and global metadata files.
+/// 1. A single APK/XAPK archive, or an unpacked APK directory, that contains the IL2CPP and global
+///    metadata files.
 /// 2. Separate IL2CPP and global metadata file paths.
 ///
 /// # Fields
-/// - `apk`: Optional path to the original APK/XAPK file.
-/// - `il2cpp`: Optional path to the original IL2CPP file.
-/// - `global_metadata`: Optional path to the original global metadata file.
+/// - `source`: The original IL2CPP binary and global metadata, see [`InputArgs`].
 /// - `modified`: Path to the modified IL2CPP file that will be patched.
+/// - `repack`: Optional path to write a copy of the APK/XAPK with the patched IL2CPP file swapped in.
 #[derive(Args, Default, Clone)]
 pub struct PatchArgs {
-    /// Optional path to the original APK/XAPK file.
-    #[clap(long)]
-    pub apk: Option<PathBuf>,
-    /// Optional path to the original IL2CPP file.
-    #[clap(long)]
-    pub il2cpp: Option<PathBuf>,
-    /// Optional path to the original global metadata file.
-    #[clap(long)]
-    pub global_metadata: Option<PathBuf>,
+    /// The original IL2CPP binary and global metadata.
+    #[command(flatten)]
+    pub source: InputArgs,
     /// Path where the modified IL2CPP file will be written.
     pub modified: PathBuf,
+    /// Optional path to write a copy of the APK/XAPK with the patched IL2CPP file swapped in.
+    #[clap(long)]
+    pub repack: Option<PathBuf>,
 }
 
 impl IntoTui for PatchArgs {
     /// Converts `PatchArgs` into a text-based user interface (TUI) form.
     ///
     /// This method creates a dialog window to let users choose an input method:
-    /// either providing an APK/XAPK file, or providing separate IL2CPP and Global Metadata files.
+    /// either providing an APK/XAPK file or unpacked APK folder, or providing separate IL2CPP and Global Metadata files.
     /// It also includes an input for the required modified IL2CPP file.
     /// When the user clicks "Run", the TUI fields are read and used to update `PatchArgs`,
     /// and the provided callback is executed.
@@ -79,36 +77,7 @@ impl IntoTui for PatchArgs {
             .padding_lrtb(1, 1, 1, 0)
             .content(
                 LinearLayout::vertical()
-                    // Explain available input methods
-                    .child(TextView::new("Choose one input method:").center())
-                    .child(DummyView.fixed_height(1))
-                    // Method 1: Using an APK/XAPK file.
-                    .child(TextView::new(markdown::parse(
-                        "**Method 1: Provide an APK/XAPK file:**",
-                    )))
-                    .child(make_path_input(
-                        "(X)APK File: ",
-                        "apk",
-                        BrowseType::File,
-                        Some(APK_FILTER),
-                    ))
-                    .child(DummyView.fixed_height(1))
-                    // Method 2: Using separate IL2CPP and Global Metadata files.
-                    .child(TextView::new(markdown::parse(
-                        "**Method 2: Provide IL2CPP and Global Metadata files:**",
-                    )))
-                    .child(make_path_input(
-                        "IL2CPP File: ",
-                        "il2cpp",
-                        BrowseType::File,
-                        Some(IL2CPP_FILTER),
-                    ))
-                    .child(make_path_input(
-                        "Global Metadata File: ",
-                        "global_metadata",
-                        BrowseType::File,
-                        Some(METADATA_FILTER),
-                    ))
+                    .child(InputArgs::make_tui_inputs())
                     .child(DummyView.fixed_height(1))
                     // Required modified IL2CPP file input.
                     .child(make_path_input(
@@ -116,6 +85,13 @@ impl IntoTui for PatchArgs {
                         "modified",
                         BrowseType::File,
                         Some(IL2CPP_FILTER),
+                    ))
+                    // Optional repacked archive output, only available with Method 1.
+                    .child(make_path_input(
+                        "Repacked (X)APK File: ",
+                        "repack",
+                        BrowseType::SaveFile,
+                        Some(APK_FILTER),
                     )),
             )
             .button("Run", {
@@ -123,18 +99,24 @@ impl IntoTui for PatchArgs {
                 let next_fn = Arc::clone(&next_fn);
                 move |s| {
                     // Update command arguments with paths gathered from the TUI.
-                    let mut cmd = cmd.lock().take().unwrap();
-                    cmd.apk = get_optional_path(s, "apk");
-                    cmd.il2cpp = get_optional_path(s, "il2cpp");
-                    cmd.global_metadata = get_optional_path(s, "global_metadata");
-                    cmd.modified = get_required_path(s, "modified");
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+                    cmd_inner.modified = get_required_path(s, "modified");
+                    cmd_inner.repack = get_optional_path(s, "repack");
+                    if let Err(err) = cmd_inner.source.read_tui_inputs(s) {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
 
                     // Close the dialog window.
                     s.pop_layer();
 
                     // Invoke the callback with the patched command arguments.
                     if let Some(callback) = next_fn.lock().take() {
-                        callback(s, AppCommand::Patch(cmd));
+                        callback(s, AppCommand::Patch(cmd_inner));
                     }
                 }
             })
@@ -150,8 +132,8 @@ impl IntoTui for PatchArgs {
     ///
     /// It checks:
     /// - The modified IL2CPP file exists.
-    /// - The input is either an APK (with no IL2CPP or metadata provided) or both IL2CPP and Global Metadata files are provided.
-    /// - That any provided APK, IL2CPP, or Global Metadata file exists.
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - A repacked archive is only requested together with an APK file, and does not overwrite it.
     ///
     /// # Returns
     /// - `Ok(())` if all validations pass.
@@ -162,38 +144,24 @@ impl IntoTui for PatchArgs {
             return Err("Modified IL2CPP File doesn't exist".into());
         }
 
-        // Determine which optional files have been provided.
-        let apk_provided = self.apk.is_some();
-        let il2cpp_provided = self.il2cpp.is_some();
-        let global_provided = self.global_metadata.is_some();
-
-        // Accept only one valid combination:
-        // Either an APK is provided (and no IL2CPP or Global Metadata),
-        // or both IL2CPP and Global Metadata are provided (and no APK).
-        match (apk_provided, il2cpp_provided, global_provided) {
-            (true, false, false) | (false, true, true) => { /* valid combo */ }
-            _ => {
-                return Err(
-                    "Only an (X)APK or both IL2CPP and Global metadata files can be provided"
-                        .into(),
-                );
-            }
-        }
+        self.source.validate()?;
 
-        // Verify the existence of the APK file if provided.
-        if let Some(apk) = &self.apk {
-            if !apk.exists() {
-                return Err("(X)APK File doesn't exist".into());
-            }
-        }
-
-        // Verify the existence of the IL2CPP and Global Metadata files if provided.
-        if let (Some(il2cpp), Some(global_metadata)) = (&self.il2cpp, &self.global_metadata) {
-            if !global_metadata.exists() {
-                return Err("Global metadata file doesn't exist".into());
-            }
-            if !il2cpp.exists() {
-                return Err("Il2CPP file doesn't exist".into());
+        // Repacking needs an archive to swap the patched IL2CPP file into.
+        if let Some(repack) = &self.repack {
+            match &self.source.apk {
+                None => return Err("Repacking requires an (X)APK File".into()),
+                Some(apk) if apk.is_dir() => {
+                    return Err(
+                        "Repacking requires an (X)APK File, not an unpacked APK folder".into(),
+                    );
+                }
+                Some(apk) if !detect_input_kind(apk).is_ok_and(|kind| kind.is_archive()) => {
+                    return Err("Repacking requires an (X)APK File, not a raw input file".into());
+                }
+                Some(apk) if apk == repack => {
+                    return Err("Repacked (X)APK File must differ from the original".into());
+                }
+                Some(_) => {}
             }
         }
 
@@ -205,10 +173,11 @@ impl IntoTui for PatchArgs {
 ///
 /// The patching process involves:
 /// 1. Loading input data (original IL2CPP, global metadata, and modified IL2CPP).
-/// 2. Decrypting global metadata using keys from the original IL2CPP.
+/// 2. Decrypting global metadata using keys from the original IL2CPP, unless it is already decrypted.
 /// 3. Loading and preparing both the original and modified IL2CPP data.
 /// 4. Updating function hashes and section hashes in the modified IL2CPP.
 /// 5. Writing the patched IL2CPP file to the specified output path.
+/// 6. Optionally writing a copy of the original archive with the patched IL2CPP file swapped in.
 ///
 /// # Parameters
 /// - `args`: The patching arguments containing file paths for input and output.
@@ -218,18 +187,39 @@ impl IntoTui for PatchArgs {
 /// - An error if any of the steps fail.
 pub fn execute(args: PatchArgs) -> Result<()> {
     info!("Running il2cpp patch command...");
-    info!(progress = 0, max = 8; "");
+    let steps = if args.repack.is_some() { 9 } else { 8 };
+    info!(progress = 0, max = steps; "");
 
     info!("Loading input data...");
-    let (il2cpp_data, global_metadata_data, modified_il2cpp_data) = get_input_data(&args)?;
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    let modified_il2cpp_data = fs::read(&args.modified)?;
     info!(progress_tick = 1; "");
 
-    info!("Decrypting global metadata and loading il2cpp...");
-    let il2cpp = load_encrypted_il2cpp(il2cpp_data, global_metadata_data)?;
+    info!("Loading global metadata and il2cpp...");
+    let il2cpp = load_il2cpp(
+        il2cpp_data,
+        global_metadata_data,
+        args.source.metadata_key(),
+    )?;
     info!(progress_tick = 1; "");
 
+    // The hash constants are located by matching ARM64 instruction patterns.
+    if il2cpp.elf.abi != Abi::Arm64V8a {
+        bail!(
+            "Patching is only supported for arm64-v8a IL2CPP binaries, not {}",
+            il2cpp.elf.abi
+        );
+    }
+
     info!("Loading modified il2cpp...");
     let mut modified_il2cpp = Elf::new(modified_il2cpp_data)?;
+    if modified_il2cpp.abi != il2cpp.elf.abi {
+        bail!(
+            "Modified IL2CPP is built for {}, but the original is built for {}",
+            modified_il2cpp.abi,
+            il2cpp.elf.abi
+        );
+    }
     info!(progress_tick = 1; "");
 
     info!("Finding hash constants...");
@@ -245,53 +235,30 @@ pub fn execute(args: PatchArgs) -> Result<()> {
     info!(progress_tick = 1; "");
 
     info!("Updating section hash...");
-    update_section_hash(&il2cpp, &mut modified_il2cpp, vec![".text", "il2cpp"], segment_hash_constant)?;
+    update_section_hash(
+        &il2cpp,
+        &mut modified_il2cpp,
+        vec![".text", "il2cpp"],
+        segment_hash_constant,
+    )?;
     info!(progress_tick = 1; "");
 
     info!("Writing patched il2cpp file...");
     let (_, modified_data) = modified_il2cpp.take();
-    fs::write(args.modified, modified_data)?;
+    fs::write(&args.modified, &modified_data)?;
     info!(progress_tick = 1; "");
 
+    if let (Some(repack_path), Some(apk_path)) = (&args.repack, &args.source.apk) {
+        info!("Repacking archive with patched il2cpp file...");
+        repack_archive(
+            apk_path,
+            repack_path,
+            &il2cpp.elf.abi.il2cpp_path(),
+            &modified_data,
+        )?;
+        info!(progress_tick = 1; "");
+    }
+
     info!("Done!");
     Ok(())
 }
-
-/// Retrieves the input data required for patching based on the provided file paths.
-///
-/// Depending on the provided arguments, this function extracts data using one of the following methods:
-/// - If an APK is provided, it opens the archive and reads the internal IL2CPP and Global Metadata files.
-/// - If separate IL2CPP and Global Metadata files are provided, it reads them directly.
-///
-/// The modified IL2CPP file is always read from the provided path.
-///
-/// # Parameters
-/// - `args`: A reference to `PatchArgs` containing input file paths.
-///
-/// # Returns
-/// A tuple `(il2cpp_data, global_metadata_data, modified_il2cpp_data)` where each element is a vector of bytes.
-///
-/// # Errors
-/// Returns an error if reading any file fails or if the provided arguments do not match the expected configurations.
-#[allow(clippy::type_complexity)]
-fn get_input_data(args: &PatchArgs) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
-    // Read the modified IL2CPP file data.
-    let modified_il2cpp = fs::read(&args.modified)?;
-
-    // If an APK path is provided, extract IL2CPP and metadata from the archive.
-    if let Some(ref apk_path) = args.apk {
-        let mut archive = open_archive(apk_path)?;
-        let gm_data = archive.read_internal_file(GLOBAL_METADATA_PATH)?;
-        let il2cpp_data = archive.read_internal_file(IL2CPP_PATH)?;
-        Ok((il2cpp_data, gm_data, modified_il2cpp))
-    } else if let (Some(il2cpp_path), Some(global_metadata_path)) =
-        (&args.il2cpp, &args.global_metadata)
-    {
-        // Otherwise, read the IL2CPP and Global Metadata files directly.
-        let il2cpp_data = fs::read(il2cpp_path)?;
-        let gm_data = fs::read(global_metadata_path)?;
-        Ok((il2cpp_data, gm_data, modified_il2cpp))
-    } else {
-        bail!("Provide either --apk/--xapk or both --il2cpp and --global-metadata.")
-    }
-}
@@ -15,7 +15,7 @@ use std::hash::Hasher;
 /// - `elf`: A reference to an `Elf` object representing the ELF binary.
 /// - `section_names`: A slice of section names whose data will be incorporated into the hash.
 /// - `section_hash_constant`: A constant value used to initialize the custom hash algorithm,
-///    which can influence the final hash output in a controlled manner.
+///   which can influence the final hash output in a controlled manner.
 ///
 /// # Returns
 /// An `anyhow::Result` containing the computed hash as a `u64` if successful, or an error otherwise.
@@ -44,7 +44,7 @@ fn hash_elf(elf: &Elf, section_names: &[&str], section_hash_constant: u64) -> Re
 /// - `modified_il2cpp`: A mutable reference to an `Elf` object representing the modified ELF binary.
 /// - `segments`: A vector of segment names whose contents are used to compute the hash.
 /// - `section_hash_constant`: A constant value used for the hash computation; the same constant should
-///    be used for both original and modified ELF binaries.
+///   be used for both original and modified ELF binaries.
 ///
 /// # Returns
 /// A `Result` indicating success (`Ok(())`) or an error detailing why the update failed.
@@ -8,11 +8,14 @@ use crate::proto::schema::{ProtoGenSchema, ProtoSchema};
 use crate::proto::service::{ProtoService, ProtoServiceMethod};
 use crate::proto::ProtoType;
 use crate::unity::complex_type::ComplexType;
-use crate::unity::generated::CIl2Cpp::{Il2CppImageDefinition, Il2CppTypeDefinition, TypeIndex};
+use crate::unity::custom_attributes::AttributeTarget;
+use crate::unity::generated::CIl2Cpp::{
+    Il2CppImageDefinition, Il2CppTypeDefinition, TypeDefinitionIndex, TypeIndex,
+};
 use crate::unity::il2cpp::Il2Cpp;
 use anyhow::{anyhow, bail, Result};
 use hashbrown::HashMap;
-use log::debug;
+use log::{debug, warn};
 use phf::phf_map;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -76,6 +79,9 @@ static NET_TO_PROTO: phf::Map<&'static str, &'static str> = phf_map! {
     "ByteString" => "bytes",
 };
 
+/// The attribute generated protobuf code puts on enum values with their name in the proto file.
+const ORIGINAL_NAME_ATTRIBUTE: &str = "Google.Protobuf.Reflection.OriginalNameAttribute";
+
 /// Processes a single IL2CPP image by iterating through its type definitions and generating corresponding
 /// proto definitions.
 ///
@@ -229,11 +235,10 @@ fn get_rpc_streaming_info<'a>(
     let metadata = &il2cpp.metadata;
     let start = client_ty_def.methodStart as usize;
     let end = start + client_ty_def.method_count as usize;
-    let methods = &metadata.methods[start..end];
 
-    for method in methods {
-        if metadata.get_string_by_index(method.nameIndex) == rpc_name {
-            let return_type = &il2cpp.types[method.returnType as usize];
+    for method in metadata.methods.range(start..end) {
+        if metadata.get_string_by_index(method.name_index) == rpc_name {
+            let return_type = &il2cpp.types[method.return_type as usize];
             return match return_type.get_complex_type(il2cpp)? {
                 ComplexType::Generic { ref base, .. } => {
                     let base_name = base.to_string();
@@ -341,11 +346,10 @@ fn process_message<'a>(
         }
     }
 
-    // Cache the method slice associated with this type.
-    let methods_slice = {
+    // Cache the method range associated with this type.
+    let methods_range = {
         let start = ty_def.methodStart as usize;
-        let end = start + ty_def.method_count as usize;
-        &il2cpp.metadata.methods[start..end]
+        start..start + ty_def.method_count as usize
     };
 
     // Process each field that matches the expected naming pattern.
@@ -360,11 +364,13 @@ fn process_message<'a>(
         let getter_name = format!("get_{}", proto_field_name);
 
         // Find the getter method to determine the field's type.
-        if let Some(method) = methods_slice
-            .iter()
-            .find(|m| il2cpp.metadata.get_string_by_index(m.nameIndex) == getter_name)
+        if let Some(method) = il2cpp
+            .metadata
+            .methods
+            .range(methods_range.clone())
+            .find(|m| il2cpp.metadata.get_string_by_index(m.name_index) == getter_name)
         {
-            let return_type = &il2cpp.types[method.returnType as usize];
+            let return_type = &il2cpp.types[method.return_type as usize];
             match return_type.get_complex_type(il2cpp)? {
                 ComplexType::Simple {
                     mut module,
@@ -603,13 +609,54 @@ fn parse_enum_type<'a>(il2cpp: &'a Il2Cpp<'a>, ty_def: &Il2CppTypeDefinition) ->
     let type_name = il2cpp.metadata.get_string_by_index(ty_def.nameIndex);
     let mut new_enum = ProtoEnum::create(&type_name, ty_def.byvalTypeIndex);
 
+    // The declaring type of the fields, to look up their attributes.
+    let declaring_type = unsafe {
+        il2cpp.types[ty_def.byvalTypeIndex as usize]
+            .data
+            .__klassIndex
+    };
+
     // Skip the first field ("__value") and iterate over the remaining fields.
     for j in ty_def.get_field_range().skip(1) {
         let field = &il2cpp.metadata.fields[j];
         let element_name = il2cpp.metadata.get_string_by_index(field.nameIndex);
         let element_value = get_field_default_numeric_value(il2cpp, j as i32)?;
-        new_enum.add_variant(&element_name, element_value);
+        let original_name = get_original_name(il2cpp, declaring_type, j as i32);
+        new_enum.add_variant(&element_name, element_value, original_name);
     }
 
     Ok(new_enum)
 }
+
+/// Retrieves the name of an enum value in the proto file from its `OriginalName` attribute.
+///
+/// Attributes that cannot be decoded are logged and ignored, so the variant name falls back to
+/// the one derived from the .NET name.
+///
+/// # Arguments
+/// * `il2cpp` - Reference to the IL2CPP context.
+/// * `declaring_type` - The index of the enum type definition.
+/// * `field_index` - The index of the enum value field.
+///
+/// # Returns
+/// * `Option<String>` - The original proto name, if the field has an `OriginalName` attribute.
+fn get_original_name<'a>(
+    il2cpp: &'a Il2Cpp<'a>,
+    declaring_type: TypeDefinitionIndex,
+    field_index: i32,
+) -> Option<String> {
+    let target = AttributeTarget::Field {
+        declaring_type,
+        field: field_index,
+    };
+    match il2cpp.find_attribute(target, ORIGINAL_NAME_ATTRIBUTE) {
+        Ok(attribute) => attribute?.string_argument(0).map(str::to_string),
+        Err(err) => {
+            warn!(
+                "Could not decode the attributes of enum field {}: {}",
+                field_index, err
+            );
+            None
+        }
+    }
+}
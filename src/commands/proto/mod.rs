@@ -1,16 +1,13 @@
-use crate::archive::open_archive;
+use crate::commands::input::InputArgs;
 use crate::commands::proto::extractor::generate_proto_schema;
 use crate::commands::tui::commands::IntoTui;
 use crate::commands::tui::utils::{
-    get_checkbox_value, get_optional_path, get_required_path, make_path_input, BrowseType,
+    get_checkbox_value, get_required_path, make_path_input, BrowseType,
 };
 use crate::commands::AppCommand;
 use crate::proto::writer::write_entry_file;
-use crate::unity::unity_loader::load_encrypted_il2cpp;
-use crate::utils::consts::{
-    APK_FILTER, GLOBAL_METADATA_PATH, IL2CPP_FILTER, IL2CPP_PATH, METADATA_FILTER,
-};
-use anyhow::{bail, Result};
+use crate::unity::unity_loader::load_il2cpp;
+use anyhow::Result;
 use clap::Args;
 use cursive::traits::{Nameable, Resizable};
 use cursive::utils::markup::markdown;
@@ -27,22 +24,14 @@ mod extractor;
 /// Command line arguments for the extraction process.
 ///
 /// This struct holds various optional and required file paths used during the extraction:
-/// - `apk`: Optional path to an APK archive.
-/// - `il2cpp`: Optional path to the il2cpp file.
-/// - `global_metadata`: Optional path to the global metadata file.
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
 /// - `output`: Required output directory where the generated protobuf files will be written.
 /// - `overwrite`: Flag to allow overwriting of non-empty output directories.
 #[derive(Args, Default, Clone)]
 pub struct ExtractArgs {
-    /// Path to an APK file.
-    #[clap(long)]
-    pub apk: Option<PathBuf>,
-    /// Path to the il2cpp file.
-    #[clap(long)]
-    pub il2cpp: Option<PathBuf>,
-    /// Path to the global-metadata file.
-    #[clap(long)]
-    pub global_metadata: Option<PathBuf>,
+    /// The IL2CPP binary and global metadata to work on.
+    #[command(flatten)]
+    pub source: InputArgs,
     /// Output directory for protobuf files.
     #[clap(long)]
     pub output: PathBuf,
@@ -54,9 +43,9 @@ pub struct ExtractArgs {
 impl IntoTui for ExtractArgs {
     /// Converts the extraction arguments into a TUI (Text User Interface) dialog using Cursive.
     ///
-    /// This method builds a dialog with two extraction methods: one for an APK/XAPK file,
-    /// and another for IL2CPP and Global Metadata files. It also sets up input fields and a callback
-    /// that validates and processes the user input before invoking the next command.
+    /// This method builds a dialog with two extraction methods: one for an APK/XAPK file or an
+    /// unpacked APK folder, and another for IL2CPP and Global Metadata files. It also sets up input
+    /// fields and a callback that validates and processes the user input before invoking the next command.
     ///
     /// # Arguments
     ///
@@ -74,36 +63,7 @@ impl IntoTui for ExtractArgs {
             .padding_lrtb(1, 1, 1, 0)
             .content(
                 LinearLayout::vertical()
-                    // Instruction text for choosing an extraction method
-                    .child(TextView::new("Choose one extraction method:").center())
-                    .child(DummyView.fixed_height(1))
-                    // Method 1: APK/XAPK extraction
-                    .child(TextView::new(markdown::parse(
-                        "**Method 1: Provide an APK/XAPK file:**",
-                    )))
-                    .child(make_path_input(
-                        "(X)APK File: ",
-                        "apk",
-                        BrowseType::File,
-                        Some(APK_FILTER),
-                    ))
-                    .child(DummyView.fixed_height(1))
-                    // Method 2: IL2CPP and Global Metadata extraction
-                    .child(TextView::new(markdown::parse(
-                        "**Method 2: Provide IL2CPP and Global Metadata files:**",
-                    )))
-                    .child(make_path_input(
-                        "IL2CPP File: ",
-                        "il2cpp",
-                        BrowseType::File,
-                        Some(IL2CPP_FILTER),
-                    ))
-                    .child(make_path_input(
-                        "Global Metadata File: ",
-                        "global_metadata",
-                        BrowseType::File,
-                        Some(METADATA_FILTER),
-                    ))
+                    .child(InputArgs::make_tui_inputs())
                     .child(DummyView.fixed_height(1))
                     // Common fields for extraction output
                     .child(make_path_input(
@@ -126,13 +86,14 @@ impl IntoTui for ExtractArgs {
                     let mut cmd_inner = cmd.lock().take().unwrap();
 
                     // Fill in the arguments from the TUI input fields
-                    cmd_inner.apk = get_optional_path(s, "apk");
-                    cmd_inner.il2cpp = get_optional_path(s, "il2cpp");
-                    cmd_inner.global_metadata = get_optional_path(s, "global_metadata");
                     cmd_inner.output = get_required_path(s, "output");
                     cmd_inner.overwrite = get_checkbox_value(s, "overwrite");
 
-                    if let Err(err) = cmd_inner.validate() {
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
                         cmd.lock().replace(cmd_inner);
                         s.add_layer(
                             Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
@@ -160,8 +121,7 @@ impl IntoTui for ExtractArgs {
     /// Validates the extraction arguments.
     ///
     /// Checks that:
-    /// - Either an APK file is provided, or both IL2CPP and Global Metadata files are given.
-    /// - The provided file paths exist.
+    /// - The input arguments are valid, see [`InputArgs::validate`].
     /// - The output directory is not empty unless the overwrite flag is set, and it is a directory.
     ///
     /// # Returns
@@ -169,30 +129,7 @@ impl IntoTui for ExtractArgs {
     /// * `Ok(())` if all arguments are valid.
     /// * `Err(String)` with an error message if any validation step fails.
     fn validate(&self) -> Result<(), String> {
-        // Validate input file combinations.
-        match (&self.apk, &self.il2cpp, &self.global_metadata) {
-            // Valid: Only APK is provided.
-            (Some(apk_path), None, None) => {
-                if !apk_path.exists() {
-                    return Err("APK file does not exist".into());
-                }
-            }
-            // Valid: Both IL2CPP and global-metadata are provided.
-            (None, Some(il2cpp_path), Some(global_path)) => {
-                if !il2cpp_path.exists() {
-                    return Err("IL2CPP file does not exist".into());
-                }
-                if !global_path.exists() {
-                    return Err("Global metadata file does not exist".into());
-                }
-            }
-            // Any other combination is invalid.
-            _ => {
-                return Err(
-                    "Either provide an APK file or both IL2CPP and global-metadata files".into(),
-                );
-            }
-        }
+        self.source.validate()?;
 
         if self.output.as_os_str().is_empty() {
             return Err("Output directory must not be empty".into());
@@ -226,7 +163,7 @@ impl IntoTui for ExtractArgs {
 /// This function performs the following steps:
 /// 1. Validates the output directory based on the provided path and overwrite flag.
 /// 2. Loads input data (il2cpp and global metadata) from either an XAPK/APK archive or individual files.
-/// 3. Extracts decryption keys from the il2cpp data.
+/// 3. Extracts decryption keys from the il2cpp data, unless the global metadata is already decrypted.
 /// 4. Decrypts the global metadata using the extracted keys.
 /// 5. Generates protobuf schemas from the decrypted global metadata and il2cpp data.
 /// 6. Writes the generated protobuf files (enums, messages, and services) to the output directory,
@@ -244,11 +181,15 @@ pub fn execute(args: ExtractArgs) -> Result<()> {
     info!(progress = 0, max = 7; "");
 
     info!("Loading input data...");
-    let (il2cpp_data, global_metadata_data) = get_input_data(&args)?;
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
     info!(progress_tick = 1; "");
 
-    info!("Decrypting global metadata and loading il2cpp...");
-    let il2cpp = load_encrypted_il2cpp(il2cpp_data, global_metadata_data)?;
+    info!("Loading global metadata and il2cpp...");
+    let il2cpp = load_il2cpp(
+        il2cpp_data,
+        global_metadata_data,
+        args.source.metadata_key(),
+    )?;
     info!(progress_tick = 1; "");
 
     info!("Generating protobuf schemas...");
@@ -309,32 +250,3 @@ pub fn execute(args: ExtractArgs) -> Result<()> {
     info!("Done!");
     Ok(())
 }
-
-/// Retrieves il2cpp and global metadata data from the provided input sources.
-///
-/// Depending on the provided arguments, this function will attempt to:
-/// - Open an XAPK archive and extract files at predefined paths.
-/// - Open an APK archive and extract files at predefined paths.
-/// - Read individual il2cpp and global metadata files from the file system.
-///
-/// # Errors
-///
-/// Returns an error if:
-/// - Neither archive nor individual file paths are provided.
-/// - Reading from the archive or file system fails.
-fn get_input_data(args: &ExtractArgs) -> Result<(Vec<u8>, Vec<u8>)> {
-    if let Some(ref apk_path) = args.apk {
-        let mut archive = open_archive(apk_path)?;
-        let gm_data = archive.read_internal_file(GLOBAL_METADATA_PATH)?;
-        let il2cpp_data = archive.read_internal_file(IL2CPP_PATH)?;
-        Ok((il2cpp_data, gm_data))
-    } else if let (Some(il2cpp_path), Some(global_metadata_path)) =
-        (&args.il2cpp, &args.global_metadata)
-    {
-        let il2cpp_data = fs::read(il2cpp_path)?;
-        let gm_data = fs::read(global_metadata_path)?;
-        Ok((il2cpp_data, gm_data))
-    } else {
-        bail!("Please provide either --apk/--xapk or both --il2cpp and --global-metadata.")
-    }
-}
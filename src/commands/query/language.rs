@@ -0,0 +1,802 @@
+use crate::commands::query::tables::Value;
+use anyhow::{anyhow, bail, Result};
+use globset::{GlobBuilder, GlobMatcher};
+use std::cmp::Ordering;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// A kind of metadata that can be queried, with one row per item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Entity {
+    /// The type definitions.
+    Types,
+    /// The fields of all type definitions.
+    Fields,
+    /// The method definitions.
+    Methods,
+    /// The parameters of all method definitions.
+    Parameters,
+    /// The string literals.
+    Strings,
+}
+
+/// The kind of values a column holds, which decides the comparisons it supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// Text, compared as is or by pattern.
+    Text,
+    /// Integers and addresses, which can also be compared by size.
+    Number,
+    /// `true` or `false`.
+    Bool,
+}
+
+/// A column of the rows of an entity.
+#[derive(Debug, Clone, Copy)]
+pub struct Column {
+    /// The name the column is referred to by in queries and output.
+    pub name: &'static str,
+    /// The kind of values of the column.
+    pub kind: ColumnKind,
+    /// A short description, shown by the `help` command of the REPL.
+    pub description: &'static str,
+}
+
+/// Shorthand for the column tables below.
+const fn column(name: &'static str, kind: ColumnKind, description: &'static str) -> Column {
+    Column {
+        name,
+        kind,
+        description,
+    }
+}
+
+const TYPE_COLUMNS: &[Column] = &[
+    column("index", ColumnKind::Number, "type definition index"),
+    column("name", ColumnKind::Text, "name without the namespace"),
+    column("namespace", ColumnKind::Text, "namespace"),
+    column("full_name", ColumnKind::Text, "fully qualified name"),
+    column("kind", ColumnKind::Text, "class, struct, enum or interface"),
+    column(
+        "image",
+        ColumnKind::Text,
+        "image (assembly) declaring the type",
+    ),
+    column(
+        "parent",
+        ColumnKind::Text,
+        "base type, null if there is none",
+    ),
+    column("fields", ColumnKind::Number, "number of fields"),
+    column("methods", ColumnKind::Number, "number of methods"),
+    column("properties", ColumnKind::Number, "number of properties"),
+    column(
+        "size",
+        ColumnKind::Number,
+        "instance size, null for generic types",
+    ),
+];
+
+const FIELD_COLUMNS: &[Column] = &[
+    column("index", ColumnKind::Number, "field index"),
+    column("name", ColumnKind::Text, "field name"),
+    column("type", ColumnKind::Text, "field type"),
+    column(
+        "declaring_type",
+        ColumnKind::Text,
+        "fully qualified name of the declaring type",
+    ),
+    column("static", ColumnKind::Bool, "whether the field is static"),
+    column("const", ColumnKind::Bool, "whether the field is a constant"),
+    column(
+        "offset",
+        ColumnKind::Number,
+        "offset, null for constants and generic types",
+    ),
+];
+
+const METHOD_COLUMNS: &[Column] = &[
+    column("index", ColumnKind::Number, "method definition index"),
+    column("name", ColumnKind::Text, "method name"),
+    column(
+        "declaring_type",
+        ColumnKind::Text,
+        "fully qualified name of the declaring type",
+    ),
+    column("return_type", ColumnKind::Text, "return type"),
+    column("params", ColumnKind::Number, "number of parameters"),
+    column(
+        "signature",
+        ColumnKind::Text,
+        "parameter list, such as (int count, string name)",
+    ),
+    column("static", ColumnKind::Bool, "whether the method is static"),
+    column("virtual", ColumnKind::Bool, "whether the method is virtual"),
+    column(
+        "abstract",
+        ColumnKind::Bool,
+        "whether the method is abstract",
+    ),
+    column(
+        "generic",
+        ColumnKind::Bool,
+        "whether the method has generic parameters",
+    ),
+    column(
+        "slot",
+        ColumnKind::Number,
+        "vtable slot, null if there is none",
+    ),
+    column(
+        "address",
+        ColumnKind::Number,
+        "code address, null for methods without code",
+    ),
+];
+
+const PARAMETER_COLUMNS: &[Column] = &[
+    column("index", ColumnKind::Number, "parameter index"),
+    column(
+        "method_index",
+        ColumnKind::Number,
+        "method definition index",
+    ),
+    column("method", ColumnKind::Text, "method name"),
+    column(
+        "declaring_type",
+        ColumnKind::Text,
+        "fully qualified name of the method's type",
+    ),
+    column(
+        "position",
+        ColumnKind::Number,
+        "position in the parameter list, from 0",
+    ),
+    column("name", ColumnKind::Text, "parameter name"),
+    column("type", ColumnKind::Text, "parameter type"),
+];
+
+const STRING_COLUMNS: &[Column] = &[
+    column("index", ColumnKind::Number, "string literal index"),
+    column("value", ColumnKind::Text, "string value"),
+    column("length", ColumnKind::Number, "number of characters"),
+    column(
+        "usage",
+        ColumnKind::Number,
+        "address of the StringLiteral_N variable, if found",
+    ),
+];
+
+impl Entity {
+    /// Returns the name the entity is referred to by in queries.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Entity::Types => "types",
+            Entity::Fields => "fields",
+            Entity::Methods => "methods",
+            Entity::Parameters => "parameters",
+            Entity::Strings => "strings",
+        }
+    }
+
+    /// Returns the columns of the rows of the entity, in the order of the row values.
+    pub fn columns(&self) -> &'static [Column] {
+        match self {
+            Entity::Types => TYPE_COLUMNS,
+            Entity::Fields => FIELD_COLUMNS,
+            Entity::Methods => METHOD_COLUMNS,
+            Entity::Parameters => PARAMETER_COLUMNS,
+            Entity::Strings => STRING_COLUMNS,
+        }
+    }
+
+    /// Returns the index of the column with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing the columns of the entity if there is no such column.
+    fn column_index(&self, name: &str) -> Result<usize> {
+        self.columns()
+            .iter()
+            .position(|column| column.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let names: Vec<&str> = self.columns().iter().map(|column| column.name).collect();
+                anyhow!(
+                    "{} have no column \"{}\", expected one of: {}",
+                    self.name(),
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// A parsed query, see [`Query::parse`].
+#[derive(Debug)]
+pub struct Query {
+    /// The kind of metadata to list.
+    pub entity: Entity,
+    /// The condition rows must meet, or `None` to list all rows.
+    pub filter: Option<Filter>,
+    /// The column to sort by and whether to sort in descending order, or `None` to keep the
+    /// metadata order.
+    pub order: Option<(usize, bool)>,
+    /// The indices of the columns to output, in output order.
+    pub columns: Vec<usize>,
+    /// The maximum number of rows to output.
+    pub limit: Option<usize>,
+}
+
+/// A condition on the values of a row.
+#[derive(Debug)]
+pub enum Filter {
+    /// Compares the value of a column with a literal.
+    Compare {
+        /// The index of the column.
+        column: usize,
+        /// The comparison.
+        operator: Operator,
+        /// The literal to compare with.
+        literal: Literal,
+    },
+    /// Met if the inner condition is not.
+    Not(Box<Filter>),
+    /// Met if both conditions are.
+    And(Box<Filter>, Box<Filter>),
+    /// Met if either condition is.
+    Or(Box<Filter>, Box<Filter>),
+}
+
+/// A comparison of a column value with a literal.
+#[derive(Debug)]
+pub enum Operator {
+    /// `=`: the value equals the literal.
+    Eq,
+    /// `!=`: the value differs from the literal.
+    Ne,
+    /// `~`: the value contains the literal, ignoring case.
+    Contains,
+    /// `!~`: the value does not contain the literal, ignoring case.
+    NotContains,
+    /// `^=`: the value starts with the literal, ignoring case.
+    StartsWith,
+    /// `$=`: the value ends with the literal, ignoring case.
+    EndsWith,
+    /// `like`: the value matches a glob pattern with `*` and `?`, ignoring case.
+    Like(GlobMatcher),
+    /// `<`: the value is less than the literal.
+    Lt,
+    /// `<=`: the value is less than or equal to the literal.
+    Le,
+    /// `>`: the value is greater than the literal.
+    Gt,
+    /// `>=`: the value is greater than or equal to the literal.
+    Ge,
+}
+
+/// A literal value in a query.
+#[derive(Debug, Clone)]
+pub enum Literal {
+    /// `null`, which only equals missing values.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A decimal or `0x`-prefixed hexadecimal integer, with its text.
+    Number(i128, String),
+    /// A quoted string or a bare word.
+    Text(String),
+}
+
+impl Literal {
+    /// Returns the text of the literal, as compared with text values.
+    fn text(&self) -> &str {
+        match self {
+            Literal::Null => "null",
+            Literal::Bool(true) => "true",
+            Literal::Bool(false) => "false",
+            Literal::Number(_, text) | Literal::Text(text) => text,
+        }
+    }
+}
+
+/// A token of a query.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A keyword, column name, entity name or unquoted value.
+    Word(String),
+    /// A quoted string, without the quotes and with escapes resolved.
+    Quoted(String),
+    /// A comparison operator.
+    Operator(&'static str),
+    /// `(`
+    OpenParen,
+    /// `)`
+    CloseParen,
+    /// `,`
+    Comma,
+}
+
+/// The comparison operators, longest first so that `<=` is not read as `<`.
+const OPERATORS: &[&str] = &["!=", "!~", "^=", "$=", "<=", ">=", "=", "~", "<", ">"];
+
+/// Splits a query into tokens, each with the character position it starts at.
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        let c = chars[position];
+        let start = position;
+        if c.is_whitespace() {
+            position += 1;
+            continue;
+        }
+        let token = match c {
+            '(' => {
+                position += 1;
+                Token::OpenParen
+            }
+            ')' => {
+                position += 1;
+                Token::CloseParen
+            }
+            ',' => {
+                position += 1;
+                Token::Comma
+            }
+            '"' | '\'' => {
+                let mut value = String::new();
+                position += 1;
+                loop {
+                    match chars.get(position) {
+                        None => bail!("Unterminated string starting at position {}", start + 1),
+                        Some(&end) if end == c => break,
+                        Some('\\') => {
+                            let escaped = chars.get(position + 1).ok_or_else(|| {
+                                anyhow!("Unterminated string starting at position {}", start + 1)
+                            })?;
+                            value.push(match escaped {
+                                'n' => '\n',
+                                'r' => '\r',
+                                't' => '\t',
+                                '0' => '\0',
+                                other => *other,
+                            });
+                            position += 2;
+                        }
+                        Some(&other) => {
+                            value.push(other);
+                            position += 1;
+                        }
+                    }
+                }
+                position += 1;
+                Token::Quoted(value)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' => {
+                while position < chars.len()
+                    && (chars[position].is_alphanumeric() || "_-.".contains(chars[position]))
+                {
+                    position += 1;
+                }
+                Token::Word(chars[start..position].iter().collect())
+            }
+            _ => {
+                let rest: String = chars[position..].iter().take(2).collect();
+                let operator = OPERATORS
+                    .iter()
+                    .find(|operator| rest.starts_with(**operator))
+                    .ok_or_else(|| {
+                        anyhow!("Unexpected character '{}' at position {}", c, start + 1)
+                    })?;
+                position += operator.chars().count();
+                Token::Operator(operator)
+            }
+        };
+        tokens.push((token, start));
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent parser over the tokens of a query.
+struct Parser {
+    /// The tokens with their positions.
+    tokens: Vec<(Token, usize)>,
+    /// The index of the next token.
+    next: usize,
+    /// The character count of the query, reported as the position of a missing token.
+    end: usize,
+}
+
+impl Parser {
+    /// Returns the next token without consuming it.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(token, _)| token)
+    }
+
+    /// Returns the position of the next token, from 1, for error messages.
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .map_or(self.end, |(_, position)| *position)
+            + 1
+    }
+
+    /// Consumes and returns the next token.
+    fn take(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).map(|(token, _)| token.clone());
+        self.next += 1;
+        token
+    }
+
+    /// Consumes the next token if it is the given keyword, ignoring case.
+    fn take_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.next += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Consumes the given keyword, or fails if the next token is something else.
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if !self.take_keyword(keyword) {
+            bail!("Expected \"{}\" at position {}", keyword, self.position());
+        }
+        Ok(())
+    }
+
+    /// Consumes a column name of the entity and returns its index.
+    fn column(&mut self, entity: Entity) -> Result<usize> {
+        let position = self.position();
+        match self.take() {
+            Some(Token::Word(name)) => entity.column_index(&name),
+            _ => bail!("Expected a column name at position {}", position),
+        }
+    }
+
+    /// Parses `<filter> := <and> ("or" <and>)*`.
+    fn filter(&mut self, entity: Entity) -> Result<Filter> {
+        let mut filter = self.and(entity)?;
+        while self.take_keyword("or") {
+            filter = Filter::Or(Box::new(filter), Box::new(self.and(entity)?));
+        }
+        Ok(filter)
+    }
+
+    /// Parses `<and> := <unary> ("and" <unary>)*`.
+    fn and(&mut self, entity: Entity) -> Result<Filter> {
+        let mut filter = self.unary(entity)?;
+        while self.take_keyword("and") {
+            filter = Filter::And(Box::new(filter), Box::new(self.unary(entity)?));
+        }
+        Ok(filter)
+    }
+
+    /// Parses `<unary> := "not" <unary> | "(" <filter> ")" | <comparison>`.
+    fn unary(&mut self, entity: Entity) -> Result<Filter> {
+        if self.take_keyword("not") {
+            return Ok(Filter::Not(Box::new(self.unary(entity)?)));
+        }
+        if self.peek() == Some(&Token::OpenParen) {
+            self.next += 1;
+            let filter = self.filter(entity)?;
+            if self.take() != Some(Token::CloseParen) {
+                bail!("Expected \")\" at position {}", self.position() - 1);
+            }
+            return Ok(filter);
+        }
+        self.comparison(entity)
+    }
+
+    /// Parses `<comparison> := <column> <operator> <literal> | <column>` and checks that the
+    /// column supports the comparison. A boolean column on its own is short for
+    /// `<column> = true`.
+    fn comparison(&mut self, entity: Entity) -> Result<Filter> {
+        let column = self.column(entity)?;
+        let definition = entity.columns()[column];
+
+        let has_operator = match self.peek() {
+            Some(Token::Operator(_)) => true,
+            Some(Token::Word(word)) => word.eq_ignore_ascii_case("like"),
+            _ => false,
+        };
+        if !has_operator && definition.kind == ColumnKind::Bool {
+            return Ok(Filter::Compare {
+                column,
+                operator: Operator::Eq,
+                literal: Literal::Bool(true),
+            });
+        }
+
+        let position = self.position();
+        let operator = match self.take() {
+            Some(Token::Operator(operator)) => operator,
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("like") => "like",
+            _ => bail!("Expected a comparison operator at position {}", position),
+        };
+
+        let position = self.position();
+        let literal = match self.take() {
+            Some(Token::Quoted(text)) => Literal::Text(text),
+            Some(Token::Word(word)) => parse_word(word),
+            _ => bail!("Expected a value at position {}", position),
+        };
+
+        let operator = match operator {
+            "=" => Operator::Eq,
+            "!=" => Operator::Ne,
+            "~" => Operator::Contains,
+            "!~" => Operator::NotContains,
+            "^=" => Operator::StartsWith,
+            "$=" => Operator::EndsWith,
+            "like" => {
+                let matcher = GlobBuilder::new(literal.text())
+                    .case_insensitive(true)
+                    .literal_separator(false)
+                    .build()
+                    .map_err(|err| anyhow!("Invalid pattern at position {}: {}", position, err))?
+                    .compile_matcher();
+                Operator::Like(matcher)
+            }
+            "<" => Operator::Lt,
+            "<=" => Operator::Le,
+            ">" => Operator::Gt,
+            _ => Operator::Ge,
+        };
+
+        // Equality with a number or boolean column needs a literal of the same kind, and size
+        // comparisons need numbers on both sides.
+        let is_ordering = matches!(
+            operator,
+            Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge
+        );
+        if is_ordering && definition.kind != ColumnKind::Number {
+            bail!("Column {} is not numeric", definition.name);
+        }
+        if matches!(operator, Operator::Eq | Operator::Ne) || is_ordering {
+            match (definition.kind, &literal) {
+                (_, Literal::Null) if !is_ordering => {}
+                (ColumnKind::Number, Literal::Number(..)) => {}
+                (ColumnKind::Bool, Literal::Bool(_)) => {}
+                (ColumnKind::Text, Literal::Text(_) | Literal::Number(..) | Literal::Bool(_)) => {}
+                (ColumnKind::Number, _) => bail!(
+                    "Column {} is numeric, but \"{}\" is not a number",
+                    definition.name,
+                    literal.text()
+                ),
+                (ColumnKind::Bool, _) => bail!(
+                    "Column {} is true or false, but \"{}\" is neither",
+                    definition.name,
+                    literal.text()
+                ),
+                (ColumnKind::Text, _) => bail!(
+                    "Column {} cannot be compared with null using {}",
+                    definition.name,
+                    operator_text(&operator)
+                ),
+            }
+        }
+
+        Ok(Filter::Compare {
+            column,
+            operator,
+            literal,
+        })
+    }
+}
+
+/// Returns the text of an operator for error messages.
+fn operator_text(operator: &Operator) -> &'static str {
+    match operator {
+        Operator::Eq => "=",
+        Operator::Ne => "!=",
+        Operator::Contains => "~",
+        Operator::NotContains => "!~",
+        Operator::StartsWith => "^=",
+        Operator::EndsWith => "$=",
+        Operator::Like(_) => "like",
+        Operator::Lt => "<",
+        Operator::Le => "<=",
+        Operator::Gt => ">",
+        Operator::Ge => ">=",
+    }
+}
+
+/// Interprets an unquoted value as `null`, a boolean, a number or text.
+fn parse_word(word: String) -> Literal {
+    if word.eq_ignore_ascii_case("null") {
+        return Literal::Null;
+    }
+    if word.eq_ignore_ascii_case("true") {
+        return Literal::Bool(true);
+    }
+    if word.eq_ignore_ascii_case("false") {
+        return Literal::Bool(false);
+    }
+    let (negative, digits) = match word.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, word.as_str()),
+    };
+    let number = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => i128::from_str_radix(hex, 16),
+        None => digits.parse(),
+    };
+    match number {
+        Ok(number) => Literal::Number(if negative { -number } else { number }, word),
+        Err(_) => Literal::Text(word),
+    }
+}
+
+impl Query {
+    /// Parses a query of the form
+    ///
+    /// ```text
+    /// <entity> [where <filter>] [order by <column> [asc|desc]] [select <column>, ...] [limit <n>]
+    /// ```
+    ///
+    /// where the entity is one of `types`, `fields`, `methods`, `parameters` and `strings`, and the
+    /// filter combines comparisons such as `name = Foo`, `return_type like "Task<*>"` or
+    /// `address > 0x1000` with `and`, `or`, `not` and parentheses. Keywords and column names
+    /// ignore case. Values are quoted strings, numbers, `true`, `false`, `null` or bare words.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the position of the problem if the query is malformed, refers to an
+    /// unknown column, or compares a column with a value of the wrong kind.
+    pub fn parse(text: &str) -> Result<Query> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            next: 0,
+            end: text.chars().count(),
+        };
+
+        let entity = match parser.take() {
+            Some(Token::Word(name)) => Entity::iter()
+                .find(|entity| entity.name().eq_ignore_ascii_case(&name))
+                .ok_or_else(|| {
+                    let names: Vec<&str> = Entity::iter().map(|entity| entity.name()).collect();
+                    anyhow!(
+                        "Unknown entity \"{}\", expected one of: {}",
+                        name,
+                        names.join(", ")
+                    )
+                })?,
+            _ => bail!("A query must start with what to list, such as \"types\""),
+        };
+
+        let mut query = Query {
+            entity,
+            filter: None,
+            order: None,
+            columns: (0..entity.columns().len()).collect(),
+            limit: None,
+        };
+        let mut seen = Vec::new();
+        while parser.peek().is_some() {
+            let position = parser.position();
+            let clause = match parser.take() {
+                Some(Token::Word(word)) => word.to_ascii_lowercase(),
+                _ => bail!("Expected a clause at position {}", position),
+            };
+            if seen.contains(&clause) {
+                bail!("Duplicate \"{}\" clause at position {}", clause, position);
+            }
+            match clause.as_str() {
+                "where" => query.filter = Some(parser.filter(entity)?),
+                "order" => {
+                    parser.expect_keyword("by")?;
+                    let column = parser.column(entity)?;
+                    let descending = if parser.take_keyword("desc") {
+                        true
+                    } else {
+                        parser.take_keyword("asc");
+                        false
+                    };
+                    query.order = Some((column, descending));
+                }
+                "select" => {
+                    query.columns = vec![parser.column(entity)?];
+                    while parser.peek() == Some(&Token::Comma) {
+                        parser.next += 1;
+                        query.columns.push(parser.column(entity)?);
+                    }
+                }
+                "limit" => {
+                    let position = parser.position();
+                    query.limit = match parser.take() {
+                        Some(Token::Word(word)) => word.parse().ok(),
+                        _ => None,
+                    };
+                    if query.limit.is_none() {
+                        bail!("Expected a row count at position {}", position);
+                    }
+                }
+                _ => bail!(
+                    "Expected \"where\", \"order by\", \"select\" or \"limit\" at position {}",
+                    position
+                ),
+            }
+            seen.push(clause);
+        }
+        Ok(query)
+    }
+}
+
+impl Filter {
+    /// Checks whether a row meets the condition.
+    pub fn matches(&self, row: &[Value]) -> bool {
+        match self {
+            Filter::Compare {
+                column,
+                operator,
+                literal,
+            } => compare(&row[*column], operator, literal),
+            Filter::Not(filter) => !filter.matches(row),
+            Filter::And(left, right) => left.matches(row) && right.matches(row),
+            Filter::Or(left, right) => left.matches(row) || right.matches(row),
+        }
+    }
+}
+
+/// Compares a value with a literal. Missing values only equal `null`.
+fn compare(value: &Value, operator: &Operator, literal: &Literal) -> bool {
+    if let Literal::Null = literal {
+        return match operator {
+            Operator::Eq => *value == Value::Null,
+            Operator::Ne => *value != Value::Null,
+            _ => false,
+        };
+    }
+    if *value == Value::Null {
+        return matches!(operator, Operator::Ne | Operator::NotContains);
+    }
+
+    let text = || value.to_string().to_lowercase();
+    let pattern = || literal.text().to_lowercase();
+    match operator {
+        Operator::Eq => equals(value, literal),
+        Operator::Ne => !equals(value, literal),
+        Operator::Contains => text().contains(&pattern()),
+        Operator::NotContains => !text().contains(&pattern()),
+        Operator::StartsWith => text().starts_with(&pattern()),
+        Operator::EndsWith => text().ends_with(&pattern()),
+        Operator::Like(matcher) => matcher.is_match(value.to_string()),
+        Operator::Lt => order(value, literal) == Some(Ordering::Less),
+        Operator::Le => matches!(
+            order(value, literal),
+            Some(Ordering::Less | Ordering::Equal)
+        ),
+        Operator::Gt => order(value, literal) == Some(Ordering::Greater),
+        Operator::Ge => matches!(
+            order(value, literal),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+    }
+}
+
+/// Checks whether a value equals a literal: numbers and booleans by value, text exactly.
+fn equals(value: &Value, literal: &Literal) -> bool {
+    match (value, literal) {
+        (Value::Bool(value), Literal::Bool(literal)) => value == literal,
+        (Value::Text(value), literal) => value == literal.text(),
+        _ => order(value, literal) == Some(Ordering::Equal),
+    }
+}
+
+/// Orders a numeric value relative to a numeric literal.
+fn order(value: &Value, literal: &Literal) -> Option<Ordering> {
+    let Literal::Number(literal, _) = literal else {
+        return None;
+    };
+    let value = match value {
+        Value::Int(value) => *value as i128,
+        Value::Address(value) => *value as i128,
+        _ => return None,
+    };
+    Some(value.cmp(literal))
+}
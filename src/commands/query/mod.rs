@@ -0,0 +1,323 @@
+use crate::commands::input::InputArgs;
+use crate::commands::query::language::{Entity, Query};
+use crate::commands::query::output::{format_result, OutputFormat};
+use crate::commands::query::tables::Tables;
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{
+    get_checkbox_value, get_optional_path, get_text_value, make_path_input, make_text_input,
+    BrowseType,
+};
+use crate::commands::AppCommand;
+use crate::unity::unity_loader::load_il2cpp;
+use anyhow::Result;
+use clap::{ArgGroup, Args, ValueEnum};
+use cursive::traits::{Nameable, Resizable};
+use cursive::utils::markup::markdown;
+use cursive::views::{Checkbox, Dialog, DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use log::{error, info};
+use parking_lot::Mutex;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+
+mod language;
+mod output;
+mod tables;
+
+/// Command line arguments for the metadata query command.
+///
+/// This struct holds the input files, the query and the output options:
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
+/// - `query`: Query to run once, see [`Query::parse`] for the syntax.
+/// - `repl`: Flag to read queries from the standard input instead.
+/// - `format`: Whether to print results as a table or as JSON.
+/// - `output`: Optional path to also write the result of the query to.
+#[derive(Args, Default, Clone)]
+#[clap(group(ArgGroup::new("mode").required(true).args(["query", "repl"])))]
+pub struct QueryArgs {
+    /// The IL2CPP binary and global metadata to work on.
+    #[command(flatten)]
+    pub source: InputArgs,
+    /// Query to run once, such as `methods where return_type like "Task<*>" limit 10`.
+    #[clap(long)]
+    pub query: Option<String>,
+    /// Read queries from the standard input, one per line, until it ends or `exit` is entered.
+    #[clap(long)]
+    pub repl: bool,
+    /// How to print query results.
+    #[clap(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+    /// Path to also write the result of the query to.
+    #[clap(long, conflicts_with = "repl")]
+    pub output: Option<PathBuf>,
+}
+
+impl IntoTui for QueryArgs {
+    /// Converts the query arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with two input methods: one for an APK/XAPK file or an
+    /// unpacked APK folder, and another for IL2CPP and Global Metadata files, followed by the
+    /// query, the output format and the optional output file. The REPL is only available on
+    /// the command line. It sets up a callback that validates and processes the user input before
+    /// invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the query arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Query Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(InputArgs::make_tui_inputs())
+                    .child(DummyView.fixed_height(1))
+                    // Query to run and how to print its result
+                    .child(make_text_input("Query (*): ", "query"))
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(TextView::new("Print JSON: "))
+                            .child(Checkbox::new().with_checked(false).with_name("json")),
+                    )
+                    .child(make_path_input(
+                        "Output File: ",
+                        "output",
+                        BrowseType::SaveFile,
+                        None,
+                    )),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our QueryArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    let query = get_text_value(s, "query").trim().to_string();
+                    cmd_inner.query = (!query.is_empty()).then_some(query);
+                    cmd_inner.format = if get_checkbox_value(s, "json") {
+                        OutputFormat::Json
+                    } else {
+                        OutputFormat::Table
+                    };
+                    cmd_inner.output = get_optional_path(s, "output");
+
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::Query(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the query arguments.
+    ///
+    /// Checks that:
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - Either a valid query is provided or the REPL is requested, but not both.
+    /// - An output file, if provided, is only used with a query, is not a directory and differs
+    ///   from the input files.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        self.source.validate()?;
+
+        match (&self.query, self.repl) {
+            (Some(query), false) => {
+                Query::parse(query).map_err(|err| format!("Invalid query: {}", err))?;
+            }
+            (None, true) => {}
+            _ => return Err("Either provide a query or start the REPL".into()),
+        }
+
+        if let Some(output) = &self.output {
+            if self.repl {
+                return Err("Output file can only be used with a query".into());
+            }
+            if output.is_dir() {
+                return Err("Output file is a directory".into());
+            }
+            if self.source.paths().any(|input| input == output) {
+                return Err("Output file must differ from the input files".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the metadata query command.
+///
+/// This function performs the following steps:
+/// 1. Loads input data (il2cpp and global metadata) from either an archive or individual files.
+/// 2. Decrypts the global metadata, unless it is already decrypted, and loads the il2cpp binary.
+/// 3. Runs the query and prints the result, writing it to the output file if one is given, or
+///    reads queries from the standard input until it ends or `exit` is entered. The loaded
+///    metadata and the rows built for earlier queries are reused, so later queries only filter
+///    them.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - Missing or unreadable input files.
+/// - Failure to decrypt the global metadata or load the il2cpp binary.
+/// - A malformed query, or metadata that cannot be resolved.
+/// - File I/O errors while writing the output file or reading the standard input.
+pub fn execute(args: QueryArgs) -> Result<()> {
+    info!("Running query command...");
+    info!(progress = 0, max = 3; "");
+
+    info!("Loading input data...");
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    info!(progress_tick = 1; "");
+
+    info!("Loading global metadata and il2cpp...");
+    let il2cpp = load_il2cpp(
+        il2cpp_data,
+        global_metadata_data,
+        args.source.metadata_key(),
+    )?;
+    let mut tables = Tables::new(&il2cpp);
+    info!(progress_tick = 1; "");
+
+    match &args.query {
+        Some(query) => {
+            info!("Running query...");
+            let output = run_query(&mut tables, query, args.format)?;
+            info!("{}", output);
+            if let Some(path) = &args.output {
+                fs::write(path, output + "\n")?;
+                info!("Wrote the result to {}", path.display());
+            }
+        }
+        None => run_repl(&mut tables, args.format)?,
+    }
+    info!(progress_tick = 1; "");
+
+    info!("Done!");
+    Ok(())
+}
+
+/// Parses and runs a query, and formats its result.
+fn run_query(tables: &mut Tables, text: &str, format: OutputFormat) -> Result<String> {
+    let query = Query::parse(text)?;
+    let result = tables.run(&query)?;
+    format_result(&result, format)
+}
+
+/// Reads queries from the standard input, one per line, and prints their results until the
+/// input ends or `exit` is entered.
+///
+/// Besides queries, `help` lists the syntax and the columns of each entity, and `format table`
+/// or `format json` changes how results are printed. Errors in a query are printed and do not
+/// end the loop.
+fn run_repl(tables: &mut Tables, mut format: OutputFormat) -> Result<()> {
+    info!("Enter a query, \"help\" for the syntax and columns, or \"exit\" to quit.");
+    let stdin = io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("query> ");
+        io::stdout().flush()?;
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            println!();
+            break;
+        }
+
+        let command = line.trim();
+        let mut words = command.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (None, ..) => continue,
+            (Some(word), None, _)
+                if word.eq_ignore_ascii_case("exit") || word.eq_ignore_ascii_case("quit") =>
+            {
+                break;
+            }
+            (Some(word), None, _) if word.eq_ignore_ascii_case("help") => {
+                for help_line in help_lines() {
+                    info!("{}", help_line);
+                }
+            }
+            (Some(word), Some(name), None) if word.eq_ignore_ascii_case("format") => {
+                match OutputFormat::from_str(name, true) {
+                    Ok(new_format) => {
+                        format = new_format;
+                        info!("Printing results as {}", name.to_ascii_lowercase());
+                    }
+                    Err(_) => error!("Unknown format \"{}\", expected table or json", name),
+                }
+            }
+            _ => match run_query(tables, command, format) {
+                Ok(output) => info!("{}", output),
+                Err(err) => error!("Error: {}", err),
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Returns the lines printed by the `help` command of the REPL: the query syntax, the
+/// operators and the columns of each entity.
+fn help_lines() -> Vec<String> {
+    let mut lines = vec![
+        "<entity> [where <filter>] [order by <column> [asc|desc]] [select <column>, ...] [limit <n>]"
+            .to_string(),
+        String::new(),
+        "Filters compare a column with a value and combine with and, or, not and parentheses."
+            .to_string(),
+        "Values are quoted strings, numbers (decimal or 0x...), true, false, null or bare words."
+            .to_string(),
+        "  =, !=           equal, not equal".to_string(),
+        "  ~, !~           contains, does not contain (ignoring case)".to_string(),
+        "  ^=, $=          starts with, ends with (ignoring case)".to_string(),
+        "  like            matches a pattern with * and ? (ignoring case)".to_string(),
+        "  <, <=, >, >=    compares numbers".to_string(),
+        "A true or false column on its own, such as static, is short for static = true."
+            .to_string(),
+        String::new(),
+        "Other commands: format table, format json, help, exit".to_string(),
+    ];
+    for entity in Entity::iter() {
+        lines.push(String::new());
+        lines.push(format!("{}:", entity.name()));
+        for column in entity.columns() {
+            lines.push(format!("  {:<16}{}", column.name, column.description));
+        }
+    }
+    lines
+}
@@ -0,0 +1,117 @@
+use crate::commands::query::tables::{QueryResult, Value};
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+
+/// How query results are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// An aligned text table with a header line.
+    #[default]
+    Table,
+    /// A JSON array with one object per row, keyed by column name.
+    Json,
+}
+
+/// A query result as a JSON array of row objects, with the keys in column order.
+struct JsonRows<'a>(&'a QueryResult);
+
+/// A row as a JSON object, with the keys in column order.
+struct JsonRow<'a>(&'a [&'static str], &'a [Value]);
+
+impl Serialize for JsonRows<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.rows.len()))?;
+        for row in &self.0.rows {
+            seq.serialize_element(&JsonRow(&self.0.columns, row))?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (column, value) in self.0.iter().zip(self.1) {
+            map.serialize_entry(column, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Formats a query result in the given format.
+///
+/// Tables have a header line, a separator line and one line per row, with the columns padded
+/// to their widest value, followed by the row count. Line breaks and tabs in values are escaped
+/// so that every row fits on one line.
+///
+/// # Errors
+///
+/// Returns an error if the result cannot be serialized to JSON.
+pub fn format_result(result: &QueryResult, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&JsonRows(result))?),
+        OutputFormat::Table => Ok(format_table(result)),
+    }
+}
+
+/// Formats a query result as an aligned text table.
+fn format_table(result: &QueryResult) -> String {
+    let cells: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .map(|row| row.iter().map(format_cell).collect())
+        .collect();
+    let mut widths: Vec<usize> = result
+        .columns
+        .iter()
+        .map(|column| column.chars().count())
+        .collect();
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut lines = Vec::with_capacity(cells.len() + 3);
+    let header: Vec<String> = result.columns.iter().map(|c| c.to_string()).collect();
+    lines.push(format_line(&header, &widths));
+    let separator: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+    lines.push(format_line(&separator, &widths));
+    for row in &cells {
+        lines.push(format_line(row, &widths));
+    }
+    lines.push(match cells.len() {
+        1 => "1 row".to_string(),
+        count => format!("{} rows", count),
+    });
+    lines.join("\n")
+}
+
+/// Formats the cells of a table line, padding all but the last cell to the column width.
+fn format_line(cells: &[String], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (index, (cell, width)) in cells.iter().zip(widths).enumerate() {
+        if index + 1 == cells.len() {
+            line.push_str(cell);
+        } else {
+            line.push_str(&format!("{:<width$}  ", cell, width = width));
+        }
+    }
+    line
+}
+
+/// Formats a value for a table cell, with missing values as `-` and line breaks, tabs and
+/// backslashes in text escaped.
+fn format_cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::Text(text) => text
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+            .replace('\t', "\\t"),
+        value => value.to_string(),
+    }
+}
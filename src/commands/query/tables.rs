@@ -0,0 +1,374 @@
+use crate::commands::query::language::{Entity, Query};
+use crate::unity::field_layout::{FieldOffset, FIELD_ATTRIBUTE_LITERAL, FIELD_ATTRIBUTE_STATIC};
+use crate::unity::generated::CIl2Cpp::TypeDefinitionIndex;
+use crate::unity::il2cpp::Il2Cpp;
+use crate::unity::metadata_usages::MetadataUsageKind;
+use anyhow::Result;
+use hashbrown::HashMap;
+use log::debug;
+use nohash_hasher::IntMap;
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Vtable slot of methods that have no slot.
+const NO_SLOT: u16 = 0xFFFF;
+
+/// A value in a row of a query result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A missing value, such as the base type of `object`.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// An index, count, size or offset.
+    Int(i64),
+    /// A virtual address, shown in hexadecimal.
+    Address(u64),
+    /// A name or string.
+    Text(String),
+}
+
+impl Value {
+    /// Orders values for sorting: missing values first, then numbers by value, booleans and
+    /// text.
+    pub fn sort_order(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Null, _) => Ordering::Less,
+            (_, Value::Null) => Ordering::Greater,
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::Address(a), Value::Address(b)) => a.cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Text(a), Value::Text(b)) => a.cmp(b),
+            _ => self.to_string().cmp(&other.to_string()),
+        }
+    }
+}
+
+impl Display for Value {
+    /// Formats the value as shown in tables and matched by text comparisons.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Address(value) => write!(f, "0x{:X}", value),
+            Value::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Serialize for Value {
+    /// Serializes the value as the JSON value of the same kind, with addresses as numbers.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_none(),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Int(value) => serializer.serialize_i64(*value),
+            Value::Address(value) => serializer.serialize_u64(*value),
+            Value::Text(value) => serializer.serialize_str(value),
+        }
+    }
+}
+
+/// The rows of a query result, with the names of their columns.
+pub struct QueryResult {
+    /// The column names, in the order of the row values.
+    pub columns: Vec<&'static str>,
+    /// The matching rows.
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// The rows of each entity of an IL2CPP binary and its metadata.
+///
+/// The rows of an entity are built when it is first queried and kept, so that later queries,
+/// such as those of the REPL, only filter them.
+pub struct Tables<'a> {
+    /// The IL2CPP context.
+    il2cpp: &'a Il2Cpp<'a>,
+    /// The rows built so far, by entity.
+    rows: HashMap<Entity, Vec<Vec<Value>>>,
+}
+
+impl<'a> Tables<'a> {
+    /// Creates the tables of an IL2CPP context, without building any rows yet.
+    pub fn new(il2cpp: &'a Il2Cpp<'a>) -> Self {
+        Tables {
+            il2cpp,
+            rows: HashMap::new(),
+        }
+    }
+
+    /// Runs a query: filters the rows of its entity, sorts them, applies the limit and keeps the
+    /// selected columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rows of the entity cannot be built.
+    pub fn run(&mut self, query: &Query) -> Result<QueryResult> {
+        let rows = self.rows(query.entity)?;
+        let mut matching: Vec<&Vec<Value>> = rows
+            .iter()
+            .filter(|row| {
+                query
+                    .filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.matches(row))
+            })
+            .collect();
+        if let Some((column, descending)) = query.order {
+            matching.sort_by(|a, b| {
+                let ordering = a[column].sort_order(&b[column]);
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+        if let Some(limit) = query.limit {
+            matching.truncate(limit);
+        }
+
+        let columns = query.entity.columns();
+        Ok(QueryResult {
+            columns: query
+                .columns
+                .iter()
+                .map(|&index| columns[index].name)
+                .collect(),
+            rows: matching
+                .into_iter()
+                .map(|row| {
+                    query
+                        .columns
+                        .iter()
+                        .map(|&index| row[index].clone())
+                        .collect()
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the rows of an entity, building them on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a type, member or string literal cannot be resolved.
+    fn rows(&mut self, entity: Entity) -> Result<&[Vec<Value>]> {
+        if !self.rows.contains_key(&entity) {
+            debug!("Building the {} table...", entity.name());
+            let rows = match entity {
+                Entity::Types => self.type_rows()?,
+                Entity::Fields => self.field_rows()?,
+                Entity::Methods => self.method_rows()?,
+                Entity::Parameters => self.parameter_rows()?,
+                Entity::Strings => self.string_rows()?,
+            };
+            self.rows.insert(entity, rows);
+        }
+        Ok(&self.rows[&entity])
+    }
+
+    /// Builds one row per type definition, see [`Entity::columns`] for the values.
+    fn type_rows(&self) -> Result<Vec<Vec<Value>>> {
+        let il2cpp = self.il2cpp;
+        let metadata = &il2cpp.metadata;
+        let mut image_names = vec![String::new(); metadata.type_definitions.len()];
+        for image in &metadata.images {
+            let name = metadata.get_string_by_index(image.nameIndex);
+            let start = image.typeStart as usize;
+            for type_index in start..start + image.typeCount as usize {
+                if let Some(image_name) = image_names.get_mut(type_index) {
+                    image_name.clone_from(&name);
+                }
+            }
+        }
+
+        let mut rows = Vec::with_capacity(metadata.type_definitions.len());
+        for (index, ty_def) in metadata.type_definitions.iter().enumerate() {
+            let type_index = index as TypeDefinitionIndex;
+            let name = il2cpp.types[ty_def.byvalTypeIndex as usize]
+                .get_complex_type(il2cpp)?
+                .get_name_str(true)?;
+            let full_name = il2cpp.type_definition_name(type_index)?;
+            let namespace = full_name
+                .strip_suffix(name.as_str())
+                .and_then(|prefix| prefix.strip_suffix('.'))
+                .unwrap_or_default()
+                .to_string();
+            let parent = if ty_def.parentIndex >= 0 {
+                Value::Text(il2cpp.type_full_name(ty_def.parentIndex)?)
+            } else {
+                Value::Null
+            };
+            let size = il2cpp
+                .type_sizes(type_index)?
+                .map_or(Value::Null, |sizes| Value::Int(sizes.instance_size as i64));
+            rows.push(vec![
+                Value::Int(index as i64),
+                Value::Text(name),
+                Value::Text(namespace),
+                Value::Text(full_name),
+                Value::Text(ty_def.kind_name().to_string()),
+                Value::Text(image_names[index].clone()),
+                parent,
+                Value::Int(ty_def.field_count as i64),
+                Value::Int(ty_def.method_count as i64),
+                Value::Int(ty_def.property_count as i64),
+                size,
+            ]);
+        }
+        Ok(rows)
+    }
+
+    /// Builds one row per field of each type definition, see [`Entity::columns`] for the
+    /// values.
+    fn field_rows(&self) -> Result<Vec<Vec<Value>>> {
+        let il2cpp = self.il2cpp;
+        let metadata = &il2cpp.metadata;
+        let mut rows = Vec::with_capacity(metadata.fields.len());
+        for (index, ty_def) in metadata.type_definitions.iter().enumerate() {
+            let type_index = index as TypeDefinitionIndex;
+            let declaring_type = il2cpp.type_definition_name(type_index)?;
+            for field_index in ty_def.get_field_range() {
+                let field = &metadata.fields[field_index];
+                let ty = &il2cpp.types[field.typeIndex as usize];
+                let attrs = ty.attrs();
+                let offset = match il2cpp.field_offset(type_index, field_index as i32)? {
+                    Some(
+                        FieldOffset::Instance(offset)
+                        | FieldOffset::Static(offset)
+                        | FieldOffset::ThreadStatic(offset),
+                    ) => Value::Int(offset as i64),
+                    None => Value::Null,
+                };
+                rows.push(vec![
+                    Value::Int(field_index as i64),
+                    Value::Text(metadata.get_string_by_index(field.nameIndex)),
+                    Value::Text(ty.get_complex_type(il2cpp)?.to_string()),
+                    Value::Text(declaring_type.clone()),
+                    Value::Bool(attrs & FIELD_ATTRIBUTE_STATIC != 0),
+                    Value::Bool(attrs & FIELD_ATTRIBUTE_LITERAL != 0),
+                    offset,
+                ]);
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Builds one row per method definition, see [`Entity::columns`] for the values.
+    fn method_rows(&self) -> Result<Vec<Vec<Value>>> {
+        let il2cpp = self.il2cpp;
+        let metadata = &il2cpp.metadata;
+        let method_pointers = il2cpp.method_pointers()?;
+        let declaring_types = self.type_definition_names()?;
+        let mut rows = Vec::with_capacity(metadata.methods.len());
+        for (index, method) in metadata.methods.iter().enumerate() {
+            let mut parameters = Vec::with_capacity(method.parameter_count as usize);
+            for i in 0..method.parameter_count as i32 {
+                let parameter = &metadata.parameters[(method.parameter_start + i) as usize];
+                parameters.push(format!(
+                    "{} {}",
+                    il2cpp.types[parameter.typeIndex as usize].get_complex_type(il2cpp)?,
+                    metadata.get_string_by_index(parameter.nameIndex)
+                ));
+            }
+            let declaring_type = declaring_types
+                .get(&method.declaring_type)
+                .map_or(Value::Null, |name| Value::Text(name.clone()));
+            rows.push(vec![
+                Value::Int(index as i64),
+                Value::Text(metadata.get_string_by_index(method.name_index)),
+                declaring_type,
+                Value::Text(
+                    il2cpp.types[method.return_type as usize]
+                        .get_complex_type(il2cpp)?
+                        .to_string(),
+                ),
+                Value::Int(method.parameter_count as i64),
+                Value::Text(format!("({})", parameters.join(", "))),
+                Value::Bool(method.is_static()),
+                Value::Bool(method.is_virtual()),
+                Value::Bool(method.is_abstract()),
+                Value::Bool(method.generic_container_index >= 0),
+                if method.slot == NO_SLOT {
+                    Value::Null
+                } else {
+                    Value::Int(method.slot as i64)
+                },
+                method_pointers[index].map_or(Value::Null, Value::Address),
+            ]);
+        }
+        Ok(rows)
+    }
+
+    /// Builds one row per parameter of each method definition, see [`Entity::columns`] for the
+    /// values.
+    fn parameter_rows(&self) -> Result<Vec<Vec<Value>>> {
+        let il2cpp = self.il2cpp;
+        let metadata = &il2cpp.metadata;
+        let declaring_types = self.type_definition_names()?;
+        let mut rows = Vec::with_capacity(metadata.parameters.len());
+        for (method_index, method) in metadata.methods.iter().enumerate() {
+            let method_name = metadata.get_string_by_index(method.name_index);
+            let declaring_type = declaring_types
+                .get(&method.declaring_type)
+                .map_or(Value::Null, |name| Value::Text(name.clone()));
+            for position in 0..method.parameter_count as i32 {
+                let index = method.parameter_start + position;
+                let parameter = &metadata.parameters[index as usize];
+                rows.push(vec![
+                    Value::Int(index as i64),
+                    Value::Int(method_index as i64),
+                    Value::Text(method_name.clone()),
+                    declaring_type.clone(),
+                    Value::Int(position as i64),
+                    Value::Text(metadata.get_string_by_index(parameter.nameIndex)),
+                    Value::Text(
+                        il2cpp.types[parameter.typeIndex as usize]
+                            .get_complex_type(il2cpp)?
+                            .to_string(),
+                    ),
+                ]);
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Builds one row per string literal, see [`Entity::columns`] for the values.
+    fn string_rows(&self) -> Result<Vec<Vec<Value>>> {
+        let il2cpp = self.il2cpp;
+        let metadata = &il2cpp.metadata;
+        let mut usages: IntMap<u32, u64> = IntMap::default();
+        for usage in il2cpp.metadata_usages() {
+            if usage.kind == MetadataUsageKind::StringLiteral {
+                usages.entry(usage.index).or_insert(usage.address);
+            }
+        }
+
+        let count = metadata.string_literal_count();
+        let mut rows = Vec::with_capacity(count);
+        for index in 0..count {
+            let value = metadata.string_literal(index)?;
+            let length = value.chars().count();
+            rows.push(vec![
+                Value::Int(index as i64),
+                Value::Text(value.into_owned()),
+                Value::Int(length as i64),
+                usages
+                    .get(&(index as u32))
+                    .map_or(Value::Null, |&address| Value::Address(address)),
+            ]);
+        }
+        Ok(rows)
+    }
+
+    /// Returns the fully qualified names of all type definitions, by index.
+    fn type_definition_names(&self) -> Result<IntMap<TypeDefinitionIndex, String>> {
+        (0..self.il2cpp.metadata.type_definitions.len() as TypeDefinitionIndex)
+            .map(|type_index| Ok((type_index, self.il2cpp.type_definition_name(type_index)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,196 @@
+use crate::archive::sign::sign_archive;
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{
+    get_optional_path, get_required_path, get_text_value, make_path_input, make_secret_input,
+    BrowseType,
+};
+use crate::commands::AppCommand;
+use crate::crypto::signing_key::SigningKey;
+use crate::utils::consts::{APK_FILTER, SIGNING_KEY_FILTER};
+use anyhow::Result;
+use clap::Args;
+use cursive::traits::Resizable;
+use cursive::utils::markup::markdown;
+use cursive::views::{Dialog, DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use log::info;
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Command line arguments for the signing process.
+///
+/// This struct holds the archive to sign and the key to sign it with:
+/// - `apk`: Required path to an APK, XAPK, APKS or APKM archive.
+/// - `output`: Required path to write the signed archive to.
+/// - `key`: Optional PKCS#12 keystore or PEM file; a generated debug key is used when omitted.
+/// - `password`: Optional password of the PKCS#12 keystore.
+#[derive(Args, Default, Clone)]
+pub struct SignArgs {
+    /// Path to an APK, XAPK, APKS or APKM file.
+    #[clap(long)]
+    pub apk: PathBuf,
+    /// Path to write the signed archive to.
+    #[clap(long)]
+    pub output: PathBuf,
+    /// PKCS#12 keystore (.p12/.pfx) or PEM file with an RSA private key and its certificate.
+    /// A debug key is generated and reused when omitted.
+    #[clap(long)]
+    pub key: Option<PathBuf>,
+    /// Password of the PKCS#12 keystore.
+    #[clap(long)]
+    pub password: Option<String>,
+}
+
+impl IntoTui for SignArgs {
+    /// Converts the sign arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with the archive to sign, the output file, an optional key
+    /// file and its password. It sets up a callback that validates and processes the user input
+    /// before invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the sign arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Sign Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(make_path_input(
+                        "Archive File (*): ",
+                        "apk",
+                        BrowseType::File,
+                        Some(APK_FILTER),
+                    ))
+                    .child(make_path_input(
+                        "Signed Archive File (*): ",
+                        "output",
+                        BrowseType::SaveFile,
+                        Some(APK_FILTER),
+                    ))
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new(markdown::parse(
+                        "**Signing key** (leave empty to use the generated debug key):",
+                    )))
+                    .child(make_path_input(
+                        "Key File: ",
+                        "key",
+                        BrowseType::File,
+                        Some(SIGNING_KEY_FILTER),
+                    ))
+                    .child(make_secret_input("Password: ", "password")),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our SignArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.apk = get_required_path(s, "apk");
+                    cmd_inner.output = get_required_path(s, "output");
+                    cmd_inner.key = get_optional_path(s, "key");
+                    cmd_inner.password =
+                        Some(get_text_value(s, "password")).filter(|password| !password.is_empty());
+
+                    if let Err(err) = cmd_inner.validate() {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::Sign(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the sign arguments.
+    ///
+    /// Checks that:
+    /// - The archive file exists.
+    /// - An output file is provided and differs from the archive file.
+    /// - The key file exists, if one is provided.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        if self.apk.as_os_str().is_empty() {
+            return Err("Archive file must not be empty".into());
+        }
+        if !self.apk.exists() {
+            return Err("Archive file does not exist".into());
+        }
+        if self.output.as_os_str().is_empty() {
+            return Err("Signed archive file must not be empty".into());
+        }
+        if self.output == self.apk {
+            return Err("Signed archive file must differ from the archive file".into());
+        }
+        if let Some(key) = &self.key {
+            if !key.exists() {
+                return Err("Key file does not exist".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Executes the signing process.
+///
+/// This function performs the following steps:
+/// 1. Loads the signing key, or the generated debug key if no key file was given.
+/// 2. Signs the APK, or every split APK inside an XAPK/APKS container, with the v1, v2 and v3
+///    signature schemes.
+/// 3. Writes the signed archive to the output path.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - The key file cannot be read or does not contain an RSA key and matching certificate.
+/// - The input is not an archive or cannot be parsed.
+/// - File I/O errors while writing the signed archive.
+pub fn execute(args: SignArgs) -> Result<()> {
+    info!("Running sign command...");
+    info!(progress = 0, max = 2; "");
+
+    info!("Loading signing key...");
+    let key = match &args.key {
+        Some(path) => SigningKey::load(path, args.password.as_deref())?,
+        None => SigningKey::load_or_create_debug()?,
+    };
+    info!("Signing as {}", key.certificate().tbs_certificate.subject);
+    info!(progress_tick = 1; "");
+
+    info!("Signing archive...");
+    sign_archive(&args.apk, &args.output, &key)?;
+    info!(progress_tick = 1; "");
+
+    info!("Done! Signed archive written to {}", args.output.display());
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Header line of the TSV format.
+const TSV_HEADER: &str = "index\tvalue";
+
+/// A string literal with its index in the metadata, as exported and imported.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StringLiteralEntry {
+    /// Index of the string literal in the metadata.
+    pub index: usize,
+    /// The decoded string literal.
+    pub value: String,
+}
+
+/// Returns `true` if the file uses the TSV format, based on its extension. Any other file uses
+/// the JSON format.
+fn is_tsv(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("tsv"))
+}
+
+/// Writes string literals to a JSON or TSV file, depending on its extension.
+///
+/// JSON files contain an array of `{"index": ..., "value": ...}` objects. TSV files contain a
+/// header line followed by one `index<TAB>value` line per literal, with backslashes, tabs and
+/// line breaks in the value escaped as `\\`, `\t`, `\n` and `\r`.
+///
+/// # Errors
+/// Returns an error if the file cannot be written.
+pub fn write_entries(path: &Path, entries: &[StringLiteralEntry]) -> Result<()> {
+    let content = if is_tsv(path) {
+        let mut content = format!("{}\n", TSV_HEADER);
+        for entry in entries {
+            content.push_str(&format!("{}\t{}\n", entry.index, escape(&entry.value)));
+        }
+        content
+    } else {
+        serde_json::to_string_pretty(entries)?
+    };
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Reads string literals from a JSON or TSV file written by [`write_entries`], depending on its
+/// extension. The file may contain any subset of the literals.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or is not in the expected format.
+pub fn read_entries(path: &Path) -> Result<Vec<StringLiteralEntry>> {
+    let content = fs::read_to_string(path)?;
+    if !is_tsv(path) {
+        return serde_json::from_str(&content)
+            .map_err(|err| anyhow!("{} is not a string literal export: {}", path.display(), err));
+    }
+
+    let mut entries = Vec::new();
+    for (line_number, line) in content.lines().enumerate() {
+        // The header line is optional, and empty lines are ignored.
+        if (line_number == 0 && line == TSV_HEADER) || line.is_empty() {
+            continue;
+        }
+        let (index, value) = line.split_once('\t').ok_or_else(|| {
+            anyhow!(
+                "Line {}: expected an index and a value separated by a tab",
+                line_number + 1
+            )
+        })?;
+        let index = index
+            .parse()
+            .map_err(|_| anyhow!("Line {}: invalid index \"{}\"", line_number + 1, index))?;
+        let value = unescape(value).map_err(|err| anyhow!("Line {}: {}", line_number + 1, err))?;
+        entries.push(StringLiteralEntry { index, value });
+    }
+    Ok(entries)
+}
+
+/// Escapes backslashes, tabs and line breaks so a value fits on one TSV line.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverses [`escape`].
+fn unescape(value: &str) -> Result<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => bail!("invalid escape sequence \"\\{}\"", c),
+            None => bail!("value ends with an unfinished escape sequence"),
+        }
+    }
+    Ok(unescaped)
+}
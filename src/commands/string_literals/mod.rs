@@ -0,0 +1,286 @@
+use crate::binary::elf::Elf;
+use crate::commands::input::InputArgs;
+use crate::commands::string_literals::format::{read_entries, write_entries, StringLiteralEntry};
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{get_optional_path, make_path_input, BrowseType};
+use crate::commands::AppCommand;
+use crate::crypto::global_metadata;
+use crate::unity::global_metadata::{is_plain_global_metadata, Metadata};
+use crate::unity::unity_loader::{
+    cache_global_metadata_key, decrypt_global_metadata, EncryptionKeyParts,
+};
+use crate::utils::consts::{METADATA_FILTER, STRING_LITERALS_FILTER};
+use anyhow::{bail, Result};
+use clap::{ArgGroup, Args};
+use cursive::traits::Resizable;
+use cursive::utils::markup::markdown;
+use cursive::views::{Dialog, DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod format;
+
+/// Command line arguments for exporting or editing the string literals of the global metadata.
+///
+/// This struct holds the game files and the action to perform:
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
+/// - `export`: Optional path to write all string literals to.
+/// - `import`: Optional path to read edited string literals from.
+/// - `output`: Path to write the edited global metadata to, required when importing.
+#[derive(Args, Default, Clone)]
+#[clap(group(ArgGroup::new("action").required(true).args(["export", "import"])))]
+pub struct StringLiteralsArgs {
+    /// The IL2CPP binary and global metadata to work on.
+    #[command(flatten)]
+    pub source: InputArgs,
+    /// Path to write all string literals to, as TSV for a .tsv extension and as JSON otherwise.
+    #[clap(long)]
+    pub export: Option<PathBuf>,
+    /// Path to a JSON or TSV file of edited string literals, in the format written by --export.
+    #[clap(long, requires = "output")]
+    pub import: Option<PathBuf>,
+    /// Path to write the edited global-metadata file to, encrypted like the original.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+impl IntoTui for StringLiteralsArgs {
+    /// Converts the string literal arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with two input methods: one for an APK/XAPK file or an
+    /// unpacked APK folder, and another for IL2CPP and Global Metadata files. These are followed
+    /// by the optional metadata key, the export file, and the import file with the edited
+    /// metadata output. It sets up a callback that validates and processes the user input before
+    /// invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the string literal arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("String Literals Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(InputArgs::make_tui_inputs())
+                    .child(DummyView.fixed_height(1))
+                    // Either export the literals, or import edited ones into a new metadata file
+                    .child(TextView::new(markdown::parse(
+                        "**Export the string literals (.json or .tsv):**",
+                    )))
+                    .child(make_path_input(
+                        "Export To File: ",
+                        "export",
+                        BrowseType::SaveFile,
+                        Some(STRING_LITERALS_FILTER),
+                    ))
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new(markdown::parse(
+                        "**Or import edited string literals:**",
+                    )))
+                    .child(make_path_input(
+                        "Import From File: ",
+                        "import",
+                        BrowseType::File,
+                        Some(STRING_LITERALS_FILTER),
+                    ))
+                    .child(make_path_input(
+                        "Edited Metadata File: ",
+                        "output",
+                        BrowseType::SaveFile,
+                        Some(METADATA_FILTER),
+                    )),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our StringLiteralsArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.export = get_optional_path(s, "export");
+                    cmd_inner.import = get_optional_path(s, "import");
+                    cmd_inner.output = get_optional_path(s, "output");
+
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::StringLiterals(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the string literal arguments.
+    ///
+    /// Checks that:
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - Exactly one of the export and import files is provided.
+    /// - An edited metadata file is provided when importing, and differs from the input files.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        self.source.validate()?;
+
+        match (&self.export, &self.import) {
+            (Some(export), None) => {
+                if export.is_dir() {
+                    return Err("Export file is a directory".into());
+                }
+            }
+            (None, Some(import)) => {
+                if !import.is_file() {
+                    return Err("Import file does not exist".into());
+                }
+                let Some(output) = &self.output else {
+                    return Err("Edited metadata file is required when importing".into());
+                };
+                if output.is_dir() {
+                    return Err("Edited metadata file is a directory".into());
+                }
+                if self
+                    .source
+                    .paths()
+                    .chain(&self.import)
+                    .any(|input| input == output)
+                {
+                    return Err("Edited metadata file must differ from the input files".into());
+                }
+            }
+            _ => return Err("Either provide an export file or an import file".into()),
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the string literal export or import.
+///
+/// This function performs the following steps:
+/// 1. Loads input data (il2cpp and global metadata) from either an archive or individual files.
+/// 2. Decrypts the global metadata, unless it is already decrypted, and loads it.
+/// 3. Either writes all string literals to the export file, or replaces the literals from the
+///    import file, rebuilds the metadata and writes it encrypted with the original key, so it can
+///    be put back into the APK. Plain input metadata is written back plain.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - Missing or unreadable input files.
+/// - Failure to decrypt or load the global metadata.
+/// - An import file that is not in the export format or refers to literals that do not exist.
+/// - File I/O errors while writing the output file.
+pub fn execute(args: StringLiteralsArgs) -> Result<()> {
+    info!("Running string literals command...");
+    info!(progress = 0, max = 3; "");
+
+    info!("Loading input data...");
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    info!(progress_tick = 1; "");
+
+    info!("Loading global metadata...");
+    let (plain_data, key) = if is_plain_global_metadata(&global_metadata_data) {
+        info!("Global metadata is already decrypted, skipping decryption");
+        (global_metadata_data, None)
+    } else {
+        let elf = Elf::new(il2cpp_data)?;
+        let decrypted =
+            decrypt_global_metadata(&elf, &global_metadata_data, args.source.metadata_key())?;
+        let key: EncryptionKeyParts = (decrypted.key, decrypted.key_xor);
+        (decrypted.data, Some(key))
+    };
+    let mut metadata = Metadata::load_from_reader(Cursor::new(&plain_data))?;
+    info!("Found {} string literals", metadata.string_literal_count());
+    info!(progress_tick = 1; "");
+
+    if let Some(export) = &args.export {
+        info!("Exporting string literals...");
+        let entries = (0..metadata.string_literal_count())
+            .map(|index| {
+                Ok(StringLiteralEntry {
+                    index,
+                    value: metadata.string_literal(index)?.into_owned(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        write_entries(export, &entries)?;
+        info!(progress_tick = 1; "");
+        info!(
+            "Done! Exported {} string literals to {}",
+            entries.len(),
+            export.display()
+        );
+        return Ok(());
+    }
+
+    let (Some(import), Some(output)) = (&args.import, &args.output) else {
+        bail!("Please provide either --export, or --import with --output.");
+    };
+    info!("Importing string literals...");
+    let edits: Vec<(usize, String)> = read_entries(import)?
+        .into_iter()
+        .map(|entry| (entry.index, entry.value))
+        .collect();
+    let changed = metadata.set_string_literals(&edits)?;
+    if changed == 0 {
+        warn!("The imported file does not change any string literal");
+    }
+
+    let data = metadata.to_bytes()?;
+    let data = match key {
+        Some((key, key_xor)) => {
+            let encrypted = global_metadata::encrypt(&data, key, key_xor);
+            cache_global_metadata_key(&encrypted, key, key_xor)?;
+            encrypted
+        }
+        None => {
+            info!("Writing plain global metadata, as the input global metadata is not encrypted");
+            data
+        }
+    };
+    fs::write(output, data)?;
+    info!(progress_tick = 1; "");
+
+    info!(
+        "Done! Changed {} string literals, global metadata written to {}",
+        changed,
+        output.display()
+    );
+    Ok(())
+}
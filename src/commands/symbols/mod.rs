@@ -0,0 +1,199 @@
+use crate::commands::input::InputArgs;
+use crate::commands::symbols::scripts::SymbolTable;
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{get_required_path, make_path_input, BrowseType};
+use crate::commands::AppCommand;
+use crate::unity::unity_loader::load_il2cpp;
+use anyhow::Result;
+use clap::Args;
+use cursive::traits::Resizable;
+use cursive::utils::markup::markdown;
+use cursive::views::{Dialog, DummyView, LinearLayout};
+use cursive::Cursive;
+use log::info;
+use parking_lot::Mutex;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+
+mod scripts;
+
+pub use scripts::ScriptFormat;
+
+/// Command line arguments for the disassembler symbol script export.
+///
+/// This struct holds the input files, the output directory and the script formats:
+/// - `source`: The IL2CPP binary and global metadata, see [`InputArgs`].
+/// - `output`: Required directory to write the scripts to.
+/// - `format`: Disassemblers to write scripts for, all of them if empty.
+#[derive(Args, Default, Clone)]
+pub struct SymbolsArgs {
+    /// The IL2CPP binary and global metadata to work on.
+    #[command(flatten)]
+    pub source: InputArgs,
+    /// Directory to write the scripts to.
+    #[clap(long)]
+    pub output: PathBuf,
+    /// Disassemblers to write scripts for, separated by commas. Scripts for all of them are written if omitted.
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub format: Vec<ScriptFormat>,
+}
+
+impl IntoTui for SymbolsArgs {
+    /// Converts the symbol script arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with two input methods: one for an APK/XAPK file or an
+    /// unpacked APK folder, and another for IL2CPP and Global Metadata files, followed by the
+    /// output directory. Scripts are written for every disassembler. It sets up a callback that validates and processes the user input before
+    /// invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the symbol script arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Symbol Scripts Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(InputArgs::make_tui_inputs())
+                    .child(DummyView.fixed_height(1))
+                    // Output directory for the scripts
+                    .child(make_path_input(
+                        "Output Directory (*): ",
+                        "output",
+                        BrowseType::Folder,
+                        None,
+                    )),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our SymbolsArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.output = get_required_path(s, "output");
+
+                    let result = cmd_inner
+                        .source
+                        .read_tui_inputs(s)
+                        .and_then(|()| cmd_inner.validate());
+                    if let Err(err) = result {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::Symbols(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the symbol script arguments.
+    ///
+    /// Checks that:
+    /// - The input arguments are valid, see [`InputArgs::validate`].
+    /// - An output directory is provided and is not a file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        self.source.validate()?;
+
+        if self.output.as_os_str().is_empty() {
+            return Err("Output directory must not be empty".into());
+        }
+        if self.output.exists() && !self.output.is_dir() {
+            return Err("Output path is not a directory".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the disassembler symbol script export.
+///
+/// This function performs the following steps:
+/// 1. Loads input data (il2cpp and global metadata) from either an archive or individual files.
+/// 2. Decrypts the global metadata, unless it is already decrypted, and loads the il2cpp binary.
+/// 3. Collects the address of every method, the metadata usage variables (type info, method,
+///    field and string literal pointers) and the string literal values.
+/// 4. Writes a script for each requested disassembler that applies these names and comments.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - Missing or unreadable input files.
+/// - Failure to decrypt the global metadata or load the il2cpp binary.
+/// - A method name that cannot be resolved.
+/// - File I/O errors while writing the scripts.
+pub fn execute(args: SymbolsArgs) -> Result<()> {
+    info!("Running symbols command...");
+    info!(progress = 0, max = 4; "");
+
+    info!("Loading input data...");
+    let (il2cpp_data, global_metadata_data) = args.source.load_inputs()?;
+    info!(progress_tick = 1; "");
+
+    info!("Loading global metadata and il2cpp...");
+    let il2cpp = load_il2cpp(
+        il2cpp_data,
+        global_metadata_data,
+        args.source.metadata_key(),
+    )?;
+    info!(progress_tick = 1; "");
+
+    info!("Collecting symbols...");
+    let symbols = SymbolTable::collect(&il2cpp)?;
+    info!(
+        "Found {} functions and {} metadata usages",
+        symbols.function_count(),
+        symbols.data_count()
+    );
+    info!(progress_tick = 1; "");
+
+    let formats = if args.format.is_empty() {
+        ScriptFormat::iter().collect()
+    } else {
+        args.format.clone()
+    };
+    fs::create_dir_all(&args.output)?;
+    for format in formats {
+        let path = args.output.join(format.file_name());
+        info!("Writing {}...", path.display());
+        let mut out = BufWriter::new(File::create(&path)?);
+        symbols.write_script(format, &mut out)?;
+        out.flush()?;
+    }
+    info!(progress_tick = 1; "");
+
+    info!("Done! Wrote symbol scripts to {}", args.output.display());
+    Ok(())
+}
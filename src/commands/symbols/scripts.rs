@@ -0,0 +1,320 @@
+use crate::unity::il2cpp::Il2Cpp;
+use crate::unity::metadata_usages::MetadataUsageKind;
+use anyhow::Result;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use clap::ValueEnum;
+use hashbrown::HashMap;
+use log::warn;
+use std::collections::BTreeMap;
+use std::io::Write;
+use strum::EnumIter;
+
+/// A disassembler that a symbol script can be written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, EnumIter)]
+pub enum ScriptFormat {
+    /// An IDAPython script for IDA Pro 7.4 or later.
+    Ida,
+    /// A Python script for Ghidra's script manager.
+    Ghidra,
+    /// A Python script for Binary Ninja.
+    BinaryNinja,
+    /// A radare2 command script.
+    Radare2,
+}
+
+impl ScriptFormat {
+    /// Returns the file name the script is written to.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ScriptFormat::Ida => "ida_symbols.py",
+            ScriptFormat::Ghidra => "ghidra_symbols.py",
+            ScriptFormat::BinaryNinja => "binja_symbols.py",
+            ScriptFormat::Radare2 => "r2_symbols.r2",
+        }
+    }
+}
+
+const IDA_HEADER: &str = "\
+# -*- coding: utf-8 -*-
+# IDAPython script generated by ptcgp_tool.
+# Names the IL2CPP methods, metadata usages and string literals of libil2cpp.so.
+# Run it with File > Script file... once the initial auto analysis has finished.
+import ida_bytes
+import ida_funcs
+import ida_name
+import idaapi
+
+BASE = idaapi.get_imagebase()
+FLAGS = ida_name.SN_NOWARN | ida_name.SN_NOCHECK | ida_name.SN_FORCE
+";
+
+const IDA_FOOTER: &str = "
+for address, name in FUNCTIONS:
+    ida_funcs.add_func(BASE + address)
+    ida_name.set_name(BASE + address, name, FLAGS)
+
+for address, name in DATA:
+    ida_bytes.create_qword(BASE + address, 8)
+    ida_name.set_name(BASE + address, name, FLAGS)
+
+for address, comment in COMMENTS:
+    ida_bytes.set_cmt(BASE + address, comment, True)
+
+print(\"Named %d functions and %d data symbols\" % (len(FUNCTIONS), len(DATA)))
+";
+
+const GHIDRA_HEADER: &str = "\
+# -*- coding: utf-8 -*-
+# Ghidra script generated by ptcgp_tool.
+# Names the IL2CPP methods, metadata usages and string literals of libil2cpp.so.
+# Run it from the Script Manager once the initial auto analysis has finished.
+#@category IL2CPP
+from ghidra.program.model.symbol import SourceType
+
+BASE = currentProgram.getImageBase()
+";
+
+const GHIDRA_FOOTER: &str = "
+for address, name in FUNCTIONS:
+    function = getFunctionAt(BASE.add(address))
+    if function is None:
+        function = createFunction(BASE.add(address), name)
+    if function is None:
+        createLabel(BASE.add(address), name, True, SourceType.USER_DEFINED)
+    else:
+        function.setName(name, SourceType.USER_DEFINED)
+
+for address, name in DATA:
+    createLabel(BASE.add(address), name, True, SourceType.USER_DEFINED)
+
+for address, comment in COMMENTS:
+    setEOLComment(BASE.add(address), comment)
+
+print(\"Named %d functions and %d data symbols\" % (len(FUNCTIONS), len(DATA)))
+";
+
+const BINARY_NINJA_HEADER: &str = "\
+# -*- coding: utf-8 -*-
+# Binary Ninja script generated by ptcgp_tool.
+# Names the IL2CPP methods, metadata usages and string literals of libil2cpp.so.
+# Run it with File > Run Script... once the initial analysis has finished.
+from binaryninja import Symbol, SymbolType
+
+BASE = bv.start
+";
+
+const BINARY_NINJA_FOOTER: &str = "
+for address, name in FUNCTIONS:
+    bv.add_function(BASE + address)
+    bv.define_user_symbol(Symbol(SymbolType.FunctionSymbol, BASE + address, name))
+
+for address, name in DATA:
+    bv.define_user_symbol(Symbol(SymbolType.DataSymbol, BASE + address, name))
+
+for address, comment in COMMENTS:
+    bv.set_comment_at(BASE + address, comment)
+
+bv.update_analysis()
+print(\"Named %d functions and %d data symbols\" % (len(FUNCTIONS), len(DATA)))
+";
+
+const RADARE2_HEADER: &str = "\
+# radare2 script generated by ptcgp_tool.
+# Names the IL2CPP methods, metadata usages and string literals of libil2cpp.so.
+# Load the library at its default base address and run `. r2_symbols.r2`.
+";
+
+/// A named address in the IL2CPP binary.
+struct Symbol {
+    /// The virtual address of the symbol.
+    address: u64,
+    /// The name of the symbol, unique within the symbol table.
+    name: String,
+}
+
+/// The symbols of an IL2CPP binary: the code of every method, the metadata usage variables and
+/// the values of the string literals they refer to.
+pub struct SymbolTable {
+    /// The methods, one per code address.
+    functions: Vec<Symbol>,
+    /// The metadata usage variables, such as type info and string literal pointers.
+    data: Vec<Symbol>,
+    /// Comments, holding the values of string literals at their usage variables.
+    comments: Vec<(u64, String)>,
+}
+
+impl SymbolTable {
+    /// Collects the symbols of an IL2CPP binary.
+    ///
+    /// Methods that share their code, such as shared generic instances, are named after the
+    /// first method with that address. Names are reduced to characters that every supported
+    /// disassembler accepts, and repeated names get a numeric suffix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method pointers or method names cannot be resolved.
+    pub fn collect<'a>(il2cpp: &'a Il2Cpp<'a>) -> Result<Self> {
+        let functions = il2cpp.methods()?.into_iter().collect::<BTreeMap<_, _>>();
+
+        let mut data = BTreeMap::new();
+        let mut comments = Vec::new();
+        for usage in il2cpp.metadata_usages() {
+            let name = match il2cpp.metadata_usage_name(&usage) {
+                Ok(name) => name,
+                Err(err) => {
+                    warn!("Skipping metadata usage at 0x{:X}: {}", usage.address, err);
+                    continue;
+                }
+            };
+            // Still name the data symbol if its literal cannot be read, only without the comment.
+            if usage.kind == MetadataUsageKind::StringLiteral {
+                match il2cpp.metadata.string_literal(usage.index as usize) {
+                    Ok(value) => comments.push((usage.address, value.into_owned())),
+                    Err(err) => warn!(
+                        "Skipping string literal comment at 0x{:X}: {}",
+                        usage.address, err
+                    ),
+                }
+            }
+            data.insert(usage.address, name);
+        }
+
+        let mut names = HashMap::new();
+        let mut unique = |(address, name): (u64, String)| {
+            let name = sanitize_name(&name);
+            let count = names.entry(name.clone()).or_insert(0usize);
+            *count += 1;
+            let name = if *count == 1 {
+                name
+            } else {
+                format!("{}_{}", name, *count - 1)
+            };
+            Symbol { address, name }
+        };
+        let functions = functions.into_iter().map(&mut unique).collect();
+        let data = data.into_iter().map(&mut unique).collect();
+
+        Ok(SymbolTable {
+            functions,
+            data,
+            comments,
+        })
+    }
+
+    /// Returns the number of function symbols.
+    pub fn function_count(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns the number of data symbols.
+    pub fn data_count(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Writes the symbols as a script for the given disassembler.
+    pub fn write_script<W: Write>(&self, format: ScriptFormat, out: &mut W) -> Result<()> {
+        match format {
+            ScriptFormat::Ida => self.write_python(out, IDA_HEADER, "", IDA_FOOTER),
+            // Ghidra runs scripts with Jython, where only unicode literals can hold any text.
+            ScriptFormat::Ghidra => self.write_python(out, GHIDRA_HEADER, "u", GHIDRA_FOOTER),
+            ScriptFormat::BinaryNinja => {
+                self.write_python(out, BINARY_NINJA_HEADER, "", BINARY_NINJA_FOOTER)
+            }
+            ScriptFormat::Radare2 => self.write_radare2(out),
+        }
+    }
+
+    /// Writes a Python script that defines the `FUNCTIONS`, `DATA` and `COMMENTS` lists of
+    /// address and text pairs between a header and a footer that applies them.
+    ///
+    /// Strings are written as JSON strings, which are also valid Python string literals.
+    fn write_python<W: Write>(
+        &self,
+        out: &mut W,
+        header: &str,
+        string_prefix: &str,
+        footer: &str,
+    ) -> Result<()> {
+        write!(out, "{}", header)?;
+        let lists = [
+            ("FUNCTIONS", symbol_pairs(&self.functions)),
+            ("DATA", symbol_pairs(&self.data)),
+            (
+                "COMMENTS",
+                self.comments
+                    .iter()
+                    .map(|(a, c)| (*a, c.as_str()))
+                    .collect(),
+            ),
+        ];
+        for (list_name, entries) in lists {
+            writeln!(out, "\n{} = [", list_name)?;
+            for (address, text) in entries {
+                writeln!(
+                    out,
+                    "    (0x{:X}, {}{}),",
+                    address,
+                    string_prefix,
+                    serde_json::to_string(text)?
+                )?;
+            }
+            writeln!(out, "]")?;
+        }
+        write!(out, "{}", footer)?;
+        Ok(())
+    }
+
+    /// Writes a radare2 script that analyzes and names the functions, flags the data symbols and
+    /// adds the comments, base64 encoded so any text can be used.
+    fn write_radare2<W: Write>(&self, out: &mut W) -> Result<()> {
+        write!(out, "{}", RADARE2_HEADER)?;
+        // radare2 expands `$` in command arguments, so it cannot be used in names.
+        for symbol in &self.functions {
+            writeln!(
+                out,
+                "af {} @ 0x{:X}",
+                symbol.name.replace('$', "_"),
+                symbol.address
+            )?;
+        }
+        for symbol in &self.data {
+            writeln!(
+                out,
+                "f {} 8 @ 0x{:X}",
+                symbol.name.replace('$', "_"),
+                symbol.address
+            )?;
+        }
+        for (address, comment) in &self.comments {
+            writeln!(
+                out,
+                "CCu base64:{} @ 0x{:X}",
+                BASE64_STANDARD.encode(comment),
+                address
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the address and name of each symbol.
+fn symbol_pairs(symbols: &[Symbol]) -> Vec<(u64, &str)> {
+    symbols
+        .iter()
+        .map(|symbol| (symbol.address, symbol.name.as_str()))
+        .collect()
+}
+
+/// Replaces every character that is not an ASCII letter, digit, `_`, `.` or `$` with `_`, such
+/// as the brackets and commas of generic type names.
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -6,9 +6,9 @@ use anyhow::Result;
 use cursive::{Cursive, CursiveExt};
 
 pub mod commands;
+mod logger;
 mod themes;
 pub mod utils;
-mod logger;
 
 /// Launches the text user interface (TUI) for the application.
 ///
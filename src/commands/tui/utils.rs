@@ -1,5 +1,8 @@
 #![allow(dead_code)]
 
+use crate::unity::unity_loader::{
+    parse_encryption_key, parse_encryption_key_xor, EncryptionKey, EncryptionKeyXor,
+};
 use cursive::traits::*;
 use cursive::view::SizeConstraint;
 use cursive::views::{Button, Checkbox, EditView, LinearLayout, ResizedView, TextView};
@@ -64,6 +67,39 @@ pub fn make_path_input(
         .child(Button::new_raw(" <Browse>", button_cb))
 }
 
+/// Creates a horizontal layout with:
+///  - A label (e.g., "Include patterns: ")
+///  - An editable text field for free-form input.
+pub fn make_text_input(label: &str, name: &str) -> LinearLayout {
+    LinearLayout::horizontal()
+        .child(TextView::new(label))
+        .child(ResizedView::new(
+            SizeConstraint::Full,
+            SizeConstraint::Fixed(1),
+            EditView::new().with_name(name),
+        ))
+}
+
+/// Creates a horizontal layout with:
+///  - A label (e.g., "Password: ")
+///  - An editable text field that masks its content.
+pub fn make_secret_input(label: &str, name: &str) -> LinearLayout {
+    LinearLayout::horizontal()
+        .child(TextView::new(label))
+        .child(ResizedView::new(
+            SizeConstraint::Full,
+            SizeConstraint::Fixed(1),
+            EditView::new().secret().with_name(name),
+        ))
+}
+
+/// Retrieves the content of the named `EditView` as a `String`.
+/// Panics if the view isn't found (the `.unwrap()`).
+pub fn get_text_value(s: &mut Cursive, name: &str) -> String {
+    s.call_on_name(name, |view: &mut EditView| view.get_content().to_string())
+        .unwrap()
+}
+
 /// Retrieves the content of the named `TextView` as a required `PathBuf`.
 /// Panics if the view isn't found (the `.unwrap()`).
 pub fn get_required_path(s: &mut Cursive, name: &str) -> PathBuf {
@@ -92,3 +128,32 @@ pub fn get_checkbox_value(s: &mut Cursive, name: &str) -> bool {
     s.call_on_name(name, |view: &mut Checkbox| view.is_checked())
         .unwrap()
 }
+
+/// Creates a vertical layout with text fields for a manual metadata key and key xor, read back
+/// with [`get_metadata_key_inputs`].
+pub fn make_metadata_key_inputs() -> LinearLayout {
+    LinearLayout::vertical()
+        .child(make_text_input("Metadata Key (optional): ", "metadata_key"))
+        .child(make_text_input(
+            "Metadata Key Xor (optional): ",
+            "metadata_key_xor",
+        ))
+}
+
+/// Retrieves and parses the fields created by [`make_metadata_key_inputs`].
+/// Empty fields are returned as `None`; invalid values are returned as an error message.
+pub fn get_metadata_key_inputs(
+    s: &mut Cursive,
+) -> Result<(Option<EncryptionKey>, Option<EncryptionKeyXor>), String> {
+    let key = get_text_value(s, "metadata_key");
+    let key_xor = get_text_value(s, "metadata_key_xor");
+    let key = (!key.trim().is_empty())
+        .then(|| parse_encryption_key(&key))
+        .transpose()
+        .map_err(|err| err.to_string())?;
+    let key_xor = (!key_xor.trim().is_empty())
+        .then(|| parse_encryption_key_xor(&key_xor))
+        .transpose()
+        .map_err(|err| err.to_string())?;
+    Ok((key, key_xor))
+}
@@ -0,0 +1,271 @@
+use crate::archive::{open_archive, ArchiveEntry};
+use crate::commands::tui::commands::IntoTui;
+use crate::commands::tui::utils::{
+    get_checkbox_value, get_optional_path, get_required_path, get_text_value, make_path_input,
+    make_text_input, BrowseType,
+};
+use crate::commands::AppCommand;
+use crate::utils::consts::APK_FILTER;
+use anyhow::{anyhow, bail, Result};
+use clap::Args;
+use cursive::traits::{Nameable, Resizable};
+use cursive::utils::markup::markdown;
+use cursive::views::{Checkbox, Dialog, DummyView, LinearLayout, TextView};
+use cursive::Cursive;
+use log::{debug, info};
+use parking_lot::Mutex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Command line arguments for the unpack process.
+///
+/// This struct holds the archive to unpack and controls which files are listed or extracted:
+/// - `apk`: Required path to an APK, XAPK, APKS or APKM archive.
+/// - `output`: Output directory for the extracted files, required unless only listing.
+/// - `include`: Glob patterns selecting the files to process; all files when empty.
+/// - `list`: Flag to only list the matching files instead of extracting them.
+/// - `overwrite`: Flag to allow extracting into a non-empty output directory.
+#[derive(Args, Default, Clone)]
+pub struct UnpackArgs {
+    /// Path to an APK, XAPK, APKS or APKM file.
+    #[clap(long)]
+    pub apk: PathBuf,
+    /// Output directory for the extracted files.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+    /// Glob pattern selecting the files to process (e.g. `lib/**/*.so` or `assets/`). Can be repeated.
+    #[clap(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+    /// Only list the matching files instead of extracting them.
+    #[clap(long)]
+    pub list: bool,
+    /// Extract into the output directory even if it is not empty, replacing existing files.
+    #[clap(long)]
+    pub overwrite: bool,
+}
+
+impl IntoTui for UnpackArgs {
+    /// Converts the unpack arguments into a TUI (Text User Interface) dialog using Cursive.
+    ///
+    /// This method builds a dialog with an archive input, an output directory, a free-form list of
+    /// include patterns, and options for listing only and overwriting. It sets up a callback that
+    /// validates and processes the user input before invoking the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `siv` - A mutable reference to the Cursive TUI instance.
+    /// * `next_fn` - A callback function to be executed after the unpack arguments are processed.
+    fn into_tui<F>(self, siv: &mut Cursive, next_fn: F)
+    where
+        F: 'static + FnOnce(&mut Cursive, AppCommand) + Send + Sync,
+    {
+        let cmd = Arc::new(Mutex::new(Some(self)));
+        let next_fn = Arc::new(Mutex::new(Some(next_fn)));
+
+        let dialog = Dialog::new()
+            .title("Unpack Arguments")
+            .padding_lrtb(1, 1, 1, 0)
+            .content(
+                LinearLayout::vertical()
+                    .child(make_path_input(
+                        "Archive File (*): ",
+                        "apk",
+                        BrowseType::File,
+                        Some(APK_FILTER),
+                    ))
+                    .child(make_path_input(
+                        "Output Directory: ",
+                        "output",
+                        BrowseType::Folder,
+                        None,
+                    ))
+                    .child(DummyView.fixed_height(1))
+                    // Patterns are separated by spaces or commas; leaving the field empty selects all files.
+                    .child(TextView::new(markdown::parse(
+                        "**Include patterns** (e.g. `assets/ lib/**/*.so`, empty for all files):",
+                    )))
+                    .child(make_text_input("Patterns: ", "include"))
+                    .child(DummyView.fixed_height(1))
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(TextView::new("List only: "))
+                            .child(Checkbox::new().with_checked(false).with_name("list")),
+                    )
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(TextView::new("Overwrite directory: "))
+                            .child(Checkbox::new().with_checked(false).with_name("overwrite")),
+                    ),
+            )
+            .button("Run", {
+                let cmd = Arc::clone(&cmd);
+                let next_fn = Arc::clone(&next_fn);
+                move |s| {
+                    // Lock and take our UnpackArgs
+                    let mut cmd_inner = cmd.lock().take().unwrap();
+
+                    // Fill in the arguments from the TUI input fields
+                    cmd_inner.apk = get_required_path(s, "apk");
+                    cmd_inner.output = get_optional_path(s, "output");
+                    cmd_inner.include = get_text_value(s, "include")
+                        .split([' ', ','])
+                        .filter(|pattern| !pattern.is_empty())
+                        .map(String::from)
+                        .collect();
+                    cmd_inner.list = get_checkbox_value(s, "list");
+                    cmd_inner.overwrite = get_checkbox_value(s, "overwrite");
+
+                    if let Err(err) = cmd_inner.validate() {
+                        cmd.lock().replace(cmd_inner);
+                        s.add_layer(
+                            Dialog::text(markdown::parse(format!("**Error:**\n\n{}", err)))
+                                .dismiss_button("Back"),
+                        );
+                        return;
+                    }
+
+                    // Pop the dialog after successful validation
+                    s.pop_layer();
+
+                    // Call the callback if present
+                    if let Some(callback) = next_fn.lock().take() {
+                        callback(s, AppCommand::Unpack(cmd_inner));
+                    }
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            });
+
+        siv.add_layer(dialog.max_width(80));
+    }
+
+    /// Validates the unpack arguments.
+    ///
+    /// Checks that:
+    /// - The archive file exists.
+    /// - An output directory is provided unless only listing.
+    /// - The output directory is not empty unless the overwrite flag is set, and it is a directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all arguments are valid.
+    /// * `Err(String)` with an error message if any validation step fails.
+    fn validate(&self) -> Result<(), String> {
+        if self.apk.as_os_str().is_empty() {
+            return Err("Archive file must not be empty".into());
+        }
+        if !self.apk.exists() {
+            return Err("Archive file does not exist".into());
+        }
+
+        // Listing does not write anything, so the output directory is irrelevant.
+        if self.list {
+            return Ok(());
+        }
+
+        let Some(output) = &self.output else {
+            return Err("Output directory is required unless only listing files".into());
+        };
+
+        // Validate output directory.
+        if output.exists() {
+            if !output.is_dir() {
+                return Err("Output path is not a directory".into());
+            }
+            // If the directory exists and is not empty, then the overwrite flag must be set.
+            match fs::read_dir(output) {
+                Ok(mut entries) => {
+                    if entries.next().is_some() && !self.overwrite {
+                        return Err(
+                            "Output directory is not empty. Use --overwrite to allow overwriting."
+                                .into(),
+                        );
+                    }
+                }
+                Err(e) => return Err(format!("Failed to read output directory: {}", e)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the unpack process.
+///
+/// This function performs the following steps:
+/// 1. Opens the archive, detecting its type by content.
+/// 2. Lists the files matching the include patterns, across all split APKs.
+/// 3. Either logs the matching files or extracts them into the output directory, preserving
+///    their relative paths.
+///
+/// # Errors
+///
+/// Returns an error if any step fails, such as:
+/// - The archive cannot be opened or an include pattern is invalid.
+/// - No files match the include patterns.
+/// - File I/O errors during extraction.
+pub fn execute(args: UnpackArgs) -> Result<()> {
+    info!("Running unpack command...");
+
+    info!("Opening archive...");
+    let mut archive = open_archive(&args.apk)?;
+    let entries = archive.find_entries(&args.include)?;
+    if entries.is_empty() {
+        bail!("No files in the archive match the given patterns");
+    }
+
+    if args.list {
+        list_entries(&entries);
+        info!("Done!");
+        return Ok(());
+    }
+
+    let output = args
+        .output
+        .as_ref()
+        .ok_or(anyhow!("An output directory is required to extract files"))?;
+    fs::create_dir_all(output)?;
+
+    info!("Extracting {} files...", entries.len());
+    info!(progress = 0, max = entries.len(); "");
+    for entry in &entries {
+        debug!("\t-{}", entry.path);
+        archive.extract_entry(entry, output)?;
+        info!(progress_tick = 1; "");
+    }
+
+    info!("Done!");
+    Ok(())
+}
+
+/// Logs a table of archive entries with their sizes and the split APK that provides them.
+///
+/// # Arguments
+///
+/// * `entries` - The entries to log.
+fn list_entries(entries: &[ArchiveEntry]) {
+    info!("{:>12} {:>12}  {}", "Size", "Compressed", "Path");
+    for entry in entries {
+        match &entry.split_apk {
+            Some(split_apk) => info!(
+                "{:>12} {:>12}  {} [{}]",
+                entry.size, entry.compressed_size, entry.path, split_apk
+            ),
+            None => info!(
+                "{:>12} {:>12}  {}",
+                entry.size, entry.compressed_size, entry.path
+            ),
+        }
+    }
+
+    let total_size: u64 = entries.iter().map(|entry| entry.size).sum();
+    let total_compressed: u64 = entries.iter().map(|entry| entry.compressed_size).sum();
+    info!(
+        "{:>12} {:>12}  {} files",
+        total_size,
+        total_compressed,
+        entries.len()
+    );
+}
@@ -17,26 +17,100 @@ type Aes128Ctr = ctr::Ctr128BE<Aes128>;
 /// A vector containing the decrypted plaintext.
 ///
 /// # Explanation
+/// 1. **Data Parsing:**  
+///    The input `data` starts with a 4-byte header that indicates the length of the ciphertext.
+///    The function separates these 4 bytes and converts them into a `u32` to verify the data integrity.
+///
+/// 2. **Decryption:**  
+///    The keystream is applied to a copy of the ciphertext (see [`apply_keystream`]).
+///
+/// Note: This function assumes that the input data is well-formed and will panic if the header or lengths are incorrect.
+pub fn decrypt(data: &[u8], key: [u8; 16], key_xor: u64) -> Vec<u8> {
+    // The first 4 bytes of `data` represent the ciphertext length (stored in little-endian order).
+    let (data_len_bytes, ciphertext) = data.split_at(4);
+    let data_len = u32::from_le_bytes(data_len_bytes.try_into().unwrap());
+    debug_assert_eq!(data_len as usize, ciphertext.len());
+
+    // Create a mutable copy of the ciphertext and decrypt it in place using the keystream.
+    let mut decrypted = ciphertext.to_vec();
+    apply_keystream(&mut decrypted, key, key_xor);
+    decrypted
+}
+
+/// Decrypts only the first `len` bytes of the ciphertext, to check a candidate key against the
+/// metadata header without decrypting the whole file.
+///
+/// # Parameters
+/// - `data`: The encrypted data, in the format expected by [`decrypt`].
+/// - `len`: The number of plaintext bytes to decrypt, clamped to the ciphertext length.
+/// - `key`: A 16-byte encryption key.
+/// - `key_xor`: A 64-bit value used to XOR with the original key.
+///
+/// # Returns
+/// A vector containing the first `len` bytes of the plaintext.
+pub fn decrypt_prefix(data: &[u8], len: usize, key: [u8; 16], key_xor: u64) -> Vec<u8> {
+    let ciphertext = data.get(4..).unwrap_or_default();
+    let mut decrypted = ciphertext[..len.min(ciphertext.len())].to_vec();
+    new_cipher(key, key_xor).apply_keystream(&mut decrypted);
+    decrypted
+}
+
+/// Encrypts data in the format expected by [`decrypt`] and by the game.
+///
+/// # Parameters
+/// - `data`: The plaintext to encrypt.
+/// - `key`: A 16-byte encryption key.
+/// - `key_xor`: A 64-bit value used to XOR with the original key for an extra layer of key obfuscation.
+///
+/// # Returns
+/// A vector containing a 4-byte little-endian header with the ciphertext length, followed by the
+/// ciphertext itself.
+///
+/// # Panics
+/// Panics if the plaintext is larger than `u32::MAX` bytes, as its length cannot be stored in the header.
+pub fn encrypt(data: &[u8], key: [u8; 16], key_xor: u64) -> Vec<u8> {
+    let data_len = u32::try_from(data.len()).expect("plaintext is too large to encrypt");
+
+    // Prefix the ciphertext with its length, then encrypt it in place using the keystream.
+    let mut encrypted = Vec::with_capacity(data.len() + 4);
+    encrypted.extend_from_slice(&data_len.to_le_bytes());
+    encrypted.extend_from_slice(data);
+    apply_keystream(&mut encrypted[4..], key, key_xor);
+    encrypted
+}
+
+/// Applies the AES-CTR keystream to the data in place. Since CTR mode is symmetric, this both
+/// encrypts and decrypts.
+///
+/// The keystream is applied to the data in chunks, reporting progress. See [`new_cipher`] for how
+/// the cipher is set up.
+fn apply_keystream(data: &mut [u8], key: [u8; 16], key_xor: u64) {
+    let mut cipher = new_cipher(key, key_xor);
+
+    let total = data.len();
+    debug!(progress = 0, max = total; "");
+    let chunk_size = 1024;
+
+    // Process the data in chunks, applying the keystream and reporting progress.
+    for chunk in data.chunks_mut(chunk_size) {
+        cipher.apply_keystream(chunk);
+        debug!(progress_tick = chunk.len(); "");
+    }
+}
+
+/// Creates the AES-CTR cipher for the custom encryption scheme of the global metadata.
+///
+/// # Explanation
 /// 1. **Key Obfuscation:**  
 ///    The provided `key` is XORed with the little-endian byte representation of `key_xor`.
 ///    The 8-byte representation of `key_xor` is cycled over the 16-byte key. This extra step
 ///    hides the actual key by combining it with an additional value.
 ///
-/// 2. **Data Parsing:**  
-///    The input `data` starts with a 4-byte header that indicates the length of the ciphertext.
-///    The function separates these 4 bytes and converts them into a `u32` to verify the data integrity.
-///
-/// 3. **IV Setup for CTR Mode:**  
+/// 2. **IV Setup for CTR Mode:**  
 ///    The custom encryption scheme uses an initial counter of 1 (instead of 0) for the first block.
 ///    To achieve this, an Initialization Vector (IV) is constructed with its lower 8 bytes set to the big-endian
 ///    representation of `1` and the remaining bytes set to zero.
-///
-/// 4. **Decryption:**  
-///    Using the adjusted key and the IV, the AES-CTR cipher is initialized. The ciphertext is then decrypted
-///    in place by applying the keystream.
-///
-/// Note: This function assumes that the input data is well-formed and will panic if the header or lengths are incorrect.
-pub fn decrypt(data: &[u8], key: [u8; 16], key_xor: u64) -> Vec<u8> {
+fn new_cipher(key: [u8; 16], key_xor: u64) -> Aes128Ctr {
     // Convert the key_xor value to its little-endian byte representation.
     let key_xor_bytes = key_xor.to_le_bytes();
 
@@ -50,11 +124,6 @@ pub fn decrypt(data: &[u8], key: [u8; 16], key_xor: u64) -> Vec<u8> {
         .try_into()
         .unwrap();
 
-    // The first 4 bytes of `data` represent the ciphertext length (stored in little-endian order).
-    let (data_len_bytes, ciphertext) = data.split_at(4);
-    let data_len = u32::from_le_bytes(data_len_bytes.try_into().unwrap());
-    debug_assert_eq!(data_len as usize, ciphertext.len());
-
     // Prepare the Initialization Vector (IV) for AES-CTR.
     // The custom implementation increments the counter before encryption,
     // so the first block is encrypted with counter = 1. Here, we construct a 16-byte IV
@@ -63,18 +132,5 @@ pub fn decrypt(data: &[u8], key: [u8; 16], key_xor: u64) -> Vec<u8> {
     iv[8..16].copy_from_slice(&1u64.to_be_bytes());
 
     // Initialize the AES-CTR cipher with the obfuscated key and custom IV.
-    let mut cipher = Aes128Ctr::new(&key_bytes.into(), &iv.into());
-
-    // Create a mutable copy of the ciphertext and decrypt it in place using the keystream.
-    let mut decrypted = ciphertext.to_vec();
-    let total = decrypted.len();
-    debug!(progress = 0, max = total; "");
-    let chunk_size = 1024;
-
-    // Process the ciphertext in chunks, applying the keystream and reporting progress.
-    for chunk in decrypted.chunks_mut(chunk_size) {
-        cipher.apply_keystream(chunk);
-        debug!(progress_tick = chunk.len(); "");
-    }
-    decrypted
+    Aes128Ctr::new(&key_bytes.into(), &iv.into())
 }
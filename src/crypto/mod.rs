@@ -1 +1,2 @@
 pub mod global_metadata;
+pub mod signing_key;
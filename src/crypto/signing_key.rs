@@ -0,0 +1,223 @@
+use crate::utils::file_backed_cache::FileBackedCache;
+use anyhow::{anyhow, bail, Result};
+use log::info;
+use p12_keystore::KeyStore;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs1v15;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, EncodePublicKey};
+use rsa::rand_core::{OsRng, RngCore};
+use rsa::signature::{SignatureEncoding, Signer};
+use rsa::RsaPrivateKey;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use x509_cert::builder::{Builder, CertificateBuilder, Profile};
+use x509_cert::der::{Decode, Encode};
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::spki::SubjectPublicKeyInfoOwned;
+use x509_cert::time::Validity;
+use x509_cert::Certificate;
+
+/// Name of the cache file holding generated signing keys.
+const SIGNING_KEY_CACHE_NAME: &str = "signing_keys";
+/// Cache key of the generated debug key.
+const DEBUG_KEY_NAME: &str = "debug";
+/// Subject of the generated debug certificate, matching the Android SDK debug keystore.
+const DEBUG_CERTIFICATE_SUBJECT: &str = "CN=Android Debug,O=Android,C=US";
+/// Validity of the generated debug certificate: 30 years, like the Android SDK debug keystore.
+const DEBUG_CERTIFICATE_VALIDITY: Duration = Duration::from_secs(30 * 365 * 24 * 60 * 60);
+/// Size of the generated debug key in bits.
+const DEBUG_KEY_BITS: usize = 2048;
+
+/// A signing key as persisted in the signing key cache, PEM-encoded.
+#[derive(Serialize, Deserialize)]
+struct StoredSigningKey {
+    /// The PKCS#8 private key.
+    private_key: String,
+    /// The X.509 certificate.
+    certificate: String,
+}
+
+/// An RSA private key together with the certificate identifying it, used to sign APKs.
+pub struct SigningKey {
+    /// The private key.
+    private_key: RsaPrivateKey,
+    /// The certificate of the key, embedded in every signature.
+    certificate: Certificate,
+}
+
+impl SigningKey {
+    /// Loads a signing key from a PEM file or a PKCS#12 keystore.
+    ///
+    /// PEM files must contain an unencrypted RSA private key (`PRIVATE KEY` or `RSA PRIVATE KEY`)
+    /// followed or preceded by its certificate. Any other file is read as a PKCS#12 keystore
+    /// (`.p12`/`.pfx`), from which the first private key and its certificate are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the key file.
+    /// * `password` - The keystore password, if any. Ignored for PEM files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, contains no usable key or
+    /// certificate, or if the key does not belong to the certificate.
+    pub fn load(path: &Path, password: Option<&str>) -> Result<Self> {
+        let data = fs::read(path)?;
+        let key = if data.starts_with(b"-----BEGIN") {
+            Self::from_pem(&data)?
+        } else {
+            Self::from_pkcs12(&data, password.unwrap_or_default())?
+        };
+        key.check_certificate()?;
+        Ok(key)
+    }
+
+    /// Loads the generated debug key, generating and caching it first if necessary.
+    ///
+    /// The debug key is stored in the configuration directory, so all archives signed with it
+    /// share the same certificate and can be updated in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if key generation fails or the cached key cannot be parsed or saved.
+    pub fn load_or_create_debug() -> Result<Self> {
+        let mut cache = FileBackedCache::<String, StoredSigningKey>::new(SIGNING_KEY_CACHE_NAME);
+        if let Some(stored) = cache.get(&DEBUG_KEY_NAME.to_string()) {
+            let mut pem_data = stored.private_key.clone().into_bytes();
+            pem_data.extend_from_slice(stored.certificate.as_bytes());
+            return Self::from_pem(&pem_data);
+        }
+
+        info!("Generating debug signing key...");
+        let key = Self::generate_self_signed(DEBUG_CERTIFICATE_SUBJECT)?;
+        let stored = StoredSigningKey {
+            private_key: pem::encode(&pem::Pem::new(
+                "PRIVATE KEY",
+                key.private_key.to_pkcs8_der()?.as_bytes(),
+            )),
+            certificate: pem::encode(&pem::Pem::new("CERTIFICATE", key.certificate_der()?)),
+        };
+        cache.insert(DEBUG_KEY_NAME.to_string(), stored)?;
+        Ok(key)
+    }
+
+    /// Returns the certificate of the key.
+    pub fn certificate(&self) -> &Certificate {
+        &self.certificate
+    }
+
+    /// Returns the DER encoding of the certificate.
+    pub fn certificate_der(&self) -> Result<Vec<u8>> {
+        Ok(self.certificate.to_der()?)
+    }
+
+    /// Returns the DER encoding of the public key as an X.509 `SubjectPublicKeyInfo`.
+    pub fn public_key_der(&self) -> Result<Vec<u8>> {
+        Ok(self
+            .certificate
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()?)
+    }
+
+    /// Signs data with RSASSA-PKCS1-v1_5 using SHA-256.
+    pub fn sign_sha256(&self, data: &[u8]) -> Vec<u8> {
+        pkcs1v15::SigningKey::<Sha256>::new(self.private_key.clone())
+            .sign(data)
+            .to_vec()
+    }
+
+    /// Parses a private key and certificate from a file containing several PEM blocks.
+    fn from_pem(data: &[u8]) -> Result<Self> {
+        let mut private_key = None;
+        let mut certificate = None;
+        for block in pem::parse_many(data)? {
+            match block.tag() {
+                "PRIVATE KEY" => {
+                    private_key = Some(RsaPrivateKey::from_pkcs8_der(block.contents())?)
+                }
+                "RSA PRIVATE KEY" => {
+                    private_key = Some(RsaPrivateKey::from_pkcs1_der(block.contents())?)
+                }
+                "ENCRYPTED PRIVATE KEY" => {
+                    bail!("Encrypted PEM keys are not supported, use a PKCS#12 keystore instead")
+                }
+                // Only the first certificate is the signer's, the rest is its chain.
+                "CERTIFICATE" if certificate.is_none() => {
+                    certificate = Some(Certificate::from_der(block.contents())?)
+                }
+                _ => {}
+            }
+        }
+        Ok(SigningKey {
+            private_key: private_key.ok_or(anyhow!("No RSA private key found in PEM file"))?,
+            certificate: certificate.ok_or(anyhow!("No certificate found in PEM file"))?,
+        })
+    }
+
+    /// Parses the first private key and its certificate from a PKCS#12 keystore.
+    fn from_pkcs12(data: &[u8], password: &str) -> Result<Self> {
+        let keystore = KeyStore::from_pkcs12(data, password)
+            .map_err(|err| anyhow!("Failed to read PKCS#12 keystore: {}", err))?;
+        let (alias, chain) = keystore
+            .private_key_chain()
+            .ok_or(anyhow!("No private key found in PKCS#12 keystore"))?;
+        let certificate = chain.chain().first().ok_or(anyhow!(
+            "No certificate found for key {} in keystore",
+            alias
+        ))?;
+        Ok(SigningKey {
+            private_key: RsaPrivateKey::from_pkcs8_der(chain.key())
+                .map_err(|_| anyhow!("Key {} is not an RSA key", alias))?,
+            certificate: Certificate::from_der(certificate.as_der())?,
+        })
+    }
+
+    /// Generates a new RSA key with a self-signed certificate for the given subject.
+    fn generate_self_signed(subject: &str) -> Result<Self> {
+        let private_key = RsaPrivateKey::new(&mut OsRng, DEBUG_KEY_BITS)?;
+        let signer = pkcs1v15::SigningKey::<Sha256>::new(private_key.clone());
+        let public_key_info = SubjectPublicKeyInfoOwned::from_key(private_key.to_public_key())?;
+
+        // Serial numbers must be positive, so the top bit of the first byte is cleared.
+        let mut serial = [0u8; 8];
+        OsRng.fill_bytes(&mut serial);
+        serial[0] &= 0x7F;
+
+        // A self-issued leaf certificate, since a CA profile restricts the key usage to
+        // certificate signing, which verifiers reject for code signatures.
+        let subject = Name::from_str(subject)?;
+        let certificate = CertificateBuilder::new(
+            Profile::Leaf {
+                issuer: subject.clone(),
+                enable_key_agreement: false,
+                enable_key_encipherment: false,
+            },
+            SerialNumber::new(&serial)?,
+            Validity::from_now(DEBUG_CERTIFICATE_VALIDITY)?,
+            subject,
+            public_key_info,
+            &signer,
+        )?
+        .build::<pkcs1v15::Signature>()?;
+
+        Ok(SigningKey {
+            private_key,
+            certificate,
+        })
+    }
+
+    /// Ensures the certificate was issued for the private key.
+    fn check_certificate(&self) -> Result<()> {
+        let key_public_key = self.private_key.to_public_key().to_public_key_der()?;
+        if key_public_key.as_bytes() != self.public_key_der()? {
+            bail!("The private key does not match the certificate");
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,3 @@
 #![allow(dead_code)]
 
-pub mod il2cpp_code_hasher;
\ No newline at end of file
+pub mod il2cpp_code_hasher;
@@ -14,7 +14,6 @@ pub(crate) mod proto;
 pub(crate) mod unity;
 pub(crate) mod utils;
 
-use crate::unity::generated::SUPPORTED_VERSION_NAME;
 use anyhow::Result;
 use const_format::formatcp;
 
@@ -22,11 +21,7 @@ use const_format::formatcp;
 ///
 /// This constant holds the application title and includes the build version
 /// extracted from the environment variable `VERSION`.
-pub const TITLE: &str = formatcp!(
-    "Pokemon TCG Pocket Tool - v{} ({})",
-    env!("VERSION"),
-    SUPPORTED_VERSION_NAME
-);
+pub const TITLE: &str = formatcp!("Pokemon TCG Pocket Tool - v{}", env!("VERSION"));
 
 /// Main entry point for the CLI application.
 ///
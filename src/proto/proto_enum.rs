@@ -41,10 +41,13 @@ impl ProtoEnum {
     ///
     /// * `name` - The name of the variant.
     /// * `number` - The numeric tag associated with the variant.
-    pub fn add_variant(&mut self, name: &str, number: i32) {
+    /// * `original_name` - The exact name of the variant in the proto file, if known from its
+    ///   `OriginalName` attribute.
+    pub fn add_variant(&mut self, name: &str, number: i32, original_name: Option<String>) {
         let enum_name = format!("{}_{}", self.name, name);
-        self.variants
-            .insert(name.into(), ProtoEnumVariant::new(&enum_name, number));
+        let mut variant = ProtoEnumVariant::new(&enum_name, number);
+        variant.original_name = original_name;
+        self.variants.insert(name.into(), variant);
     }
 }
 
@@ -55,6 +58,8 @@ pub struct ProtoEnumVariant {
     pub name: String,
     /// The numeric tag associated with the variant.
     pub tag: i32,
+    /// The exact name of the variant in the proto file, written instead of `name` when known.
+    pub original_name: Option<String>,
 }
 
 impl ProtoEnumVariant {
@@ -72,6 +77,7 @@ impl ProtoEnumVariant {
         Self {
             name: name.into(),
             tag,
+            original_name: None,
         }
     }
 }
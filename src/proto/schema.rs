@@ -195,10 +195,7 @@ impl ProtoSchema {
     /// # Returns
     ///
     /// A vector of `ProtoGenFile` instances representing enum definitions.
-    fn build_enums_for_package(
-        &self,
-        package: &ProtoPackage,
-    ) -> Result<Vec<ProtoGenFile>> {
+    fn build_enums_for_package(&self, package: &ProtoPackage) -> Result<Vec<ProtoGenFile>> {
         let mut files = Vec::new();
         for en in &package.enums {
             let filename = self.get_formatted_filename(en.type_index)?;
@@ -227,10 +224,7 @@ impl ProtoSchema {
     /// # Returns
     ///
     /// A vector of `ProtoGenFile` instances representing message definitions.
-    fn build_messages_for_package(
-        &self,
-        package: &ProtoPackage,
-    ) -> Result<Vec<ProtoGenFile>> {
+    fn build_messages_for_package(&self, package: &ProtoPackage) -> Result<Vec<ProtoGenFile>> {
         let mut files = Vec::new();
         for msg_group in package.msg_groups.as_ref().unwrap() {
             let filename = self.get_formatted_filename(msg_group.get_primary().type_index)?;
@@ -271,10 +265,7 @@ impl ProtoSchema {
     /// # Returns
     ///
     /// A vector of `ProtoGenFile` instances representing service definitions.
-    fn build_services_for_package(
-        &self,
-        package: &ProtoPackage,
-    ) -> Result<Vec<ProtoGenFile>> {
+    fn build_services_for_package(&self, package: &ProtoPackage) -> Result<Vec<ProtoGenFile>> {
         let mut files = Vec::new();
         for svc in &package.services {
             let filename = self.get_formatted_filename(svc.type_index)?;
@@ -1,4 +1,3 @@
-
 mod field;
 mod map;
 mod message;
@@ -35,20 +35,18 @@ impl ProtoEnum {
 impl ProtoEnumVariant {
     /// Formats the enum variant in a human-readable form with the specified indentation.
     ///
-    /// The variant name is converted to SHOUTY_SNAKE_CASE.
+    /// The original name of the variant is written as is when known; otherwise the variant name is
+    /// converted to SHOUTY_SNAKE_CASE.
     ///
     /// # Parameters
     /// - `f`: A mutable reference to the string buffer where the formatted enum variant will be written.
     /// - `indent`: The number of spaces to indent the enum variant.
     pub fn fmt_pretty(&self, f: &mut String, indent: usize) -> fmt::Result {
-        writeln!(
-            f,
-            "{:width$}{} = {};",
-            "",
-            self.name.to_shouty_snake_case(),
-            self.tag,
-            width = indent
-        )
+        let name = match &self.original_name {
+            Some(original_name) => original_name.clone(),
+            None => self.name.to_shouty_snake_case(),
+        };
+        writeln!(f, "{:width$}{} = {};", "", name, self.tag, width = indent)
     }
 
     /// Returns a pretty-formatted string representation of the enum variant.
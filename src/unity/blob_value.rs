@@ -1,5 +1,8 @@
-use crate::unity::generated::CIl2Cpp::{Il2CppType, Il2CppTypeEnum};
-use anyhow::{anyhow, Result};
+use crate::unity::generated::CIl2Cpp::{
+    Il2CppType, Il2CppTypeEnum, TypeIndex, IL2CPP_TYPE_ENUM, IL2CPP_TYPE_VALUETYPE,
+};
+use crate::unity::il2cpp::Il2Cpp;
+use anyhow::{anyhow, bail, Result};
 
 /// Enumerates the various data types that can be stored in a blob value.
 /// This enum encapsulates primitive types (e.g. integers, floats, booleans, characters),
@@ -89,3 +92,275 @@ impl BlobValue {
         }
     }
 }
+
+/// Sequential reader over blob data, such as custom attribute data or default value data.
+///
+/// The metadata read helpers take an offset and do not report how many bytes they consumed, so
+/// this reader tracks the position and checks bounds before reading, turning truncated or corrupt
+/// blobs into errors instead of panics.
+pub(crate) struct BlobReader<'d> {
+    /// The blob data being decoded.
+    data: &'d [u8],
+    /// The offset of the next value in `data`.
+    offset: usize,
+}
+
+impl<'d> BlobReader<'d> {
+    /// Creates a reader over `data`, starting at `offset`.
+    pub(crate) fn new(data: &'d [u8], offset: usize) -> Self {
+        Self { data, offset }
+    }
+
+    /// Ensures that `len` bytes can be read at the current offset and returns the current offset.
+    fn take(&mut self, len: usize) -> Result<usize> {
+        let offset = self.offset;
+        if offset + len > self.data.len() {
+            bail!(
+                "Blob data is truncated at offset {} (needs {} bytes, has {})",
+                offset,
+                len,
+                self.data.len() - offset.min(self.data.len())
+            );
+        }
+        self.offset += len;
+        Ok(offset)
+    }
+
+    /// Returns the number of bytes of the compressed integer at the current offset.
+    fn compressed_len(&self) -> Result<usize> {
+        let first = *self
+            .data
+            .get(self.offset)
+            .ok_or_else(|| anyhow!("Blob data is truncated at offset {}", self.offset))?;
+        match first {
+            0x00..=0x7F | 0xFE | 0xFF => Ok(1),
+            0x80..=0xBF => Ok(2),
+            0xC0..=0xDF => Ok(4),
+            0xF0 => Ok(5),
+            _ => bail!(
+                "Invalid compressed integer in blob data: byte = 0x{:02X}",
+                first
+            ),
+        }
+    }
+
+    pub(crate) fn read_u8(&mut self, il2cpp: &Il2Cpp) -> Result<u8> {
+        let offset = self.take(1)?;
+        Ok(il2cpp.metadata.read_u8(self.data, offset))
+    }
+
+    pub(crate) fn read_u32(&mut self, il2cpp: &Il2Cpp) -> Result<u32> {
+        let offset = self.take(4)?;
+        Ok(il2cpp.metadata.read_u32(self.data, offset))
+    }
+
+    pub(crate) fn read_compressed_u32(&mut self, il2cpp: &Il2Cpp) -> Result<u32> {
+        let offset = self.take(self.compressed_len()?)?;
+        Ok(il2cpp.metadata.read_compressed_u32(self.data, offset))
+    }
+
+    pub(crate) fn read_compressed_i32(&mut self, il2cpp: &Il2Cpp) -> Result<i32> {
+        let offset = self.take(self.compressed_len()?)?;
+        Ok(il2cpp.metadata.read_compressed_i32(self.data, offset))
+    }
+}
+
+impl<'a> Il2Cpp<'a> {
+    /// Reads a value of the given type from blob data, such as the default value of a field or
+    /// parameter.
+    ///
+    /// Values are encoded like in custom attribute data: 4-byte integers are compressed, and
+    /// strings and arrays are prefixed with their compressed length.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The blob data, such as `field_and_parameter_default_value_data`.
+    /// * `offset` - The offset of the value in `data`.
+    /// * `ty` - The type of the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type is not supported in blobs, or if the data is truncated or
+    /// refers to types that do not exist.
+    pub fn read_blob_value(
+        &'a self,
+        data: &[u8],
+        offset: usize,
+        ty: Il2CppTypeEnum,
+    ) -> Result<BlobValue> {
+        self.read_blob_value_of_type(&mut BlobReader::new(data, offset), ty)
+    }
+
+    /// Decodes the default value of a field or parameter from
+    /// `field_and_parameter_default_value_data`.
+    ///
+    /// Constants of enum types are stored as their underlying type, so their values decode as
+    /// plain integers.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_index` - The type of the default value, from `Il2CppFieldDefaultValue` or
+    ///   `Il2CppParameterDefaultValue`.
+    /// * `data_index` - The offset of the value in the default value data.
+    ///
+    /// # Returns
+    ///
+    /// The decoded value, or `None` for null references, which have a data index of -1.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type does not exist or is not supported in blobs, or if the data
+    /// is truncated.
+    pub fn read_default_value(
+        &'a self,
+        type_index: TypeIndex,
+        data_index: i32,
+    ) -> Result<Option<BlobValue>> {
+        if data_index < 0 {
+            return Ok(None);
+        }
+        let ty = self
+            .types
+            .get(type_index as usize)
+            .ok_or_else(|| anyhow!("Default value type {} does not exist", type_index))?;
+        let value = self.read_blob_value(
+            &self.metadata.field_and_parameter_default_value_data,
+            data_index as usize,
+            self.constant_type(ty),
+        )?;
+        Ok(Some(value))
+    }
+
+    /// Returns the type a constant of the given type is stored as, which is the underlying type
+    /// for enums.
+    fn constant_type(&self, ty: &Il2CppType) -> Il2CppTypeEnum {
+        let type_enum = ty.type_();
+        if type_enum != IL2CPP_TYPE_VALUETYPE {
+            return type_enum;
+        }
+        let klass_index = unsafe { ty.data.__klassIndex };
+        self.metadata
+            .type_definitions
+            .get(klass_index as usize)
+            .filter(|ty_def| ty_def.is_enum_type())
+            .and_then(|ty_def| self.types.get(ty_def.elementTypeIndex as usize))
+            .map_or(type_enum, |element_type| element_type.type_())
+    }
+
+    /// Reads an encoded type followed by a value of that type, like the arguments in custom
+    /// attribute data.
+    pub(crate) fn read_encoded_blob_value(&'a self, reader: &mut BlobReader) -> Result<BlobValue> {
+        let (ty, enum_type) = self.read_blob_type(reader)?;
+        let mut value = self.read_blob_value_of_type(reader, ty)?;
+        value.enum_type = enum_type;
+        Ok(value)
+    }
+
+    /// Reads an encoded type, resolving enums to their underlying type like
+    /// [`Il2Cpp::read_encoded_type_enum`], but checking the enum type index.
+    fn read_blob_type(
+        &'a self,
+        reader: &mut BlobReader,
+    ) -> Result<(Il2CppTypeEnum, Option<Il2CppType>)> {
+        let ty = reader.read_u8(self)? as Il2CppTypeEnum;
+        if ty != IL2CPP_TYPE_ENUM {
+            return Ok((ty, None));
+        }
+
+        let type_index = reader.read_compressed_i32(self)?;
+        let enum_type = self
+            .types
+            .get(type_index as usize)
+            .ok_or_else(|| anyhow!("Blob enum type {} does not exist", type_index))?;
+        let klass_index = unsafe { enum_type.data.__klassIndex };
+        let element_type = self
+            .metadata
+            .type_definitions
+            .get(klass_index as usize)
+            .and_then(|ty_def| self.types.get(ty_def.elementTypeIndex as usize))
+            .ok_or_else(|| anyhow!("Blob enum type {} is invalid", type_index))?;
+        Ok((element_type.type_(), Some(**(*enum_type))))
+    }
+
+    /// Reads a value of the given type at the position of the reader.
+    ///
+    /// Follows the layout read by `Il2CppType::get_value`, while advancing past each value so that
+    /// arrays and consecutive values decode correctly.
+    fn read_blob_value_of_type(
+        &'a self,
+        reader: &mut BlobReader,
+        ty: Il2CppTypeEnum,
+    ) -> Result<BlobValue> {
+        use crate::unity::generated::CIl2Cpp::*;
+
+        let metadata = &self.metadata;
+        let value = match ty {
+            IL2CPP_TYPE_BOOLEAN => BlobValueData::Boolean(reader.read_u8(self)? != 0),
+            IL2CPP_TYPE_U1 => BlobValueData::U1(reader.read_u8(self)?),
+            IL2CPP_TYPE_I1 => BlobValueData::I1(reader.read_u8(self)? as i8),
+            IL2CPP_TYPE_CHAR => {
+                let num = metadata.read_u16(reader.data, reader.take(2)?);
+                BlobValueData::Char(
+                    std::char::from_u32(num as u32)
+                        .ok_or_else(|| anyhow!("Invalid char value: {}", num))?,
+                )
+            }
+            IL2CPP_TYPE_U2 => BlobValueData::U2(metadata.read_u16(reader.data, reader.take(2)?)),
+            IL2CPP_TYPE_I2 => BlobValueData::I2(metadata.read_i16(reader.data, reader.take(2)?)),
+            IL2CPP_TYPE_U4 => BlobValueData::U4(reader.read_compressed_u32(self)?),
+            IL2CPP_TYPE_I4 => BlobValueData::I4(reader.read_compressed_i32(self)?),
+            IL2CPP_TYPE_U8 => BlobValueData::U8(metadata.read_u64(reader.data, reader.take(8)?)),
+            IL2CPP_TYPE_I8 => BlobValueData::I8(metadata.read_i64(reader.data, reader.take(8)?)),
+            IL2CPP_TYPE_R4 => BlobValueData::R4(metadata.read_f32(reader.data, reader.take(4)?)),
+            IL2CPP_TYPE_R8 => BlobValueData::R8(metadata.read_f64(reader.data, reader.take(8)?)),
+            IL2CPP_TYPE_STRING => {
+                // A length of -1 indicates a null string.
+                let length = reader.read_compressed_i32(self)?;
+                if length < 0 {
+                    BlobValueData::String(String::new())
+                } else {
+                    let offset = reader.take(length as usize)?;
+                    let bytes = &reader.data[offset..offset + length as usize];
+                    BlobValueData::String(String::from_utf8_lossy(bytes).into_owned())
+                }
+            }
+            IL2CPP_TYPE_SZARRAY => {
+                // A length of -1 indicates a null array.
+                let array_len = reader.read_compressed_i32(self)?;
+                if array_len < 0 {
+                    BlobValueData::Array(Vec::new())
+                } else {
+                    let (array_element_type, array_enum_type) = self.read_blob_type(reader)?;
+                    let array_elements_are_different = reader.read_u8(self)? == 1;
+                    let mut array = Vec::with_capacity((array_len as usize).min(reader.data.len()));
+                    for _ in 0..array_len {
+                        // Elements of an object array each carry their own type.
+                        let (element_type, enum_type) = if array_elements_are_different {
+                            self.read_blob_type(reader)?
+                        } else {
+                            (array_element_type, array_enum_type)
+                        };
+                        let mut element = self.read_blob_value_of_type(reader, element_type)?;
+                        element.enum_type = enum_type;
+                        array.push(element);
+                    }
+                    BlobValueData::Array(array)
+                }
+            }
+            IL2CPP_TYPE_IL2CPP_TYPE_INDEX => {
+                let type_index = reader.read_compressed_i32(self)?;
+                if type_index < 0 {
+                    BlobValueData::TypeIndex(None)
+                } else {
+                    BlobValueData::TypeIndex(self.types.get(type_index as usize).map(|t| *(**t)))
+                }
+            }
+            _ => bail!("Unsupported type in blob data: {:?}", ty),
+        };
+        Ok(BlobValue {
+            il2cpp_type_enum: ty,
+            enum_type: None,
+            value,
+        })
+    }
+}
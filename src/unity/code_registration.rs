@@ -0,0 +1,406 @@
+use crate::unity::generated::CIl2Cpp::{
+    Il2CppCodeGenModule, Il2CppGenericMethodFunctionsDefinitions, Il2CppInteropData,
+    Il2CppTokenAdjustorThunkPair, Il2CppTokenIndexMethodTuple,
+};
+use crate::unity::il2cpp::Il2Cpp;
+use anyhow::{anyhow, Result};
+use hashbrown::HashMap;
+use log::warn;
+use nohash_hasher::IntMap;
+use std::mem::size_of;
+
+/// Marks the generic method index of a reverse P/Invoke wrapper of a method definition.
+const NO_GENERIC_METHOD_INDEX: u32 = u32::MAX;
+
+/// The kind of generated code a code entry points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeKind {
+    /// The code of a method definition.
+    Method,
+    /// The code of a generic method instance.
+    GenericMethod,
+    /// A thunk that unboxes `this` before calling a method of a value type.
+    AdjustorThunk,
+    /// A function that calls methods of one signature with boxed arguments, used for
+    /// reflection and delegates.
+    Invoker,
+    /// A native callable wrapper of a method marked with `MonoPInvokeCallback`.
+    ReversePInvokeWrapper,
+    /// A stub for virtual calls to a signature without generated code.
+    UnresolvedVirtualCall,
+    /// A stub for instance calls to a signature without generated code.
+    UnresolvedInstanceCall,
+    /// A stub for static calls to a signature without generated code.
+    UnresolvedStaticCall,
+    /// A wrapper that calls a native function through a delegate.
+    DelegatePInvokeWrapper,
+    /// A function that marshals a type to its native representation.
+    MarshalToNative,
+    /// A function that marshals a type from its native representation.
+    MarshalFromNative,
+    /// A function that frees the native representation of a type.
+    MarshalCleanup,
+    /// A function that creates a COM callable wrapper of a type.
+    CreateCcw,
+}
+
+/// A named code address of the IL2CPP binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeEntry {
+    /// The virtual address of the code.
+    pub address: u64,
+    /// The kind of code.
+    pub kind: CodeKind,
+    /// The name of the code, in the style of the C++ code generated by IL2CPP.
+    pub name: String,
+}
+
+impl<'a> Il2Cpp<'a> {
+    /// Resolves every code address listed by the code and metadata registrations.
+    ///
+    /// Besides the method definitions, this covers the generic method instances of the
+    /// `genericMethodTable` and their adjustor thunks, the adjustor thunks and reverse P/Invoke
+    /// wrappers of each code generation module, the invokers, the unresolved indirect call stubs
+    /// and the marshaling functions of the interop data.
+    ///
+    /// Generic method instances are named after their method spec, with the type arguments of
+    /// the instance. Invokers and unresolved call stubs are shared by every method with the same
+    /// signature, so they are named after their index, like `RuntimeInvoker_3`. Entries whose
+    /// name cannot be resolved are skipped with a warning.
+    ///
+    /// # Returns
+    ///
+    /// The entries, starting with the method definitions. Code shared by several methods has one
+    /// entry per method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method pointers, the code generation modules or a method
+    /// definition name cannot be resolved.
+    pub fn code_entries(&'a self) -> Result<Vec<CodeEntry>> {
+        let mut entries = Vec::new();
+        for (method_idx, method_ptr) in self.method_pointers()?.into_iter().enumerate() {
+            if let Some(address) = method_ptr {
+                entries.push(CodeEntry {
+                    address,
+                    kind: CodeKind::Method,
+                    name: self.method_full_name(method_idx)?,
+                });
+            }
+        }
+        self.push_generic_method_entries(&mut entries);
+        self.push_module_entries(&mut entries)?;
+        self.push_invoker_entries(&mut entries);
+        self.push_unresolved_call_entries(&mut entries);
+        self.push_interop_entries(&mut entries);
+        Ok(entries)
+    }
+
+    /// Adds the generic method instances of the `genericMethodTable` and their adjustor thunks.
+    fn push_generic_method_entries(&'a self, entries: &mut Vec<CodeEntry>) {
+        let code_reg = &self.code_registration;
+        let metadata_reg = &self.metadata_registration;
+        let method_pointers = self.elf.read_pointer_array(
+            code_reg.genericMethodPointers as u64,
+            code_reg.genericMethodPointersCount as usize,
+        );
+        let adjustor_thunks = if code_reg.genericAdjustorThunks.is_null() {
+            Vec::new()
+        } else {
+            // The adjustor thunks share the indices of the generic method pointers.
+            self.elf.read_pointer_array(
+                code_reg.genericAdjustorThunks as u64,
+                code_reg.genericMethodPointersCount as usize,
+            )
+        };
+
+        for table_idx in 0..metadata_reg.genericMethodTableCount.max(0) as usize {
+            let Ok(functions) = self.load_array_element::<Il2CppGenericMethodFunctionsDefinitions>(
+                metadata_reg.genericMethodTable as u64,
+                table_idx,
+            ) else {
+                warn!("Generic method table entry {} cannot be read", table_idx);
+                continue;
+            };
+            let indices = &functions.indices;
+            let address = pointer_at(&method_pointers, indices.methodIndex);
+            let thunk_address = pointer_at(&adjustor_thunks, indices.adjustorThunkIndex);
+            if address.is_none() && thunk_address.is_none() {
+                continue;
+            }
+
+            let name = match self.method_spec_full_name(functions.genericMethodIndex as usize) {
+                Ok(name) => name,
+                Err(err) => {
+                    warn!(
+                        "Skipping generic method {}: {}",
+                        functions.genericMethodIndex, err
+                    );
+                    continue;
+                }
+            };
+            if let Some(address) = thunk_address {
+                entries.push(CodeEntry {
+                    address,
+                    kind: CodeKind::AdjustorThunk,
+                    name: format!("{}_AdjustorThunk", name),
+                });
+            }
+            if let Some(address) = address {
+                entries.push(CodeEntry {
+                    address,
+                    kind: CodeKind::GenericMethod,
+                    name,
+                });
+            }
+        }
+    }
+
+    /// Adds the adjustor thunks and reverse P/Invoke wrappers of each code generation module.
+    ///
+    /// Both tables refer to methods by their token, which is only unique within the image of
+    /// the module.
+    fn push_module_entries(&'a self, entries: &mut Vec<CodeEntry>) -> Result<()> {
+        let code_reg = &self.code_registration;
+        let code_gen_modules = self.load_data_array::<Il2CppCodeGenModule>(
+            code_reg.codeGenModules,
+            code_reg.codeGenModulesCount as usize,
+        )?;
+        let image_methods = self.image_method_tokens();
+        let reverse_wrappers = self.elf.read_pointer_array(
+            code_reg.reversePInvokeWrappers as u64,
+            code_reg.reversePInvokeWrapperCount as usize,
+        );
+
+        for module in code_gen_modules {
+            let module_name = self.elf.read_va_str(module.moduleName as u64, None)?;
+            let Some(methods) = image_methods.get(&module_name) else {
+                continue;
+            };
+            let method_name = |token: u32| {
+                let method_idx = *methods
+                    .get(&token)
+                    .ok_or_else(|| anyhow!("Method token 0x{:X} does not exist", token))?;
+                self.method_full_name(method_idx)
+            };
+
+            for thunk_idx in 0..module.adjustorThunkCount as usize {
+                let Ok(pair) = self.load_array_element::<Il2CppTokenAdjustorThunkPair>(
+                    module.adjustorThunks as u64,
+                    thunk_idx,
+                ) else {
+                    break;
+                };
+                let Some(address) = pair.adjustorThunk.map(|f| f as usize as u64) else {
+                    continue;
+                };
+                match method_name(pair.token) {
+                    Ok(name) => entries.push(CodeEntry {
+                        address,
+                        kind: CodeKind::AdjustorThunk,
+                        name: format!("{}_AdjustorThunk", name),
+                    }),
+                    Err(err) => warn!("Skipping adjustor thunk at 0x{:X}: {}", address, err),
+                }
+            }
+
+            for tuple_idx in 0..module.reversePInvokeWrapperCount as usize {
+                let Ok(tuple) = self.load_array_element::<Il2CppTokenIndexMethodTuple>(
+                    module.reversePInvokeWrapperIndices as u64,
+                    tuple_idx,
+                ) else {
+                    break;
+                };
+                let Some(address) = pointer_at(&reverse_wrappers, tuple.index) else {
+                    continue;
+                };
+                let name = if tuple.__genericMethodIndex == NO_GENERIC_METHOD_INDEX {
+                    method_name(tuple.token)
+                } else {
+                    self.method_spec_full_name(tuple.__genericMethodIndex as usize)
+                };
+                match name {
+                    Ok(name) => entries.push(CodeEntry {
+                        address,
+                        kind: CodeKind::ReversePInvokeWrapper,
+                        name: format!("ReversePInvokeWrapper_{}", name),
+                    }),
+                    Err(err) => warn!(
+                        "Skipping reverse P/Invoke wrapper at 0x{:X}: {}",
+                        address, err
+                    ),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the invokers, named after their index in `invokerPointers`.
+    fn push_invoker_entries(&self, entries: &mut Vec<CodeEntry>) {
+        let code_reg = &self.code_registration;
+        let invokers = self.elf.read_pointer_array(
+            code_reg.invokerPointers as u64,
+            code_reg.invokerPointersCount as usize,
+        );
+        entries.extend(
+            invokers
+                .into_iter()
+                .enumerate()
+                .filter(|&(_, address)| address != 0)
+                .map(|(invoker_idx, address)| CodeEntry {
+                    address,
+                    kind: CodeKind::Invoker,
+                    name: format!("RuntimeInvoker_{}", invoker_idx),
+                }),
+        );
+    }
+
+    /// Adds the unresolved virtual, instance and static call stubs, named after their index in
+    /// the unresolved indirect call tables.
+    fn push_unresolved_call_entries(&self, entries: &mut Vec<CodeEntry>) {
+        let code_reg = &self.code_registration;
+        let tables = [
+            (
+                code_reg.unresolvedVirtualCallPointers,
+                CodeKind::UnresolvedVirtualCall,
+                "UnresolvedVirtualCall",
+            ),
+            (
+                code_reg.unresolvedInstanceCallPointers,
+                CodeKind::UnresolvedInstanceCall,
+                "UnresolvedInstanceCall",
+            ),
+            (
+                code_reg.unresolvedStaticCallPointers,
+                CodeKind::UnresolvedStaticCall,
+                "UnresolvedStaticCall",
+            ),
+        ];
+        for (table, kind, prefix) in tables {
+            if table.is_null() {
+                continue;
+            }
+            let stubs = self
+                .elf
+                .read_pointer_array(table as u64, code_reg.unresolvedIndirectCallCount as usize);
+            entries.extend(
+                stubs
+                    .into_iter()
+                    .enumerate()
+                    .filter(|&(_, address)| address != 0)
+                    .map(|(call_idx, address)| CodeEntry {
+                        address,
+                        kind,
+                        name: format!("{}_{}", prefix, call_idx),
+                    }),
+            );
+        }
+    }
+
+    /// Adds the delegate wrappers and marshaling functions of the interop data, named after the
+    /// type they belong to.
+    fn push_interop_entries(&'a self, entries: &mut Vec<CodeEntry>) {
+        let code_reg = &self.code_registration;
+        for interop_idx in 0..code_reg.interopDataCount as usize {
+            let Ok(interop) = self
+                .load_array_element::<Il2CppInteropData>(code_reg.interopData as u64, interop_idx)
+            else {
+                warn!("Interop data {} cannot be read", interop_idx);
+                break;
+            };
+            let type_name = self
+                .type_by_ptr(interop.type_ as u64)
+                .ok_or_else(|| anyhow!("Unknown interop type"))
+                .and_then(|ty| Ok(ty.get_complex_type(self)?.get_name_str(true)?));
+            let type_name = match type_name {
+                Ok(type_name) => type_name,
+                Err(err) => {
+                    warn!("Skipping interop data {}: {}", interop_idx, err);
+                    continue;
+                }
+            };
+
+            let functions = [
+                (
+                    interop
+                        .delegatePInvokeWrapperFunction
+                        .map(|f| f as usize as u64),
+                    CodeKind::DelegatePInvokeWrapper,
+                    format!("DelegatePInvokeWrapper_{}", type_name),
+                ),
+                (
+                    interop
+                        .pinvokeMarshalToNativeFunction
+                        .map(|f| f as usize as u64),
+                    CodeKind::MarshalToNative,
+                    format!("{}_marshal_pinvoke", type_name),
+                ),
+                (
+                    interop
+                        .pinvokeMarshalFromNativeFunction
+                        .map(|f| f as usize as u64),
+                    CodeKind::MarshalFromNative,
+                    format!("{}_marshal_pinvoke_back", type_name),
+                ),
+                (
+                    interop
+                        .pinvokeMarshalCleanupFunction
+                        .map(|f| f as usize as u64),
+                    CodeKind::MarshalCleanup,
+                    format!("{}_marshal_pinvoke_cleanup", type_name),
+                ),
+                (
+                    interop.createCCWFunction.map(|f| f as usize as u64),
+                    CodeKind::CreateCcw,
+                    format!("CreateComCallableWrapperFor_{}", type_name),
+                ),
+            ];
+            for (address, kind, name) in functions {
+                if let Some(address) = address {
+                    entries.push(CodeEntry {
+                        address,
+                        kind,
+                        name,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Maps the name of each image to the method definition index of each method token of the
+    /// image.
+    fn image_method_tokens(&self) -> HashMap<String, IntMap<u32, usize>> {
+        let metadata = &self.metadata;
+        let mut image_methods = HashMap::with_capacity(metadata.images.len());
+        for image in &metadata.images {
+            let mut methods = IntMap::default();
+            let type_end = image.typeStart as usize + image.typeCount as usize;
+            for ty_idx in image.typeStart as usize..type_end {
+                let ty_def = metadata.type_definitions[ty_idx];
+                let method_start = ty_def.methodStart as usize;
+                let method_range = method_start..method_start + ty_def.method_count as usize;
+                for (method_idx, method) in method_range
+                    .clone()
+                    .zip(metadata.methods.range(method_range))
+                {
+                    methods.insert(method.token, method_idx);
+                }
+            }
+            image_methods.insert(metadata.get_string_by_index(image.nameIndex), methods);
+        }
+        image_methods
+    }
+
+    /// Loads the element at the given index of an array of `T` stored in place, rather than as
+    /// an array of pointers.
+    fn load_array_element<T>(&'a self, array_ptr: u64, index: usize) -> Result<&'a T> {
+        self.load_data_instance::<T>(array_ptr + (index * size_of::<T>()) as u64)
+    }
+}
+
+/// Returns the non-null pointer at a signed index of a pointer table, or `None` for negative
+/// indices, which mark a missing entry.
+fn pointer_at(pointers: &[u64], index: i32) -> Option<u64> {
+    let pointer = *pointers.get(usize::try_from(index).ok()?)?;
+    (pointer != 0).then_some(pointer)
+}
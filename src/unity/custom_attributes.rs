@@ -0,0 +1,317 @@
+use crate::unity::blob_value::{BlobReader, BlobValue, BlobValueData};
+use crate::unity::generated::CIl2Cpp::{
+    FieldIndex, Il2CppImageDefinition, MethodIndex, TypeDefinitionIndex,
+};
+use crate::unity::il2cpp::Il2Cpp;
+use anyhow::{anyhow, bail, Result};
+
+/// A metadata entity that custom attributes can be applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeTarget {
+    /// A type definition, by its index in the type definitions.
+    Type(TypeDefinitionIndex),
+    /// A method definition, by its index in the method definitions.
+    Method(MethodIndex),
+    /// A field definition, by its index in the field definitions and the index of the type
+    /// definition declaring it, as fields do not record their declaring type.
+    Field {
+        declaring_type: TypeDefinitionIndex,
+        field: FieldIndex,
+    },
+}
+
+/// A field or property set by a custom attribute, such as `PreferredAlias` in
+/// `[OriginalName("FOO", PreferredAlias = false)]`.
+#[derive(Debug, Clone)]
+pub struct CustomAttributeNamedArgument {
+    /// The name of the field or property.
+    pub name: String,
+    /// The index of the field or property definition.
+    pub index: usize,
+    /// The value assigned to the field or property.
+    pub value: BlobValue,
+}
+
+/// A decoded custom attribute, with the arguments passed to its constructor.
+#[derive(Debug, Clone)]
+pub struct CustomAttribute {
+    /// The index of the type definition of the attribute.
+    pub type_index: TypeDefinitionIndex,
+    /// The namespace of the attribute type, such as `Google.Protobuf.Reflection`.
+    pub namespace: String,
+    /// The name of the attribute type, such as `OriginalNameAttribute`.
+    pub name: String,
+    /// The index of the method definition of the attribute constructor.
+    pub constructor: MethodIndex,
+    /// The positional constructor arguments.
+    pub arguments: Vec<BlobValue>,
+    /// The fields set by the attribute.
+    pub fields: Vec<CustomAttributeNamedArgument>,
+    /// The properties set by the attribute.
+    pub properties: Vec<CustomAttributeNamedArgument>,
+}
+
+impl CustomAttribute {
+    /// Returns the fully qualified name of the attribute type, such as
+    /// `Google.Protobuf.Reflection.OriginalNameAttribute`.
+    pub fn full_name(&self) -> String {
+        if self.namespace.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.namespace, self.name)
+        }
+    }
+
+    /// Returns the string value of the constructor argument at the given position, if the
+    /// attribute has a string argument there.
+    pub fn string_argument(&self, position: usize) -> Option<&str> {
+        match &self.arguments.get(position)?.value {
+            BlobValueData::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Il2Cpp<'a> {
+    /// Decodes the custom attributes applied to a type, method or field.
+    ///
+    /// Attributes are looked up by the metadata token of the target in the attribute data ranges
+    /// of the image declaring it, and decoded from the version 29+ attribute blob: a compressed
+    /// attribute count, the constructor method index of each attribute, then for each attribute
+    /// its positional arguments, fields and properties.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The type, method or field to read the attributes of.
+    ///
+    /// # Returns
+    ///
+    /// The attributes in declaration order, or an empty vector if the target has none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target index is out of bounds, or if the attribute data is
+    /// truncated or refers to metadata that does not exist.
+    pub fn attributes_of(&'a self, target: AttributeTarget) -> Result<Vec<CustomAttribute>> {
+        let metadata = &self.metadata;
+        let (type_index, token) = match target {
+            AttributeTarget::Type(type_index) => {
+                let ty_def = metadata
+                    .type_definitions
+                    .get(type_index as usize)
+                    .ok_or_else(|| anyhow!("Type definition {} does not exist", type_index))?;
+                (type_index, ty_def.token)
+            }
+            AttributeTarget::Method(method_index) => {
+                if method_index < 0 || method_index as usize >= metadata.methods.len() {
+                    bail!("Method definition {} does not exist", method_index);
+                }
+                let method = metadata.methods.get(method_index as usize);
+                (method.declaring_type, method.token)
+            }
+            AttributeTarget::Field {
+                declaring_type,
+                field,
+            } => {
+                let field_def = metadata
+                    .fields
+                    .get(field as usize)
+                    .ok_or_else(|| anyhow!("Field definition {} does not exist", field))?;
+                (declaring_type, field_def.token)
+            }
+        };
+
+        let image = self.image_of_type(type_index)?;
+        let Some(data) = self.attribute_data_of(image, token)? else {
+            return Ok(Vec::new());
+        };
+        self.decode_attributes(data)
+    }
+
+    /// Returns the first attribute of the given fully qualified type applied to the target, such
+    /// as `Google.Protobuf.Reflection.OriginalNameAttribute`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the attributes of the target cannot be decoded.
+    pub fn find_attribute(
+        &'a self,
+        target: AttributeTarget,
+        full_name: &str,
+    ) -> Result<Option<CustomAttribute>> {
+        Ok(self
+            .attributes_of(target)?
+            .into_iter()
+            .find(|attribute| attribute.full_name() == full_name))
+    }
+
+    /// Returns the image whose type range contains the given type definition.
+    fn image_of_type(&self, type_index: TypeDefinitionIndex) -> Result<&Il2CppImageDefinition> {
+        let type_index = type_index as u32;
+        self.metadata
+            .images
+            .iter()
+            .find(|image| {
+                let start = image.typeStart as u32;
+                (start..start + image.typeCount).contains(&type_index)
+            })
+            .ok_or_else(|| anyhow!("No image declares type definition {}", type_index))
+    }
+
+    /// Returns the attribute blob of the entity with the given token in an image, if it has any
+    /// attributes.
+    ///
+    /// The data ranges of an image are sorted by token, and each blob ends where the data of the
+    /// next range starts.
+    fn attribute_data_of(
+        &self,
+        image: &Il2CppImageDefinition,
+        token: u32,
+    ) -> Result<Option<&[u8]>> {
+        let metadata = &self.metadata;
+        let ranges = &metadata.attribute_data_range;
+        let start = image.customAttributeStart as usize;
+        let end = start + image.customAttributeCount as usize;
+        let image_ranges = ranges.get(start..end).ok_or_else(|| {
+            anyhow!(
+                "Custom attribute ranges {}..{} of image {} do not exist",
+                start,
+                end,
+                metadata.get_string_by_index(image.nameIndex)
+            )
+        })?;
+        let Ok(position) = image_ranges.binary_search_by_key(&token, |range| range.token) else {
+            return Ok(None);
+        };
+
+        let index = start + position;
+        let data_start = ranges[index].startOffset as usize;
+        let data_end = ranges
+            .get(index + 1)
+            .map_or(metadata.attribute_data.len(), |next| {
+                next.startOffset as usize
+            });
+        let data = metadata
+            .attribute_data
+            .get(data_start..data_end)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Custom attribute data {}..{} for token 0x{:08X} is out of bounds",
+                    data_start,
+                    data_end,
+                    token
+                )
+            })?;
+        Ok(Some(data))
+    }
+
+    /// Decodes all attributes of an attribute blob.
+    fn decode_attributes(&'a self, data: &[u8]) -> Result<Vec<CustomAttribute>> {
+        let metadata = &self.metadata;
+        let mut reader = BlobReader::new(data, 0);
+        let count = reader.read_compressed_u32(self)? as usize;
+
+        // The constructors of all attributes come first, followed by the argument data.
+        let mut constructors = Vec::with_capacity(count.min(data.len() / 4));
+        for _ in 0..count {
+            constructors.push(reader.read_u32(self)? as usize);
+        }
+
+        let mut attributes = Vec::with_capacity(constructors.len());
+        for constructor in constructors {
+            if constructor >= metadata.methods.len() {
+                bail!(
+                    "Custom attribute constructor {} does not exist",
+                    constructor
+                );
+            }
+            let attribute_type = metadata.methods.get(constructor).declaring_type;
+            let ty_def = metadata
+                .type_definitions
+                .get(attribute_type as usize)
+                .ok_or_else(|| {
+                    anyhow!("Custom attribute type {} does not exist", attribute_type)
+                })?;
+
+            let argument_count = reader.read_compressed_u32(self)?;
+            let field_count = reader.read_compressed_u32(self)?;
+            let property_count = reader.read_compressed_u32(self)?;
+
+            let arguments = (0..argument_count)
+                .map(|_| self.read_encoded_blob_value(&mut reader))
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut fields = Vec::new();
+            for _ in 0..field_count {
+                let value = self.read_encoded_blob_value(&mut reader)?;
+                let (declaring_type, member) =
+                    self.read_named_argument_member(&mut reader, attribute_type)?;
+                let index =
+                    metadata.type_definitions[declaring_type as usize].fieldStart as usize + member;
+                let field = metadata
+                    .fields
+                    .get(index)
+                    .ok_or_else(|| anyhow!("Custom attribute field {} does not exist", index))?;
+                fields.push(CustomAttributeNamedArgument {
+                    name: metadata.get_string_by_index(field.nameIndex),
+                    index,
+                    value,
+                });
+            }
+
+            let mut properties = Vec::new();
+            for _ in 0..property_count {
+                let value = self.read_encoded_blob_value(&mut reader)?;
+                let (declaring_type, member) =
+                    self.read_named_argument_member(&mut reader, attribute_type)?;
+                let index = metadata.type_definitions[declaring_type as usize].propertyStart
+                    as usize
+                    + member;
+                let property = metadata
+                    .properties
+                    .get(index)
+                    .ok_or_else(|| anyhow!("Custom attribute property {} does not exist", index))?;
+                properties.push(CustomAttributeNamedArgument {
+                    name: metadata.get_string_by_index(property.nameIndex),
+                    index,
+                    value,
+                });
+            }
+
+            attributes.push(CustomAttribute {
+                type_index: attribute_type,
+                namespace: metadata.get_string_by_index(ty_def.namespaceIndex),
+                name: metadata.get_string_by_index(ty_def.nameIndex),
+                constructor: constructor as MethodIndex,
+                arguments,
+                fields,
+                properties,
+            });
+        }
+        Ok(attributes)
+    }
+
+    /// Reads the member index of a named argument, along with the type definition declaring the
+    /// member.
+    ///
+    /// Members of the attribute type itself are stored as a non-negative index; members inherited
+    /// from a base type are stored as `-(index + 1)`, followed by the declaring type definition.
+    fn read_named_argument_member(
+        &self,
+        reader: &mut BlobReader,
+        attribute_type: TypeDefinitionIndex,
+    ) -> Result<(TypeDefinitionIndex, usize)> {
+        let member = reader.read_compressed_i32(self)?;
+        if member >= 0 {
+            return Ok((attribute_type, member as usize));
+        }
+        let declaring_type = reader.read_compressed_u32(self)? as TypeDefinitionIndex;
+        if declaring_type < 0 || declaring_type as usize >= self.metadata.type_definitions.len() {
+            bail!(
+                "Custom attribute member type {} does not exist",
+                declaring_type
+            );
+        }
+        Ok((declaring_type, -(member + 1) as usize))
+    }
+}
@@ -0,0 +1,176 @@
+use crate::binary::elf::POINTER_SIZE;
+use crate::unity::generated::CIl2Cpp::{
+    FieldIndex, Il2CppTypeDefinition, Il2CppTypeDefinitionSizes, TypeDefinitionIndex,
+};
+use crate::unity::il2cpp::Il2Cpp;
+use anyhow::{anyhow, bail, Result};
+
+/// The field is static.
+pub const FIELD_ATTRIBUTE_STATIC: u32 = 0x10;
+/// The field is a compile-time constant, which has no storage.
+pub const FIELD_ATTRIBUTE_LITERAL: u32 = 0x40;
+
+/// Bit set in the stored offset of a thread static field.
+const THREAD_LOCAL_STATIC_MASK: u32 = 0x80000000;
+/// Size of the header at the start of every object: the class pointer and the monitor.
+const OBJECT_HEADER_SIZE: u32 = 2 * POINTER_SIZE as u32;
+
+/// Where the value of a field is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOffset {
+    /// An instance field, at this offset from the start of an object. For value types, the
+    /// offset is from the start of the unboxed value, without the object header.
+    Instance(u32),
+    /// A static field, at this offset in the static field block of its class.
+    Static(u32),
+    /// A thread static field, at this offset in the thread static field block of its class.
+    ThreadStatic(u32),
+}
+
+/// The sizes of the instances and static field blocks of a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeSizes {
+    /// The size of an instance including the object header, so for value types the size of
+    /// the boxed value.
+    pub instance_size: u32,
+    /// The size of the native representation used for marshaling, or -1 if it has none.
+    pub native_size: i32,
+    /// The size of the static field block.
+    pub static_fields_size: u32,
+    /// The size of the thread static field block.
+    pub thread_static_fields_size: u32,
+}
+
+impl From<&Il2CppTypeDefinitionSizes> for TypeSizes {
+    fn from(sizes: &Il2CppTypeDefinitionSizes) -> Self {
+        TypeSizes {
+            instance_size: sizes.instance_size,
+            native_size: sizes.native_size,
+            static_fields_size: sizes.static_fields_size,
+            thread_static_fields_size: sizes.thread_static_fields_size,
+        }
+    }
+}
+
+impl<'a> Il2Cpp<'a> {
+    /// Resolves where the value of a field is stored.
+    ///
+    /// The offsets are read from the `fieldOffsets` table of the metadata registration, which
+    /// points to the offsets of the fields of each type definition. Offsets of value type fields
+    /// are stored relative to the boxed value, and are returned relative to the unboxed value.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_index` - The type definition declaring the field.
+    /// * `field_index` - The index of the field in the field definitions.
+    ///
+    /// # Returns
+    ///
+    /// The offset of the field, or `None` for constants, which have no storage, and for fields
+    /// of generic type definitions, whose layout depends on the type arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type does not declare the field, or the offset table cannot be
+    /// read.
+    pub fn field_offset(
+        &'a self,
+        type_index: TypeDefinitionIndex,
+        field_index: FieldIndex,
+    ) -> Result<Option<FieldOffset>> {
+        let metadata = &self.metadata;
+        let ty_def = self.type_definition(type_index)?;
+        let field_start = ty_def.fieldStart;
+        if field_index < field_start || field_index >= field_start + ty_def.field_count as i32 {
+            bail!(
+                "Type definition {} does not declare field {}",
+                type_index,
+                field_index
+            );
+        }
+        let field = &metadata.fields[field_index as usize];
+        let attrs = self
+            .types
+            .get(field.typeIndex as usize)
+            .ok_or_else(|| anyhow!("Type {} does not exist", field.typeIndex))?
+            .attrs();
+        if attrs & FIELD_ATTRIBUTE_LITERAL != 0 {
+            return Ok(None);
+        }
+
+        let metadata_reg = &self.metadata_registration;
+        if type_index >= metadata_reg.fieldOffsetsCount {
+            bail!(
+                "Field offsets of type definition {} do not exist",
+                type_index
+            );
+        }
+        let table_ptr = self.read_pointer(metadata_reg.fieldOffsets as u64, type_index as usize)?;
+        if table_ptr == 0 {
+            return Ok(None);
+        }
+        let offset_ptr = table_ptr + ((field_index - field_start) as u64) * 4;
+        let offset = self
+            .elf
+            .read_bytes_at_va(offset_ptr, 4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or_else(|| anyhow!("Offset of field {} cannot be read", field_index))?;
+
+        let offset = if attrs & FIELD_ATTRIBUTE_STATIC == 0 {
+            if ty_def.is_value_type() {
+                FieldOffset::Instance(offset.saturating_sub(OBJECT_HEADER_SIZE))
+            } else {
+                FieldOffset::Instance(offset)
+            }
+        } else if offset & THREAD_LOCAL_STATIC_MASK != 0 {
+            FieldOffset::ThreadStatic(offset & !THREAD_LOCAL_STATIC_MASK)
+        } else {
+            FieldOffset::Static(offset)
+        };
+        Ok(Some(offset))
+    }
+
+    /// Returns the instance and static field block sizes of a type definition.
+    ///
+    /// The sizes are read from the `typeDefinitionsSizes` table of the metadata registration.
+    ///
+    /// # Returns
+    ///
+    /// The sizes, or `None` for generic type definitions, whose sizes depend on the type
+    /// arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition does not exist, or the size table cannot be read.
+    pub fn type_sizes(&'a self, type_index: TypeDefinitionIndex) -> Result<Option<TypeSizes>> {
+        self.type_definition(type_index)?;
+        let metadata_reg = &self.metadata_registration;
+        if type_index >= metadata_reg.typeDefinitionsSizesCount {
+            bail!("Sizes of type definition {} do not exist", type_index);
+        }
+        let sizes_ptr = self.read_pointer(
+            metadata_reg.typeDefinitionsSizes as u64,
+            type_index as usize,
+        )?;
+        if sizes_ptr == 0 {
+            return Ok(None);
+        }
+        let sizes = self.load_data_instance::<Il2CppTypeDefinitionSizes>(sizes_ptr)?;
+        Ok(Some(TypeSizes::from(sizes)))
+    }
+
+    /// Returns the type definition at the given index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition does not exist.
+    pub fn type_definition(
+        &self,
+        type_index: TypeDefinitionIndex,
+    ) -> Result<&Il2CppTypeDefinition> {
+        self.metadata
+            .type_definitions
+            .get(type_index as usize)
+            .ok_or_else(|| anyhow!("Type definition {} does not exist", type_index))
+    }
+}
@@ -6,38 +6,93 @@
 //!
 //! ## Supported Versions
 //!
-//! This module conditionally compiles bindings for different versions of the IL2CPP headers based on feature flags:
+//! Bindings for every supported version of the IL2CPP headers are compiled in, and the matching
+//! version is selected at runtime from the global metadata header (see [`UnityVersion`]):
 //!
-//! - **Version 2022322f1** (enabled with the `2022322f1` feature)
+//! - **Version 2022322f1** ([`il2cpp_2022322f1`])
 //!   - Corresponds to a supported global metadata version of **29**.
 //!
-//! - **Version 2022356f1** (enabled with the `2022356f1` feature)
+//! - **Version 2022356f1** ([`il2cpp_2022356f1`])
 //!   - Corresponds to a supported global metadata version of **31**.
 //!
-//! Choose the appropriate feature while building to match the Unity IL2CPP version you want to support.
+//! Most structures have the same layout in every supported version, so they are shared through
+//! [`CIl2Cpp`]. Structures whose layout differs between versions must not be used from
+//! [`CIl2Cpp`] directly, but through a version-agnostic accessor instead:
+//!
+//! - `Il2CppMethodDefinition` gained `returnParameterToken` in version 31, see
+//!   [`crate::unity::method_definitions`].
 //!
 //! ## Notes
 //!
 //! - The bindings are auto-generated and tailored to the specific version of the Unity IL2CPP headers.
 //! - If your project uses a different Unity IL2CPP version or requires additional customization,
 //!   you may need to adjust the generated bindings accordingly.
+//! - When adding a version, compare its header with the existing ones and add an accessor for
+//!   every structure whose layout changed.
 //!
 //! [bindgen]: https://github.com/rust-lang/rust-bindgen
 
+use std::fmt::{Display, Formatter};
+use strum::{EnumIter, IntoEnumIterator};
+
+// Keep the bindgen output byte-identical to the generator.
+#[rustfmt::skip]
+pub mod il2cpp_2022322f1;
+#[rustfmt::skip]
+pub mod il2cpp_2022356f1;
+
+/// Bindings whose layout is shared by all supported versions, taken from the latest version.
+pub use il2cpp_2022356f1::CIl2Cpp;
+
+/// A Unity version for which IL2CPP bindings have been generated.
+///
+/// The variants are declared from oldest to newest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum UnityVersion {
+    /// Unity 2022.3.22f1, using global metadata version 29.
+    Unity2022_3_22f1,
+    /// Unity 2022.3.56f1, using global metadata version 31.
+    Unity2022_3_56f1,
+}
+
+impl UnityVersion {
+    /// Returns the Unity version name (e.g. `2022.3.56f1`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            UnityVersion::Unity2022_3_22f1 => "2022.3.22f1",
+            UnityVersion::Unity2022_3_56f1 => "2022.3.56f1",
+        }
+    }
+
+    /// Returns the global metadata version written by this Unity version.
+    pub fn metadata_version(&self) -> i32 {
+        match self {
+            UnityVersion::Unity2022_3_22f1 => 29,
+            UnityVersion::Unity2022_3_56f1 => 31,
+        }
+    }
 
-mod il2cpp_2022322f1;
-mod il2cpp_2022356f1;
+    /// Determines the Unity version from the `version` field of a global metadata header.
+    ///
+    /// # Returns
+    /// - `Some(UnityVersion)` if bindings exist for the metadata version.
+    /// - `None` otherwise.
+    pub fn from_metadata_version(version: i32) -> Option<UnityVersion> {
+        UnityVersion::iter().find(|unity_version| unity_version.metadata_version() == version)
+    }
 
-#[cfg(feature = "2022322f1")]
-pub use il2cpp_2022322f1::*;
-#[cfg(feature = "2022322f1")]
-pub const SUPPORTED_GLOBAL_METADATA_VERSION: i32 = 29;
-#[cfg(feature = "2022322f1")]
-pub const SUPPORTED_VERSION_NAME: &str = "2022.3.22f1";
+    /// Returns the supported global metadata versions as a human-readable list (e.g. `29, 31`).
+    pub fn supported_metadata_versions() -> String {
+        UnityVersion::iter()
+            .map(|unity_version| unity_version.metadata_version().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
 
-#[cfg(feature = "2022356f1")]
-pub use il2cpp_2022356f1::*;
-#[cfg(feature = "2022356f1")]
-pub const SUPPORTED_GLOBAL_METADATA_VERSION: i32 = 31;
-#[cfg(feature = "2022356f1")]
-pub const SUPPORTED_VERSION_NAME: &str = "2022.3.56f1";
\ No newline at end of file
+impl Display for UnityVersion {
+    /// Formats the Unity version using its version name.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -4,12 +4,13 @@ use crate::unity::generated::CIl2Cpp::{
     FieldIndex, Il2CppAssemblyDefinition, Il2CppCustomAttributeDataRange, Il2CppEventDefinition,
     Il2CppFieldDefaultValue, Il2CppFieldDefinition, Il2CppFieldMarshaledSize, Il2CppFieldRef,
     Il2CppGenericContainer, Il2CppGenericParameter, Il2CppGlobalMetadataHeader,
-    Il2CppImageDefinition, Il2CppInterfaceOffsetPair, Il2CppMethodDefinition,
-    Il2CppParameterDefaultValue, Il2CppParameterDefinition, Il2CppPropertyDefinition,
-    Il2CppTypeDefinition, StringIndex,
+    Il2CppImageDefinition, Il2CppInterfaceOffsetPair, Il2CppParameterDefaultValue,
+    Il2CppParameterDefinition, Il2CppPropertyDefinition, Il2CppStringLiteral, Il2CppTypeDefinition,
+    StringIndex,
 };
-use crate::unity::generated::SUPPORTED_GLOBAL_METADATA_VERSION;
-use anyhow::{bail, Result};
+use crate::unity::generated::{il2cpp_2022322f1, il2cpp_2022356f1, UnityVersion};
+use crate::unity::method_definitions::MethodDefinitions;
+use anyhow::{anyhow, bail, Result};
 use memchr::memchr;
 use nohash_hasher::IntMap;
 use paste::paste;
@@ -93,9 +94,11 @@ macro_rules! read_array {
 pub struct Metadata {
     /// The global metadata header providing offsets and sizes for subsequent data segments.
     pub header: Il2CppGlobalMetadataHeader,
+    /// The Unity version that wrote the metadata, determined from the header version.
+    pub unity_version: UnityVersion,
 
-    /// Raw data representing string literals.
-    pub string_literal: Vec<u8>,
+    /// Array of string literal definitions, indexing into `string_literal_data`.
+    pub string_literal: Vec<Il2CppStringLiteral>,
     /// Raw data representing the content of string literals.
     pub string_literal_data: Vec<u8>,
     /// Raw data for all strings used in the metadata.
@@ -107,8 +110,8 @@ pub struct Metadata {
     pub events: Vec<Il2CppEventDefinition>,
     /// Array of property definitions.
     pub properties: Vec<Il2CppPropertyDefinition>,
-    /// Array of method definitions.
-    pub methods: Vec<Il2CppMethodDefinition>,
+    /// Array of method definitions, in the layout of the metadata version.
+    pub methods: MethodDefinitions,
     /// Array of default values for method parameters.
     pub parameter_default_values: Vec<Il2CppParameterDefaultValue>,
     /// Array of default values for fields.
@@ -163,13 +166,23 @@ pub struct Metadata {
     pub exported_type_definitions: Vec<i32>,
 }
 
-const GLOBAL_METADATA_MAGIC: i32 = -89056337;
+/// Sanity value stored at the start of every decrypted global metadata file (`0xFAB11BAF`).
+pub const GLOBAL_METADATA_MAGIC: i32 = -89056337;
+
+/// Returns `true` if the data starts with [`GLOBAL_METADATA_MAGIC`], i.e. it is global metadata
+/// that is not encrypted.
+pub fn is_plain_global_metadata(data: &[u8]) -> bool {
+    data.get(..4).is_some_and(|sanity| {
+        i32::from_le_bytes(sanity.try_into().unwrap()) == GLOBAL_METADATA_MAGIC
+    })
+}
 
 impl Metadata {
     /// Loads the metadata from a reader by parsing its header and subsequent data segments.
     ///
-    /// The function first reads the metadata header, performs sanity checks,
-    /// and then reads each data segment based on offsets and sizes provided in the header.
+    /// The function first reads the metadata header, performs sanity checks, selects the Unity
+    /// version from the header version, and then reads each data segment based on offsets and
+    /// sizes provided in the header, using the structure layouts of that Unity version.
     ///
     /// # Errors
     /// Returns an error if the header is invalid, the version is unsupported, or any read operation fails.
@@ -180,18 +193,33 @@ impl Metadata {
         if header.sanity != GLOBAL_METADATA_MAGIC {
             bail!("File does not have a valid header");
         }
-        if header.version != SUPPORTED_GLOBAL_METADATA_VERSION {
-            bail!("Metadata is described by unsupported version");
-        }
+        let unity_version = UnityVersion::from_metadata_version(header.version).ok_or(anyhow!(
+            "Global metadata version {} is not supported. Supported versions: {}",
+            header.version,
+            UnityVersion::supported_metadata_versions()
+        ))?;
 
-        let string_literal = read_array!(f, u8, header, stringLiteral);
+        let string_literal = read_array!(f, Il2CppStringLiteral, header, stringLiteral);
         let string_literal_data = read_array!(f, u8, header, stringLiteralData);
         let string_data = read_array!(f, u8, header, string);
         let cached_strings = Metadata::extract_null_terminated_strings(&string_data);
 
         let events = read_array!(f, Il2CppEventDefinition, header, events);
         let properties = read_array!(f, Il2CppPropertyDefinition, header, properties);
-        let methods = read_array!(f, Il2CppMethodDefinition, header, methods);
+        let methods = match unity_version {
+            UnityVersion::Unity2022_3_22f1 => MethodDefinitions::Unity2022_3_22f1(read_array!(
+                f,
+                il2cpp_2022322f1::CIl2Cpp::Il2CppMethodDefinition,
+                header,
+                methods
+            )),
+            UnityVersion::Unity2022_3_56f1 => MethodDefinitions::Unity2022_3_56f1(read_array!(
+                f,
+                il2cpp_2022356f1::CIl2Cpp::Il2CppMethodDefinition,
+                header,
+                methods
+            )),
+        };
         let parameter_default_values = read_array!(
             f,
             Il2CppParameterDefaultValue,
@@ -241,6 +269,7 @@ impl Metadata {
 
         Ok(Self {
             header,
+            unity_version,
             string_literal,
             string_literal_data,
             string_data,
@@ -0,0 +1,421 @@
+use crate::unity::generated::CIl2Cpp::{
+    kIl2CppMetadataUsageMethodDef, kIl2CppMetadataUsageMethodRef, Il2CppGenericClass,
+    Il2CppInterfaceOffsetPair, Il2CppType, TypeDefinitionIndex, TypeIndex, IL2CPP_TYPE_CLASS,
+    IL2CPP_TYPE_GENERICINST, IL2CPP_TYPE_VALUETYPE,
+};
+use crate::unity::il2cpp::Il2Cpp;
+use crate::unity::metadata_usages::decode_encoded_index;
+use anyhow::{anyhow, bail, Result};
+use hashbrown::HashSet;
+use nohash_hasher::IntMap;
+
+/// The method in a vtable slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VTableMethod {
+    /// A method definition, indexed into the method definitions.
+    Definition(usize),
+    /// A generic method instance, indexed into the method specs of the metadata registration.
+    Instance(usize),
+}
+
+impl VTableMethod {
+    /// Decodes an entry of the vtable methods of the metadata, or returns `None` for an empty
+    /// slot.
+    fn decode(encoded: u32) -> Option<Self> {
+        let (usage, index) = decode_encoded_index(encoded);
+        #[allow(non_upper_case_globals)]
+        match usage {
+            kIl2CppMetadataUsageMethodDef => Some(VTableMethod::Definition(index as usize)),
+            kIl2CppMetadataUsageMethodRef => Some(VTableMethod::Instance(index as usize)),
+            _ => None,
+        }
+    }
+
+    /// Returns the fully qualified name of the method, see [`Il2Cpp::method_full_name`] and
+    /// [`Il2Cpp::method_spec_full_name`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method cannot be resolved.
+    pub fn full_name<'a>(&self, il2cpp: &'a Il2Cpp<'a>) -> Result<String> {
+        match *self {
+            VTableMethod::Definition(method_idx) => il2cpp.method_full_name(method_idx),
+            VTableMethod::Instance(spec_idx) => il2cpp.method_spec_full_name(spec_idx),
+        }
+    }
+}
+
+impl<'a> Il2Cpp<'a> {
+    /// Returns the fully qualified name of a type of the metadata registration, such as
+    /// `Namespace.Outer.Inner` or `System.Collections.Generic.List<int>`. Nested types take the
+    /// namespace of their outermost declaring type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type or one of its declaring types cannot be resolved.
+    pub fn type_full_name(&'a self, type_index: TypeIndex) -> Result<String> {
+        let name = self
+            .types
+            .get(type_index as usize)
+            .ok_or_else(|| anyhow!("Type {} does not exist", type_index))?
+            .get_complex_type(self)?
+            .get_name_str(true)?;
+        let Some(mut outermost) = self.type_definition_index(type_index)? else {
+            return Ok(name);
+        };
+        loop {
+            let declaring_index = self.type_definition(outermost)?.declaringTypeIndex;
+            if declaring_index < 0 {
+                break;
+            }
+            outermost = self
+                .type_definition_index(declaring_index)?
+                .ok_or_else(|| anyhow!("Type {} is not a type definition", declaring_index))?;
+        }
+        let namespace_index = self.type_definition(outermost)?.namespaceIndex;
+        let namespace = self.metadata.get_string_by_index(namespace_index);
+        if namespace.is_empty() {
+            Ok(name)
+        } else {
+            Ok(format!("{}.{}", namespace, name))
+        }
+    }
+
+    /// Returns the fully qualified name of a type definition, such as `Namespace.Outer.Inner` or
+    /// `System.Collections.Generic.List<T>`, see [`Il2Cpp::type_full_name`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition or one of its declaring types cannot be resolved.
+    pub fn type_definition_name(&'a self, type_index: TypeDefinitionIndex) -> Result<String> {
+        self.type_full_name(self.type_definition(type_index)?.byvalTypeIndex)
+    }
+
+    /// Finds the type definitions with the given name.
+    ///
+    /// The name is compared with the fully qualified name of each type definition, see
+    /// [`Il2Cpp::type_definition_name`], and with its name without the namespace, both with and
+    /// without the generic parameters. So `Game.Client<T>`, `Game.Client`, `Client<T>` and
+    /// `Client` all find the generic type `Client<T>` of the `Game` namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name of a type definition cannot be resolved.
+    pub fn find_type_definitions(&'a self, name: &str) -> Result<Vec<TypeDefinitionIndex>> {
+        let mut found = Vec::new();
+        for (type_index, ty_def) in self.metadata.type_definitions.iter().enumerate() {
+            let type_index = type_index as TypeDefinitionIndex;
+            let short_name = self.types[ty_def.byvalTypeIndex as usize]
+                .get_complex_type(self)?
+                .get_name_str(true)?;
+            let full_name = self.type_definition_name(type_index)?;
+            let matches = [full_name.as_str(), short_name.as_str()]
+                .into_iter()
+                .any(|candidate| candidate == name || without_generic_args(candidate) == name);
+            if matches {
+                found.push(type_index);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Returns the type definition of a type of the metadata registration: the definition of a
+    /// class or value type, or the generic type definition of a generic instance.
+    ///
+    /// # Returns
+    ///
+    /// The type definition index, or `None` for types without a definition such as arrays and
+    /// generic parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type does not exist or its generic class cannot be read.
+    pub fn type_definition_index(
+        &'a self,
+        type_index: TypeIndex,
+    ) -> Result<Option<TypeDefinitionIndex>> {
+        let ty = self
+            .types
+            .get(type_index as usize)
+            .ok_or_else(|| anyhow!("Type {} does not exist", type_index))?;
+        let type_def_index = match ty.type_() {
+            IL2CPP_TYPE_CLASS | IL2CPP_TYPE_VALUETYPE => unsafe { ty.data.__klassIndex },
+            IL2CPP_TYPE_GENERICINST => unsafe {
+                let generic_class =
+                    self.load_data_instance::<Il2CppGenericClass>(ty.data.generic_class as u64)?;
+                let type_inst =
+                    self.load_data_instance::<Il2CppType>(generic_class.type_ as u64)?;
+                type_inst.data.__klassIndex
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(type_def_index))
+    }
+
+    /// Returns the base types of a type definition, starting with its parent and ending with the
+    /// root of the hierarchy, usually `object`.
+    ///
+    /// The base types are types of the metadata registration, so a generic base type keeps the
+    /// type arguments it is derived with, such as `ClientBase<LoginClient>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a base type cannot be resolved or the hierarchy has a cycle.
+    pub fn base_types(&'a self, type_index: TypeDefinitionIndex) -> Result<Vec<TypeIndex>> {
+        let mut base_types = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = type_index;
+        loop {
+            if !visited.insert(current) {
+                bail!("Base types of type definition {} form a cycle", type_index);
+            }
+            let parent_index = self.type_definition(current)?.parentIndex;
+            if parent_index < 0 {
+                return Ok(base_types);
+            }
+            base_types.push(parent_index);
+            match self.type_definition_index(parent_index)? {
+                Some(parent) => current = parent,
+                None => return Ok(base_types),
+            }
+        }
+    }
+
+    /// Returns the interfaces a type definition declares, as types of the metadata registration.
+    /// Interfaces inherited from base types are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition or its interface range does not exist.
+    pub fn interfaces(&self, type_index: TypeDefinitionIndex) -> Result<&[TypeIndex]> {
+        let ty_def = self.type_definition(type_index)?;
+        metadata_range(
+            &self.metadata.interfaces,
+            ty_def.interfacesStart,
+            ty_def.interfaces_count,
+            "Interfaces",
+            type_index,
+        )
+    }
+
+    /// Returns the type definitions nested directly in a type definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition or its nested type range does not exist.
+    pub fn nested_types(&self, type_index: TypeDefinitionIndex) -> Result<&[TypeDefinitionIndex]> {
+        let ty_def = self.type_definition(type_index)?;
+        metadata_range(
+            &self.metadata.nested_types,
+            ty_def.nestedTypesStart,
+            ty_def.nested_type_count,
+            "Nested types",
+            type_index,
+        )
+    }
+
+    /// Returns the interface offsets of a type definition: for every interface the type
+    /// implements, including inherited ones, the vtable slot of its first method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition or its interface offset range does not exist.
+    pub fn interface_offsets(
+        &self,
+        type_index: TypeDefinitionIndex,
+    ) -> Result<&[Il2CppInterfaceOffsetPair]> {
+        let ty_def = self.type_definition(type_index)?;
+        metadata_range(
+            &self.metadata.interface_offsets,
+            ty_def.interfaceOffsetsStart,
+            ty_def.interface_offsets_count,
+            "Interface offsets",
+            type_index,
+        )
+    }
+
+    /// Returns the vtable of a type definition, with the method of each slot, or `None` for
+    /// slots without an implementation, such as the abstract methods of abstract classes.
+    ///
+    /// The vtable includes the slots of the base types, so a method overriding a base method is
+    /// found in the slot of the base method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition or its vtable range does not exist.
+    pub fn vtable(&self, type_index: TypeDefinitionIndex) -> Result<Vec<Option<VTableMethod>>> {
+        let ty_def = self.type_definition(type_index)?;
+        let vtable = metadata_range(
+            &self.metadata.vtable_methods,
+            ty_def.vtableStart,
+            ty_def.vtable_count,
+            "VTable methods",
+            type_index,
+        )?;
+        Ok(vtable
+            .iter()
+            .map(|&encoded| VTableMethod::decode(encoded))
+            .collect())
+    }
+
+    /// Returns the method in a vtable slot of a type definition, or `None` for a slot without an
+    /// implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type definition does not exist or has no such slot.
+    pub fn vtable_method(
+        &self,
+        type_index: TypeDefinitionIndex,
+        slot: usize,
+    ) -> Result<Option<VTableMethod>> {
+        let vtable = self.vtable(type_index)?;
+        vtable.get(slot).copied().ok_or_else(|| {
+            anyhow!(
+                "Type definition {} has {} vtable slots, not slot {}",
+                type_index,
+                vtable.len(),
+                slot
+            )
+        })
+    }
+}
+
+/// The reverse relations of the type hierarchy: the subclasses of each type definition and the
+/// types implementing each interface.
+///
+/// Generic instances are resolved to their generic type definition, so a class deriving from
+/// `ClientBase<LoginClient>` is a subclass of `ClientBase<T>`.
+pub struct TypeHierarchy {
+    /// The type definitions deriving directly from each type definition.
+    subclasses: IntMap<TypeDefinitionIndex, Vec<TypeDefinitionIndex>>,
+    /// The classes and value types declaring each interface.
+    implementors: IntMap<TypeDefinitionIndex, Vec<TypeDefinitionIndex>>,
+    /// The interfaces declaring each interface, which extend it.
+    derived_interfaces: IntMap<TypeDefinitionIndex, Vec<TypeDefinitionIndex>>,
+}
+
+impl TypeHierarchy {
+    /// Builds the reverse relations from the parent and interfaces of every type definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parent or interface type cannot be resolved.
+    pub fn new<'a>(il2cpp: &'a Il2Cpp<'a>) -> Result<Self> {
+        let mut subclasses: IntMap<_, Vec<_>> = IntMap::default();
+        let mut implementors: IntMap<_, Vec<_>> = IntMap::default();
+        let mut derived_interfaces: IntMap<_, Vec<_>> = IntMap::default();
+        for (type_index, ty_def) in il2cpp.metadata.type_definitions.iter().enumerate() {
+            let type_index = type_index as TypeDefinitionIndex;
+            if ty_def.parentIndex >= 0 {
+                if let Some(parent) = il2cpp.type_definition_index(ty_def.parentIndex)? {
+                    subclasses.entry(parent).or_default().push(type_index);
+                }
+            }
+            let declarers = if ty_def.is_interface() {
+                &mut derived_interfaces
+            } else {
+                &mut implementors
+            };
+            for &interface in il2cpp.interfaces(type_index)? {
+                if let Some(interface) = il2cpp.type_definition_index(interface)? {
+                    declarers.entry(interface).or_default().push(type_index);
+                }
+            }
+        }
+        Ok(TypeHierarchy {
+            subclasses,
+            implementors,
+            derived_interfaces,
+        })
+    }
+
+    /// Returns the type definitions deriving directly from a type definition.
+    pub fn subclasses(&self, type_index: TypeDefinitionIndex) -> &[TypeDefinitionIndex] {
+        self.subclasses.get(&type_index).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns all type definitions deriving from a type definition, directly or through other
+    /// subclasses, with each subclass followed by its own subclasses.
+    pub fn all_subclasses(&self, type_index: TypeDefinitionIndex) -> Vec<TypeDefinitionIndex> {
+        let mut all = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = self.subclasses(type_index).iter().rev().collect::<Vec<_>>();
+        while let Some(&subclass) = stack.pop() {
+            if visited.insert(subclass) {
+                all.push(subclass);
+                stack.extend(self.subclasses(subclass).iter().rev());
+            }
+        }
+        all
+    }
+
+    /// Returns the classes and value types that declare an interface themselves.
+    pub fn implementors(&self, interface: TypeDefinitionIndex) -> &[TypeDefinitionIndex] {
+        self.implementors.get(&interface).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the interfaces that declare an interface, which means they extend it.
+    pub fn derived_interfaces(&self, interface: TypeDefinitionIndex) -> &[TypeDefinitionIndex] {
+        self.derived_interfaces
+            .get(&interface)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns all classes and value types implementing an interface: those declaring the
+    /// interface or an interface extending it, and all of their subclasses.
+    pub fn all_implementors(&self, interface: TypeDefinitionIndex) -> Vec<TypeDefinitionIndex> {
+        let mut all = Vec::new();
+        let mut visited = HashSet::new();
+        let mut interfaces = vec![interface];
+        let mut visited_interfaces: HashSet<_> = HashSet::from_iter([interface]);
+        while let Some(interface) = interfaces.pop() {
+            for &implementor in self.implementors(interface) {
+                let subclasses = self.all_subclasses(implementor);
+                for type_index in std::iter::once(implementor).chain(subclasses) {
+                    if visited.insert(type_index) {
+                        all.push(type_index);
+                    }
+                }
+            }
+            for &derived in self.derived_interfaces(interface) {
+                if visited_interfaces.insert(derived) {
+                    interfaces.push(derived);
+                }
+            }
+        }
+        all
+    }
+}
+
+/// Returns the entries of a metadata table that belong to a type definition.
+fn metadata_range<'t, T>(
+    table: &'t [T],
+    start: i32,
+    count: u16,
+    what: &str,
+    type_index: TypeDefinitionIndex,
+) -> Result<&'t [T]> {
+    if count == 0 {
+        return Ok(&[]);
+    }
+    let start = start as usize;
+    let end = start + count as usize;
+    table.get(start..end).ok_or_else(|| {
+        anyhow!(
+            "{} {}..{} of type definition {} do not exist",
+            what,
+            start,
+            end,
+            type_index
+        )
+    })
+}
+
+/// Returns a type name without the generic parameters or arguments at its end.
+fn without_generic_args(name: &str) -> &str {
+    if !name.ends_with('>') {
+        return name;
+    }
+    name.find('<').map_or(name, |pos| &name[..pos])
+}
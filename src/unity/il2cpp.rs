@@ -1,18 +1,24 @@
+use crate::binary::abi::Abi;
 use crate::binary::arm64::{
-    parse_add_immediate, parse_adrp, parse_bl, parse_movk, parse_movz, Register,
-    ShiftAmount, SIZEOF_ARM64_INSTRUCTION,
+    parse_add_immediate, parse_adrp, parse_bl, parse_movk, parse_movz, Register, ShiftAmount,
+    SIZEOF_ARM64_INSTRUCTION,
 };
 use crate::binary::elf::{Elf, POINTER_SIZE};
+use crate::binary::x86_64::{
+    parse_call_rel32, parse_lea_rip, parse_mov_imm64, CallRel32, LeaRip, MovImm64,
+    Register as X86Register,
+};
+use crate::unity::complex_type::{ComplexType, ComplexTypeArgs};
 use crate::unity::generated::CIl2Cpp::{
-    Il2CppCodeGenModule, Il2CppCodeRegistration, Il2CppMetadataRegistration, Il2CppType,
-    Il2CppTypeEnum, IL2CPP_TYPE_ENUM,
+    Il2CppCodeGenModule, Il2CppCodeRegistration, Il2CppGenericInst, Il2CppMetadataRegistration,
+    Il2CppMethodSpec, Il2CppType, Il2CppTypeEnum, IL2CPP_TYPE_ENUM,
 };
 use crate::unity::global_metadata::Metadata;
 use crate::utils::read_only::ReadOnly;
 use anyhow::{anyhow, bail, Result};
 use hashbrown::HashMap;
 use itertools::Itertools;
-use log::debug;
+use log::{debug, info};
 use nohash_hasher::IntMap;
 use std::io::Cursor;
 use std::mem::{offset_of, size_of};
@@ -27,9 +33,9 @@ pub struct Il2Cpp<'a> {
     /// Global metadata extracted from the Unity binary.
     pub metadata: Metadata,
     /// Code registration information for IL2CPP functions.
-    code_registration: ReadOnly<Il2CppCodeRegistration>,
+    pub(crate) code_registration: ReadOnly<Il2CppCodeRegistration>,
     /// Metadata registration information for IL2CPP types.
-    metadata_registration: ReadOnly<Il2CppMetadataRegistration>,
+    pub(crate) metadata_registration: ReadOnly<Il2CppMetadataRegistration>,
     /// List of IL2CPP type definitions.
     pub types: Vec<ReadOnly<&'a Il2CppType>>,
     /// Mapping from type pointer addresses to indices in the `types` vector.
@@ -40,7 +46,8 @@ impl<'a> Il2Cpp<'a> {
     /// Loads the IL2CPP environment from the given binary data vectors.
     ///
     /// This function constructs an `Il2Cpp` instance by parsing the IL2CPP ELF binary and associated
-    /// global metadata. It verifies the metadata version and loads both code and metadata registration structures.
+    /// global metadata. It selects the Unity version from the metadata version and loads both code and metadata
+    /// registration structures.
     ///
     /// # Arguments
     ///
@@ -52,8 +59,18 @@ impl<'a> Il2Cpp<'a> {
     /// Returns an error if the metadata version is unsupported or if parsing fails.
     pub fn load_from_vec(elf: Elf<'a>, global_metadata_data: Vec<u8>) -> Result<Self> {
         debug!("Loading IL2CPP from ELF and metadata...");
+        if !elf.abi.is_64_bit() {
+            bail!(
+                "{} IL2CPP binaries are 32-bit, which is not supported. Use an arm64-v8a or x86_64 build instead.",
+                elf.abi
+            );
+        }
         let reader = Cursor::new(global_metadata_data);
         let metadata = Metadata::load_from_reader(reader)?;
+        info!(
+            "Global metadata v{} (Unity {})",
+            metadata.header.version, metadata.unity_version
+        );
 
         let code_registration = Self::find_code_registration(&elf, &metadata)?;
         let metadata_registration = Self::find_metadata_registration(&elf, &metadata)?;
@@ -89,6 +106,56 @@ impl<'a> Il2Cpp<'a> {
         })
     }
 
+    /// Extracts the metadata key's xor key from the code of the provided elf file.
+    ///
+    /// The instruction pattern differs per architecture, so this dispatches on the ELF's ABI. On
+    /// x86_64 the pattern is ambiguous, so candidates are collected with
+    /// [`Il2Cpp::extract_metadata_key_candidates_x86_64`] instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `elf` - An instance of the Elf binary class
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some((usize, u64))` containing the offset within the `.text` section and the metadata key xor if the
+    /// pattern is found, or `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if key extraction is not implemented for the ELF's ABI.
+    pub fn extract_metadata_key_xor(elf: &Elf) -> Result<Option<(usize, u64)>> {
+        match elf.abi {
+            Abi::Arm64V8a => Ok(Self::extract_metadata_key_xor_arm64(elf)),
+            abi => bail!("Global metadata key extraction is not implemented for {abi}"),
+        }
+    }
+
+    /// Extracts the metadata key from the code of the provided elf file.
+    ///
+    /// The instruction pattern differs per architecture, so this dispatches on the ELF's ABI. On
+    /// x86_64, see [`Il2Cpp::extract_metadata_key_candidates_x86_64`] instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `elf` - A reference to an `Elf` structure representing the ELF binary.
+    /// * `key_xor_offset` - The offset within the .text section returned by [`Il2Cpp::extract_metadata_key_xor`].
+    ///
+    /// # Returns
+    ///
+    /// * `Some([u8; 16])` if the key is successfully extracted.
+    /// * `None` if the key is not found or if any computation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if key extraction is not implemented for the ELF's ABI.
+    pub fn extract_metadata_key(elf: &Elf, key_xor_offset: usize) -> Result<Option<[u8; 16]>> {
+        match elf.abi {
+            Abi::Arm64V8a => Ok(Self::extract_metadata_key_arm64(elf, key_xor_offset)),
+            abi => bail!("Global metadata key extraction is not implemented for {abi}"),
+        }
+    }
+
     /// Extracts the metadata key's xor key from ARM64 instructions in the provided elf file data.
     ///
     /// This function scans through the provided data and looks for a sequence of instructions
@@ -102,7 +169,7 @@ impl<'a> Il2Cpp<'a> {
     /// # Returns
     ///
     /// Returns `Some((usize, u64))` containing the offset and metadata key if the pattern is found, or `None` otherwise.
-    pub fn extract_metadata_key_xor(elf: &Elf) -> Option<(usize, u64)> {
+    fn extract_metadata_key_xor_arm64(elf: &Elf) -> Option<(usize, u64)> {
         debug!("Extracting global metadata key xor data from IL2CPP...");
 
         let text_section_range = elf.sections.get(".text")?;
@@ -179,7 +246,7 @@ impl<'a> Il2Cpp<'a> {
      * * `Some([u8; 16])` if the key is successfully extracted.
      * * `None` if the key is not found or if any computation fails.
      */
-    pub fn extract_metadata_key(elf: &Elf, key_xor_offset: usize) -> Option<[u8; 16]> {
+    fn extract_metadata_key_arm64(elf: &Elf, key_xor_offset: usize) -> Option<[u8; 16]> {
         debug!("Extracting global metadata encryption keys from IL2CPP...");
 
         // Retrieve the file offset range of the .text section and its corresponding instruction stream.
@@ -242,6 +309,191 @@ impl<'a> Il2Cpp<'a> {
         None
     }
 
+    /// Collects candidate metadata keys and key xors from x86_64 code, anchored on the code that
+    /// loads the global metadata.
+    ///
+    /// The metadata is loaded by file name, so the search starts at every RIP-relative `LEA` of the
+    /// `"global-metadata.dat"` string. After each such reference, and in the functions called right
+    /// after it, the key xor is passed as the second argument of the decryption call: a
+    /// `MOV RSI, imm64` followed shortly by a `CALL rel32`. The first instructions of the called
+    /// function then load the key with a RIP-relative `LEA`.
+    ///
+    /// The pattern is not specific enough to be trusted on its own, so the caller has to check each
+    /// candidate against the metadata header.
+    ///
+    /// # Arguments
+    ///
+    /// * `elf` - A reference to an `Elf` structure representing the ELF binary.
+    ///
+    /// # Returns
+    ///
+    /// The `(key, key_xor)` candidates in the order they are found, which is empty if the metadata
+    /// file name is not referenced from the code.
+    pub fn extract_metadata_key_candidates_x86_64(elf: &Elf) -> Vec<([u8; 16], u64)> {
+        debug!("Extracting global metadata key candidates from IL2CPP...");
+
+        // How far after the metadata file name reference, and into each function, to look.
+        const MAX_SCAN_BYTES: usize = 512;
+
+        let Some(text_section_range) = elf.sections.get(".text") else {
+            return Vec::new();
+        };
+        let text_section_data = &elf.data[text_section_range.start..text_section_range.end];
+
+        let name_vaddrs: Vec<u64> = elf
+            .search_elf(b"global-metadata.dat\0")
+            .into_iter()
+            .filter_map(|file_offset| elf.file_offset_to_va(file_offset))
+            .collect();
+        if name_vaddrs.is_empty() {
+            debug!("No occurrences of 'global-metadata.dat' found in ELF");
+            return Vec::new();
+        }
+
+        let name_refs: Vec<usize> = Self::find_lea_rip_refs_x86_64(elf, &name_vaddrs);
+        debug!(
+            "Found {} references to 'global-metadata.dat'",
+            name_refs.len()
+        );
+
+        let mut candidates = Vec::new();
+        for name_ref in name_refs {
+            // The decryption call is either made by the loading code itself, or by a function it
+            // calls to read the file.
+            let mut starts = vec![name_ref];
+            let end = (name_ref + MAX_SCAN_BYTES).min(text_section_data.len());
+            for offset in name_ref..end {
+                if let Some(call) = parse_call_rel32(&text_section_data[offset..]) {
+                    starts.extend(Self::call_target_x86_64(elf, offset, &call));
+                }
+            }
+
+            for start in starts {
+                let end = (start + MAX_SCAN_BYTES).min(text_section_data.len());
+                for offset in start..end {
+                    let Some(mov) = parse_mov_imm64(&text_section_data[offset..]) else {
+                        continue;
+                    };
+                    if mov.rd != X86Register::Rsi {
+                        continue;
+                    }
+                    let Some((call_offset, call)) =
+                        Self::find_call_after_x86_64(text_section_data, offset + MovImm64::LEN)
+                    else {
+                        continue;
+                    };
+                    let Some(target) = Self::call_target_x86_64(elf, call_offset, &call) else {
+                        continue;
+                    };
+                    for key in Self::find_lea_rip_keys_x86_64(elf, target) {
+                        if !candidates.contains(&(key, mov.imm64)) {
+                            candidates.push((key, mov.imm64));
+                        }
+                    }
+                }
+            }
+        }
+        debug!("Found {} metadata key candidates", candidates.len());
+        candidates
+    }
+
+    /// Finds the RIP-relative `LEA` instructions in the `.text` section whose effective address is
+    /// one of `vaddrs`.
+    ///
+    /// # Returns
+    ///
+    /// The offsets of the instructions within the `.text` section.
+    fn find_lea_rip_refs_x86_64(elf: &Elf, vaddrs: &[u64]) -> Vec<usize> {
+        // Report progress per chunk rather than per byte, which would flood the log.
+        const PROGRESS_CHUNK_SIZE: usize = 0x10000;
+
+        let Some(text_section_range) = elf.sections.get(".text") else {
+            return Vec::new();
+        };
+        let text_section_data = &elf.data[text_section_range.start..text_section_range.end];
+
+        // x86_64 instructions have variable lengths, so every byte offset is a potential match.
+        let mut refs = Vec::new();
+        let total = text_section_data.len().div_ceil(PROGRESS_CHUNK_SIZE);
+        debug!(progress = 0, max = total; "");
+        for chunk_start in (0..text_section_data.len()).step_by(PROGRESS_CHUNK_SIZE) {
+            debug!(progress_tick = 1; "");
+            let chunk_end = (chunk_start + PROGRESS_CHUNK_SIZE).min(text_section_data.len());
+            for offset in chunk_start..chunk_end {
+                let Some(lea) = parse_lea_rip(&text_section_data[offset..]) else {
+                    continue;
+                };
+                // The effective address is relative to the end of the LEA instruction.
+                let Some(lea_end_va) =
+                    elf.file_offset_to_va((text_section_range.start + offset + LeaRip::LEN) as u64)
+                else {
+                    continue;
+                };
+                if vaddrs.contains(&lea_end_va.wrapping_add_signed(lea.disp32 as i64)) {
+                    refs.push(offset);
+                }
+            }
+        }
+        refs
+    }
+
+    /// Reads the 16 bytes at the effective address of every RIP-relative `LEA` within the first
+    /// instructions of the function at `function_offset` in the `.text` section.
+    fn find_lea_rip_keys_x86_64(elf: &Elf, function_offset: usize) -> Vec<[u8; 16]> {
+        // How far into the decryption function to look for the key reference.
+        const MAX_SCAN_BYTES: usize = 256;
+
+        let Some(text_section_range) = elf.sections.get(".text") else {
+            return Vec::new();
+        };
+        let function_start = text_section_range.start + function_offset;
+        let function_end = (function_start + MAX_SCAN_BYTES).min(text_section_range.end);
+
+        let mut keys = Vec::new();
+        for offset in function_start..function_end {
+            let Some(lea) = parse_lea_rip(&elf.data[offset..]) else {
+                continue;
+            };
+            let key: Option<[u8; 16]> = elf
+                .file_offset_to_va((offset + LeaRip::LEN) as u64)
+                .map(|lea_end_va| lea_end_va.wrapping_add_signed(lea.disp32 as i64))
+                .and_then(|key_va| elf.read_bytes_at_va(key_va, 16))
+                .and_then(|slice| slice.try_into().ok());
+            keys.extend(key);
+        }
+        keys
+    }
+
+    /// Resolves the target of a `CALL rel32` at `call_offset` in the `.text` section.
+    ///
+    /// # Returns
+    ///
+    /// The offset of the target within the `.text` section, or `None` if it lies outside of it.
+    fn call_target_x86_64(elf: &Elf, call_offset: usize, call: &CallRel32) -> Option<usize> {
+        let text_section_range = elf.sections.get(".text")?;
+        // The displacement is relative to the end of the call instruction.
+        let call_end_va = elf
+            .file_offset_to_va((text_section_range.start + call_offset + CallRel32::LEN) as u64)?;
+        let target_va = call_end_va.wrapping_add_signed(call.rel32 as i64);
+        let target_offset = elf.va_to_file_offset(target_va)? as usize;
+        text_section_range
+            .contains(&target_offset)
+            .then(|| target_offset - text_section_range.start)
+    }
+
+    /// Finds the first `CALL rel32` instruction within a short distance after `start` in x86_64 code.
+    ///
+    /// # Returns
+    ///
+    /// `Some((offset, call))` with the offset of the call within `code`, or `None` if there is none.
+    fn find_call_after_x86_64(code: &[u8], start: usize) -> Option<(usize, CallRel32)> {
+        // The call is expected to follow the argument setup within a few instructions.
+        const MAX_DISTANCE: usize = 32;
+
+        let end = (start + MAX_DISTANCE).min(code.len());
+        (start..end).find_map(|offset| parse_call_rel32(&code[offset..]).map(|call| (offset, call)))
+    }
+
     /// Locates the `Il2CppCodeRegistration` structure in the ELF binary.
     ///
     /// This high-level routine works as follows:
@@ -509,6 +761,25 @@ impl<'a> Il2Cpp<'a> {
         Self::inner_load_data_array(&self.elf, ptr, count)
     }
 
+    /// Reads the pointer at the given index of a pointer array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pointer lies outside of the file data.
+    pub fn read_pointer(&self, array_ptr: u64, index: usize) -> Result<u64> {
+        self.elf
+            .read_pointer_array(array_ptr + (index * POINTER_SIZE) as u64, 1)
+            .first()
+            .copied()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Pointer {} of the array at 0x{:X} cannot be read",
+                    index,
+                    array_ptr
+                )
+            })
+    }
+
     /// Internal helper to load an array of data instances of type `T` from a pointer array in the ELF binary.
     ///
     /// # Arguments
@@ -573,19 +844,20 @@ impl<'a> Il2Cpp<'a> {
         }
     }
 
-    /// Constructs a mapping of method pointers to their corresponding fully qualified method names.
+    /// Resolves the code address of every method definition.
     ///
-    /// This function iterates over code generation modules and image metadata to combine information
-    /// from the ELF binary and global metadata, thereby resolving method pointers to human-readable names.
+    /// Method pointers are stored per code generation module, indexed by the row of the method
+    /// token, so the pointers of each image are looked up in the module with the same name.
     ///
     /// # Returns
     ///
-    /// Returns an `IntMap` where the keys are method pointers and the values are formatted method names.
+    /// Returns a vector with one entry per method definition, holding the virtual address of the
+    /// method code, or `None` for methods without code such as abstract methods.
     ///
     /// # Errors
     ///
-    /// Returns an error if any step of the method extraction fails.
-    pub fn methods(&'a self) -> Result<IntMap<u64, String>> {
+    /// Returns an error if the code generation modules cannot be read, or an image has no module.
+    pub fn method_pointers(&'a self) -> Result<Vec<Option<u64>>> {
         let code_reg = &self.code_registration;
         let code_gen_modules = self.load_data_array::<Il2CppCodeGenModule>(
             code_reg.codeGenModules,
@@ -604,40 +876,156 @@ impl<'a> Il2Cpp<'a> {
 
         let metadata = &self.metadata;
 
-        let mut methods = IntMap::default();
+        let mut method_pointers = vec![None; metadata.methods.len()];
 
         for image in &metadata.images {
             let image_name = metadata.get_string_by_index(image.nameIndex);
-            let method_pointers = module_method_pointers.get(&image_name).ok_or(anyhow!(
+            let pointers = module_method_pointers.get(&image_name).ok_or(anyhow!(
                 "Module method pointers should exist for each image"
             ))?;
 
             let type_end = image.typeStart as usize + image.typeCount as usize;
             for ty_idx in image.typeStart as usize..type_end {
                 let ty_def = metadata.type_definitions[ty_idx];
-                let ty = &self.types[ty_def.byvalTypeIndex as usize];
-                let namespace = metadata.get_string_by_index(ty_def.namespaceIndex);
-                let method_end = ty_def.methodStart as usize + ty_def.method_count as usize;
-                for method_idx in ty_def.methodStart as usize..method_end {
-                    let method = metadata.methods[method_idx];
-                    let method_name = metadata.get_string_by_index(method.nameIndex);
+                let method_range = ty_def.methodStart as usize
+                    ..ty_def.methodStart as usize + ty_def.method_count as usize;
+                let methods = metadata.methods.range(method_range.clone());
+                for (method, method_pointer) in methods.zip(&mut method_pointers[method_range]) {
                     // Adjust for zero-based index.
                     let pointer_idx = ((method.token & 0xFFFFFF) - 1) as usize;
-                    if let Some(&method_ptr) = method_pointers.get(pointer_idx) {
+                    if let Some(&method_ptr) = pointers.get(pointer_idx) {
                         if method_ptr > 0 {
-                            let method_full_name = format!(
-                                "{namespace}.{}$${method_name}",
-                                ty.get_complex_type(self)?.get_name_str(true)?
-                            );
-                            methods.insert(method_ptr, method_full_name);
+                            *method_pointer = Some(method_ptr);
                         }
                     }
                 }
             }
         }
+        Ok(method_pointers)
+    }
+
+    /// Constructs a mapping of code addresses to the names of the methods and other generated
+    /// functions they belong to.
+    ///
+    /// This function resolves every named code address with [`Il2Cpp::code_entries`], which
+    /// covers the method definitions, generic method instances, adjustor thunks, invokers,
+    /// reverse P/Invoke wrappers, unresolved call stubs and marshaling functions. When several
+    /// entries share an address, such as methods sharing their code, the first one is used, so
+    /// method definitions take precedence over the other entries.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `IntMap` where the keys are code addresses and the values are the names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any step of the method extraction fails.
+    pub fn methods(&'a self) -> Result<IntMap<u64, String>> {
+        let mut methods = IntMap::default();
+        for entry in self.code_entries()? {
+            methods.entry(entry.address).or_insert(entry.name);
+        }
         Ok(methods)
     }
 
+    /// Returns the fully qualified name of a method definition, in the form
+    /// `Namespace.Type$$Method`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method or its declaring type does not exist, or the type name
+    /// cannot be resolved.
+    pub fn method_full_name(&'a self, method_idx: usize) -> Result<String> {
+        let metadata = &self.metadata;
+        if method_idx >= metadata.methods.len() {
+            bail!("Method definition {} does not exist", method_idx);
+        }
+        let method = metadata.methods.get(method_idx);
+        let ty_def = metadata
+            .type_definitions
+            .get(method.declaring_type as usize)
+            .ok_or_else(|| anyhow!("Type definition {} does not exist", method.declaring_type))?;
+        let ty = &self.types[ty_def.byvalTypeIndex as usize];
+        let namespace = metadata.get_string_by_index(ty_def.namespaceIndex);
+        let method_name = metadata.get_string_by_index(method.name_index);
+        Ok(format!(
+            "{namespace}.{}$${method_name}",
+            ty.get_complex_type(self)?.get_name_str(true)?
+        ))
+    }
+
+    /// Returns the fully qualified name of a generic method instance of the metadata
+    /// registration, in the form `Namespace.Type<TypeArgs>$$Method<MethodArgs>`.
+    ///
+    /// The generic parameters of the declaring type and of the method are replaced by the type
+    /// arguments of the instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method spec, its method definition or one of its type arguments
+    /// cannot be resolved.
+    pub fn method_spec_full_name(&'a self, spec_idx: usize) -> Result<String> {
+        let metadata_reg = &self.metadata_registration;
+        if spec_idx >= metadata_reg.methodSpecsCount as usize {
+            bail!("Method spec {} does not exist", spec_idx);
+        }
+        let spec_ptr =
+            metadata_reg.methodSpecs as u64 + (spec_idx * size_of::<Il2CppMethodSpec>()) as u64;
+        let spec = self.load_data_instance::<Il2CppMethodSpec>(spec_ptr)?;
+
+        let metadata = &self.metadata;
+        let method_idx = spec.methodDefinitionIndex as usize;
+        if method_idx >= metadata.methods.len() {
+            bail!("Method definition {} does not exist", method_idx);
+        }
+        let method = metadata.methods.get(method_idx);
+        let ty_def = metadata
+            .type_definitions
+            .get(method.declaring_type as usize)
+            .ok_or_else(|| anyhow!("Type definition {} does not exist", method.declaring_type))?;
+        let mut ty = self.types[ty_def.byvalTypeIndex as usize].get_complex_type(self)?;
+        if spec.classIndexIndex >= 0 {
+            if let ComplexType::Generic { args, .. } = &mut ty {
+                *args =
+                    ComplexTypeArgs::new(self.generic_inst_types(spec.classIndexIndex as usize)?);
+            }
+        }
+        let namespace = metadata.get_string_by_index(ty_def.namespaceIndex);
+        let method_name = metadata.get_string_by_index(method.name_index);
+        let mut name = format!("{namespace}.{}$${method_name}", ty.get_name_str(true)?);
+        if spec.methodIndexIndex >= 0 {
+            let args =
+                ComplexTypeArgs::new(self.generic_inst_types(spec.methodIndexIndex as usize)?);
+            name.push_str(&format!("<{}>", args.get_name_str(true)?));
+        }
+        Ok(name)
+    }
+
+    /// Returns the type arguments of a generic instance of the metadata registration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generic instance does not exist or one of its type arguments
+    /// cannot be resolved.
+    pub fn generic_inst_types(&'a self, inst_idx: usize) -> Result<Vec<ComplexType>> {
+        let metadata_reg = &self.metadata_registration;
+        if inst_idx >= metadata_reg.genericInstsCount as usize {
+            bail!("Generic instance {} does not exist", inst_idx);
+        }
+        let inst_ptr = self.read_pointer(metadata_reg.genericInsts as u64, inst_idx)?;
+        let inst = self.load_data_instance::<Il2CppGenericInst>(inst_ptr)?;
+        self.elf
+            .read_pointer_array(inst.type_argv as u64, inst.type_argc as usize)
+            .into_iter()
+            .map(|arg_ptr| {
+                let arg_ty = self
+                    .type_by_ptr(arg_ptr)
+                    .ok_or(anyhow!("Unknown generic arg"))?;
+                arg_ty.get_complex_type(self)
+            })
+            .collect()
+    }
+
     /// Retrieves type information corresponding to a given pointer.
     ///
     /// This function uses an internal mapping from type pointer addresses to indices to efficiently locate
@@ -3,6 +3,9 @@ use crate::unity::generated::CIl2Cpp::Il2CppTypeDefinition;
 use crate::unity::il2cpp::Il2Cpp;
 use std::ops::Range;
 
+/// The type is an interface.
+const TYPE_ATTRIBUTE_INTERFACE: u32 = 0x20;
+
 impl Il2CppTypeDefinition {
     /// Returns the range of field indices associated with this type definition.
     ///
@@ -56,6 +59,24 @@ impl Il2CppTypeDefinition {
         (self.bitfield & (1 << 1)) != 0
     }
 
+    /// Determines if the type is an interface.
+    pub fn is_interface(&self) -> bool {
+        (self.flags & TYPE_ATTRIBUTE_INTERFACE) != 0
+    }
+
+    /// Returns the C# keyword of the kind of type: `interface`, `enum`, `struct` or `class`.
+    pub fn kind_name(&self) -> &'static str {
+        if self.is_interface() {
+            "interface"
+        } else if self.is_enum_type() {
+            "enum"
+        } else if self.is_value_type() {
+            "struct"
+        } else {
+            "class"
+        }
+    }
+
     /// Checks whether the type defines a finalizer.
     pub fn has_finalize(&self) -> bool {
         (self.bitfield & (1 << 2)) != 0
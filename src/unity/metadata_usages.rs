@@ -0,0 +1,192 @@
+use crate::binary::elf::POINTER_SIZE;
+use crate::unity::generated::CIl2Cpp::{
+    kIl2CppMetadataUsageFieldInfo, kIl2CppMetadataUsageFieldRva, kIl2CppMetadataUsageIl2CppType,
+    kIl2CppMetadataUsageMethodDef, kIl2CppMetadataUsageMethodRef,
+    kIl2CppMetadataUsageStringLiteral, kIl2CppMetadataUsageTypeInfo, Il2CppMetadataUsage,
+};
+use crate::unity::il2cpp::Il2Cpp;
+use anyhow::{anyhow, bail, Result};
+
+/// The kind of metadata a metadata usage slot is initialized with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataUsageKind {
+    /// The `Il2CppClass` of a type, indexed into the types of the metadata registration.
+    TypeInfo,
+    /// The `Il2CppType` of a type, indexed into the types of the metadata registration.
+    Il2CppType,
+    /// The `MethodInfo` of a method definition.
+    MethodDef,
+    /// The `FieldInfo` of a field, indexed into the field references.
+    FieldInfo,
+    /// The managed string object of a string literal.
+    StringLiteral,
+    /// The `MethodInfo` of a generic method instance, indexed into the method specs of the
+    /// metadata registration.
+    MethodRef,
+    /// The initial data of a field with an RVA, indexed into the field references.
+    FieldRva,
+}
+
+impl MetadataUsageKind {
+    /// Maps the usage type of an encoded token to its kind.
+    fn from_usage(usage: Il2CppMetadataUsage) -> Option<Self> {
+        #[allow(non_upper_case_globals)]
+        match usage {
+            kIl2CppMetadataUsageTypeInfo => Some(MetadataUsageKind::TypeInfo),
+            kIl2CppMetadataUsageIl2CppType => Some(MetadataUsageKind::Il2CppType),
+            kIl2CppMetadataUsageMethodDef => Some(MetadataUsageKind::MethodDef),
+            kIl2CppMetadataUsageFieldInfo => Some(MetadataUsageKind::FieldInfo),
+            kIl2CppMetadataUsageStringLiteral => Some(MetadataUsageKind::StringLiteral),
+            kIl2CppMetadataUsageMethodRef => Some(MetadataUsageKind::MethodRef),
+            kIl2CppMetadataUsageFieldRva => Some(MetadataUsageKind::FieldRva),
+            _ => None,
+        }
+    }
+}
+
+/// Splits an encoded metadata index, as stored in metadata usage variables and vtables since
+/// metadata version 27, into its usage type and index: the usage type is stored in the top 3
+/// bits and the index is shifted left by one, above the lowest bit.
+pub(crate) fn decode_encoded_index(encoded: u32) -> (Il2CppMetadataUsage, u32) {
+    ((encoded & 0xE0000000) >> 29, (encoded & 0x1FFFFFFE) >> 1)
+}
+
+/// A global variable in the IL2CPP binary that the runtime lazily fills with a pointer to
+/// metadata, such as the `Il2CppClass` of a type or the string object of a string literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetadataUsage {
+    /// The virtual address of the variable.
+    pub address: u64,
+    /// The kind of metadata the variable points to once initialized.
+    pub kind: MetadataUsageKind,
+    /// The index of the metadata, interpreted according to the kind.
+    pub index: u32,
+}
+
+impl<'a> Il2Cpp<'a> {
+    /// Finds the metadata usage variables of the IL2CPP binary.
+    ///
+    /// Since metadata version 27, the metadata registration no longer lists the usages. Instead,
+    /// every usage variable in `.data` is initialized with an encoded token until its first use:
+    /// the usage type in the top 3 bits, the index shifted left by one, and the lowest bit set.
+    /// This scans `.data` for pointer-sized values of that form whose index refers to existing
+    /// metadata.
+    ///
+    /// # Returns
+    ///
+    /// The usages in address order, or an empty vector if the binary has no `.data` section.
+    pub fn metadata_usages(&self) -> Vec<MetadataUsage> {
+        let Some(range) = self.elf.sections.get(".data") else {
+            return Vec::new();
+        };
+        // Usage variables are pointers, so only aligned values are considered.
+        let start = range.start.next_multiple_of(POINTER_SIZE);
+        let Some(data) = self.elf.data.get(start..range.end) else {
+            return Vec::new();
+        };
+
+        let mut usages = Vec::new();
+        for (position, chunk) in data.chunks_exact(POINTER_SIZE).enumerate() {
+            let value = u64::from_le_bytes(chunk.try_into().unwrap());
+            let Ok(token) = u32::try_from(value) else {
+                continue;
+            };
+            if token & 1 == 0 {
+                continue;
+            }
+            let (usage, index) = decode_encoded_index(token);
+            let Some(kind) = MetadataUsageKind::from_usage(usage) else {
+                continue;
+            };
+            if !self.is_valid_usage_index(kind, index as usize) {
+                continue;
+            }
+            let file_offset = (start + position * POINTER_SIZE) as u64;
+            let Some(address) = self.elf.file_offset_to_va(file_offset) else {
+                continue;
+            };
+            usages.push(MetadataUsage {
+                address,
+                kind,
+                index,
+            });
+        }
+        usages
+    }
+
+    /// Checks whether the index of a usage refers to existing metadata of its kind.
+    fn is_valid_usage_index(&self, kind: MetadataUsageKind, index: usize) -> bool {
+        match kind {
+            MetadataUsageKind::TypeInfo | MetadataUsageKind::Il2CppType => index < self.types.len(),
+            MetadataUsageKind::MethodDef => index < self.metadata.methods.len(),
+            MetadataUsageKind::FieldInfo | MetadataUsageKind::FieldRva => {
+                index < self.metadata.field_refs.len()
+            }
+            MetadataUsageKind::StringLiteral => index < self.metadata.string_literal_count(),
+            MetadataUsageKind::MethodRef => {
+                index < self.metadata_registration.methodSpecsCount as usize
+            }
+        }
+    }
+
+    /// Returns a symbol name for a metadata usage, in the style used by Il2CppDumper:
+    /// `Class$Type` for type info, `Type$Type` for types, `Method$Type$$Method` for methods,
+    /// `Field$Type.Field` for fields and `StringLiteral_N` for string literals.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata the usage refers to cannot be resolved.
+    pub fn metadata_usage_name(&'a self, usage: &MetadataUsage) -> Result<String> {
+        let index = usage.index as usize;
+        let name = match usage.kind {
+            MetadataUsageKind::TypeInfo => format!("Class${}", self.type_display_name(index)?),
+            MetadataUsageKind::Il2CppType => format!("Type${}", self.type_display_name(index)?),
+            MetadataUsageKind::MethodDef => format!("Method${}", self.method_full_name(index)?),
+            MetadataUsageKind::MethodRef => {
+                format!("Method${}", self.method_spec_full_name(index)?)
+            }
+            MetadataUsageKind::FieldInfo => format!("Field${}", self.field_ref_name(index)?),
+            MetadataUsageKind::FieldRva => format!("FieldRva${}", self.field_ref_name(index)?),
+            MetadataUsageKind::StringLiteral => format!("StringLiteral_{}", index),
+        };
+        Ok(name)
+    }
+
+    /// Returns the display name of a type of the metadata registration.
+    fn type_display_name(&'a self, index: usize) -> Result<String> {
+        let ty = self
+            .types
+            .get(index)
+            .ok_or_else(|| anyhow!("Type {} does not exist", index))?;
+        Ok(ty.get_complex_type(self)?.get_name_str(true)?)
+    }
+
+    /// Returns the name of a field reference, in the form `Type.Field`.
+    fn field_ref_name(&'a self, index: usize) -> Result<String> {
+        let metadata = &self.metadata;
+        let field_ref = metadata
+            .field_refs
+            .get(index)
+            .ok_or_else(|| anyhow!("Field reference {} does not exist", index))?;
+        let ty = self
+            .types
+            .get(field_ref.typeIndex as usize)
+            .ok_or_else(|| anyhow!("Type {} does not exist", field_ref.typeIndex))?;
+        let Some(ty_def) = ty.get_type_def(self)? else {
+            bail!(
+                "Field reference {} is not declared by a type definition",
+                index
+            );
+        };
+        let field_index = ty_def.fieldStart as usize + field_ref.fieldIndex as usize;
+        let field = metadata
+            .fields
+            .get(field_index)
+            .ok_or_else(|| anyhow!("Field definition {} does not exist", field_index))?;
+        Ok(format!(
+            "{}.{}",
+            ty.get_complex_type(self)?.get_name_str(true)?,
+            metadata.get_string_by_index(field.nameIndex)
+        ))
+    }
+}
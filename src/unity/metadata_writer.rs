@@ -0,0 +1,410 @@
+use crate::unity::generated::CIl2Cpp::{
+    Il2CppAssemblyDefinition, Il2CppCustomAttributeDataRange, Il2CppEventDefinition,
+    Il2CppFieldDefaultValue, Il2CppFieldDefinition, Il2CppFieldMarshaledSize, Il2CppFieldRef,
+    Il2CppGenericContainer, Il2CppGenericParameter, Il2CppGlobalMetadataHeader,
+    Il2CppImageDefinition, Il2CppInterfaceOffsetPair, Il2CppParameterDefaultValue,
+    Il2CppParameterDefinition, Il2CppPropertyDefinition, Il2CppStringLiteral, Il2CppTypeDefinition,
+};
+use crate::unity::generated::{il2cpp_2022322f1, il2cpp_2022356f1};
+use crate::unity::global_metadata::Metadata;
+use anyhow::{anyhow, bail, Result};
+use paste::paste;
+use std::io::{Cursor, Write};
+use std::mem::size_of;
+
+/// Structures that are written to the metadata file as their raw bytes.
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` structures made up of integers only, without any padding
+/// bytes, so that every byte of a value is initialized.
+pub(crate) unsafe trait MetadataStruct: Copy {}
+
+/// Implements [`MetadataStruct`] for the metadata structures and plain integers.
+macro_rules! metadata_struct {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            // SAFETY: The generated metadata structures are `#[repr(C)]` and only have integer
+            // fields, ordered so that each one is aligned without padding, as in the file.
+            unsafe impl MetadataStruct for $ty {}
+        )*
+    };
+}
+
+metadata_struct!(
+    i32,
+    u32,
+    Il2CppAssemblyDefinition,
+    Il2CppCustomAttributeDataRange,
+    Il2CppEventDefinition,
+    Il2CppFieldDefaultValue,
+    Il2CppFieldDefinition,
+    Il2CppFieldMarshaledSize,
+    Il2CppFieldRef,
+    Il2CppGenericContainer,
+    Il2CppGenericParameter,
+    Il2CppGlobalMetadataHeader,
+    Il2CppImageDefinition,
+    Il2CppInterfaceOffsetPair,
+    Il2CppParameterDefaultValue,
+    Il2CppParameterDefinition,
+    Il2CppPropertyDefinition,
+    Il2CppStringLiteral,
+    Il2CppTypeDefinition,
+    il2cpp_2022322f1::CIl2Cpp::Il2CppMethodDefinition,
+    il2cpp_2022356f1::CIl2Cpp::Il2CppMethodDefinition,
+);
+
+/// Reinterprets a slice of metadata structures as its raw bytes, in the layout they are read from
+/// the metadata file with.
+pub(crate) fn slice_as_bytes<T: MetadataStruct>(values: &[T]) -> &[u8] {
+    // SAFETY: `MetadataStruct` guarantees that `T` has no padding, so all `size_of_val(values)`
+    // bytes behind the pointer are initialized. `u8` has no alignment requirement, and the
+    // returned slice borrows `values`, so it cannot outlive the data.
+    unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, size_of_val(values)) }
+}
+
+/// Accessor for the offset and size fields of one header section.
+type SectionFields = fn(&mut Il2CppGlobalMetadataHeader) -> (&mut i32, &mut i32);
+
+/// A data section of the global metadata, along with where it was located in the original file.
+struct Section<'a> {
+    /// The header field name of the section, used in error messages.
+    name: &'static str,
+    /// The offset of the section in the file the metadata was loaded from.
+    original_offset: i32,
+    /// The size of the section in the file the metadata was loaded from.
+    original_size: i32,
+    /// The current contents of the section.
+    data: &'a [u8],
+    /// Accessor for the offset and size fields of the section in the header.
+    fields: SectionFields,
+}
+
+/// Macro to describe a section by its header field name and its contents.
+///
+/// This macro mirrors `read_array!` from the loader, pairing the `Offset` and `Size` header
+/// fields of the section with the bytes that will be written for it.
+macro_rules! section {
+    ($h:expr, $name:ident, $data:expr) => {{
+        paste! {
+            Section {
+                name: stringify!($name),
+                original_offset: $h.[<$name Offset>],
+                original_size: $h.[<$name Size>],
+                data: $data,
+                fields: |h| (&mut h.[<$name Offset>], &mut h.[<$name Size>]),
+            }
+        }
+    }};
+}
+
+impl Metadata {
+    /// Serializes the metadata back to the global metadata file format.
+    ///
+    /// The header is copied from the loaded file, with the offset and size of every section
+    /// rebuilt from the in-memory vectors. Sections keep the order and spacing they had in the
+    /// original file, and start at 4-byte aligned offsets, so unmodified metadata is written
+    /// back byte for byte.
+    ///
+    /// Only the vectors read from the file are written; derived lookups such as
+    /// `cached_strings` and `field_default_values_map` are not, and must be kept in sync by
+    /// the caller when editing.
+    ///
+    /// # Errors
+    /// Returns an error if a section grows beyond the 2 GiB a header offset can address.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut header = self.header;
+        let h = &self.header;
+        let mut sections = [
+            section!(h, stringLiteral, slice_as_bytes(&self.string_literal)),
+            section!(h, stringLiteralData, &self.string_literal_data),
+            section!(h, string, &self.string_data),
+            section!(h, events, slice_as_bytes(&self.events)),
+            section!(h, properties, slice_as_bytes(&self.properties)),
+            section!(h, methods, self.methods.as_bytes()),
+            section!(
+                h,
+                parameterDefaultValues,
+                slice_as_bytes(&self.parameter_default_values)
+            ),
+            section!(
+                h,
+                fieldDefaultValues,
+                slice_as_bytes(&self.field_default_values)
+            ),
+            section!(
+                h,
+                fieldAndParameterDefaultValueData,
+                &self.field_and_parameter_default_value_data
+            ),
+            section!(
+                h,
+                fieldMarshaledSizes,
+                slice_as_bytes(&self.field_marshaled_sizes)
+            ),
+            section!(h, parameters, slice_as_bytes(&self.parameters)),
+            section!(h, fields, slice_as_bytes(&self.fields)),
+            section!(
+                h,
+                genericParameters,
+                slice_as_bytes(&self.generic_parameters)
+            ),
+            section!(
+                h,
+                genericParameterConstraints,
+                slice_as_bytes(&self.generic_parameter_constraints)
+            ),
+            section!(
+                h,
+                genericContainers,
+                slice_as_bytes(&self.generic_containers)
+            ),
+            section!(h, nestedTypes, slice_as_bytes(&self.nested_types)),
+            section!(h, interfaces, slice_as_bytes(&self.interfaces)),
+            section!(h, vtableMethods, slice_as_bytes(&self.vtable_methods)),
+            section!(h, interfaceOffsets, slice_as_bytes(&self.interface_offsets)),
+            section!(h, typeDefinitions, slice_as_bytes(&self.type_definitions)),
+            section!(h, images, slice_as_bytes(&self.images)),
+            section!(h, assemblies, slice_as_bytes(&self.assemblies)),
+            section!(h, fieldRefs, slice_as_bytes(&self.field_refs)),
+            section!(
+                h,
+                referencedAssemblies,
+                slice_as_bytes(&self.referenced_assemblies)
+            ),
+            section!(h, attributeData, &self.attribute_data),
+            section!(
+                h,
+                attributeDataRange,
+                slice_as_bytes(&self.attribute_data_range)
+            ),
+            section!(
+                h,
+                unresolvedIndirectCallParameterTypes,
+                &self.unresolved_indirect_call_parameter_types
+            ),
+            section!(
+                h,
+                unresolvedIndirectCallParameterRanges,
+                &self.unresolved_indirect_call_parameter_ranges
+            ),
+            section!(h, windowsRuntimeTypeNames, &self.windows_runtime_type_names),
+            section!(h, windowsRuntimeStrings, &self.windows_runtime_strings),
+            section!(
+                h,
+                exportedTypeDefinitions,
+                slice_as_bytes(&self.exported_type_definitions)
+            ),
+        ];
+        // Lay the sections out in the order they had in the original file.
+        sections.sort_by_key(|section| section.original_offset);
+
+        let mut data = vec![0u8; size_of::<Il2CppGlobalMetadataHeader>()];
+        let mut original_end = data.len() as i64;
+        for section in &sections {
+            let (offset_field, size_field) = (section.fields)(&mut header);
+            // Sections that were and still are empty keep their original offset, as it is
+            // never read.
+            if section.original_size == 0 && section.data.is_empty() {
+                *offset_field = section.original_offset;
+                *size_field = 0;
+                continue;
+            }
+
+            // Keep the gap the original file had before the section, then align the section
+            // like the original one, up to 4 bytes.
+            let gap = (section.original_offset as i64 - original_end).max(0) as usize;
+            let alignment = 1usize << section.original_offset.trailing_zeros().min(2);
+            let offset = (data.len() + gap).next_multiple_of(alignment);
+            data.resize(offset, 0);
+            data.extend_from_slice(section.data);
+            original_end = section.original_offset as i64 + section.original_size as i64;
+
+            *offset_field = i32::try_from(offset).map_err(|_| {
+                anyhow!(
+                    "Global metadata is too large to write the {} section",
+                    section.name
+                )
+            })?;
+            *size_field = i32::try_from(section.data.len())
+                .map_err(|_| anyhow!("The {} section is too large to write", section.name))?;
+        }
+        if i32::try_from(data.len()).is_err() {
+            bail!("Global metadata is too large to write");
+        }
+
+        data[..size_of::<Il2CppGlobalMetadataHeader>()]
+            .copy_from_slice(slice_as_bytes(std::slice::from_ref(&header)));
+        Ok(data)
+    }
+
+    /// Serializes the metadata (see [`Metadata::to_bytes`]) and writes it to a writer.
+    ///
+    /// # Errors
+    /// Returns an error if the metadata cannot be serialized or writing fails.
+    pub fn save_to_writer<W: Write>(&self, mut writer: W) -> Result<()> {
+        writer.write_all(&self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Checks that decrypted global metadata survives a load, save and load round trip
+    /// unchanged, so edits written with [`Metadata::to_bytes`] only change what was edited.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The decrypted global metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata cannot be loaded, or if the saved metadata differs from
+    /// the input, naming the first differing offset.
+    pub fn check_round_trip(data: &[u8]) -> Result<()> {
+        let metadata = Metadata::load_from_reader(Cursor::new(data))?;
+        let saved = metadata.to_bytes()?;
+        if let Some(offset) = first_difference(data, &saved) {
+            bail!(
+                "Saved global metadata differs from the input at offset 0x{:X} ({} bytes in, {} bytes out)",
+                offset,
+                data.len(),
+                saved.len()
+            );
+        }
+
+        // The saved metadata must load again, and save to the same bytes.
+        let reloaded = Metadata::load_from_reader(Cursor::new(&saved))?;
+        if let Some(offset) = first_difference(&saved, &reloaded.to_bytes()?) {
+            bail!(
+                "Reloaded global metadata saves differently at offset 0x{:X}",
+                offset
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns the offset of the first byte that differs between two buffers, or the length of the
+/// shorter one if it is a prefix of the other.
+fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    a.iter()
+        .zip(b)
+        .position(|(x, y)| x != y)
+        .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unity::generated::UnityVersion;
+    use crate::unity::global_metadata::GLOBAL_METADATA_MAGIC;
+    use crate::unity::method_definitions::MethodDefinitions;
+    use nohash_hasher::IntMap;
+
+    /// Builds a small metadata in memory with one type that has a field and a method, and two
+    /// string literals. The header only has the sanity value and version, as its offsets and
+    /// sizes are rebuilt when writing.
+    fn sample_metadata() -> Metadata {
+        Metadata {
+            header: Il2CppGlobalMetadataHeader {
+                sanity: GLOBAL_METADATA_MAGIC,
+                version: UnityVersion::Unity2022_3_56f1.metadata_version(),
+                ..Default::default()
+            },
+            unity_version: UnityVersion::Unity2022_3_56f1,
+            string_literal: vec![
+                Il2CppStringLiteral {
+                    length: 5,
+                    dataIndex: 0,
+                },
+                Il2CppStringLiteral {
+                    length: 4,
+                    dataIndex: 5,
+                },
+            ],
+            string_literal_data: b"helloshop".to_vec(),
+            string_data: b"\0Sample\0Game\0count\0Run\0".to_vec(),
+            cached_strings: IntMap::default(),
+            events: Vec::new(),
+            properties: Vec::new(),
+            methods: MethodDefinitions::Unity2022_3_56f1(vec![
+                il2cpp_2022356f1::CIl2Cpp::Il2CppMethodDefinition {
+                    nameIndex: 19,
+                    declaringType: 0,
+                    genericContainerIndex: -1,
+                    token: 0x0600_0001,
+                    ..Default::default()
+                },
+            ]),
+            parameter_default_values: Vec::new(),
+            field_default_values: Vec::new(),
+            field_default_values_map: IntMap::default(),
+            field_and_parameter_default_value_data: Vec::new(),
+            field_marshaled_sizes: Vec::new(),
+            parameters: Vec::new(),
+            fields: vec![Il2CppFieldDefinition {
+                nameIndex: 13,
+                typeIndex: 0,
+                token: 0x0400_0001,
+            }],
+            generic_parameters: Vec::new(),
+            generic_parameter_constraints: Vec::new(),
+            generic_containers: Vec::new(),
+            nested_types: Vec::new(),
+            interfaces: Vec::new(),
+            vtable_methods: Vec::new(),
+            interface_offsets: Vec::new(),
+            type_definitions: vec![Il2CppTypeDefinition {
+                nameIndex: 1,
+                namespaceIndex: 8,
+                declaringTypeIndex: -1,
+                parentIndex: -1,
+                elementTypeIndex: -1,
+                genericContainerIndex: -1,
+                field_count: 1,
+                method_count: 1,
+                ..Default::default()
+            }],
+            images: Vec::new(),
+            assemblies: Vec::new(),
+            field_refs: Vec::new(),
+            referenced_assemblies: Vec::new(),
+            attribute_data: Vec::new(),
+            attribute_data_range: Vec::new(),
+            unresolved_indirect_call_parameter_types: Vec::new(),
+            unresolved_indirect_call_parameter_ranges: Vec::new(),
+            windows_runtime_type_names: Vec::new(),
+            windows_runtime_strings: Vec::new(),
+            exported_type_definitions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn to_bytes_round_trips() {
+        let bytes = sample_metadata().to_bytes().unwrap();
+        let reloaded = Metadata::load_from_reader(Cursor::new(&bytes)).unwrap();
+        assert_eq!(reloaded.to_bytes().unwrap(), bytes);
+        Metadata::check_round_trip(&bytes).unwrap();
+
+        assert_eq!(reloaded.get_string_by_index(1), "Sample");
+        assert_eq!(reloaded.string_literal(1).unwrap(), "shop");
+        assert_eq!(reloaded.type_definitions[0].namespaceIndex, 8);
+        assert_eq!(reloaded.fields[0].nameIndex, 13);
+        assert_eq!(reloaded.methods.len(), 1);
+    }
+
+    #[test]
+    fn edited_string_literal_survives_round_trip() {
+        let mut metadata = sample_metadata();
+        let changed = metadata
+            .set_string_literals(&[(0, "hello, world".to_string())])
+            .unwrap();
+        assert_eq!(changed, 1);
+
+        let bytes = metadata.to_bytes().unwrap();
+        let reloaded = Metadata::load_from_reader(Cursor::new(&bytes)).unwrap();
+        assert_eq!(reloaded.string_literal(0).unwrap(), "hello, world");
+        assert_eq!(reloaded.string_literal(1).unwrap(), "shop");
+        assert_eq!(reloaded.get_string_by_index(8), "Game");
+        assert_eq!(reloaded.to_bytes().unwrap(), bytes);
+    }
+}
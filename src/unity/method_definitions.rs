@@ -0,0 +1,198 @@
+use crate::unity::generated::CIl2Cpp::{
+    GenericContainerIndex, ParameterIndex, StringIndex, TypeDefinitionIndex, TypeIndex,
+};
+use crate::unity::generated::{il2cpp_2022322f1, il2cpp_2022356f1, UnityVersion};
+use crate::unity::metadata_writer::slice_as_bytes;
+use std::ops::Range;
+
+/// The method is static.
+pub const METHOD_ATTRIBUTE_STATIC: u16 = 0x10;
+/// The method is virtual.
+pub const METHOD_ATTRIBUTE_VIRTUAL: u16 = 0x40;
+/// The method is abstract.
+pub const METHOD_ATTRIBUTE_ABSTRACT: u16 = 0x400;
+
+/// A method definition from the global metadata, independent of the metadata version.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MethodDefinition {
+    /// Index of the method name in the string data.
+    pub name_index: StringIndex,
+    /// Index of the type definition declaring the method.
+    pub declaring_type: TypeDefinitionIndex,
+    /// Index of the return type.
+    pub return_type: TypeIndex,
+    /// Metadata token of the return parameter. Only present since global metadata version 31.
+    pub return_parameter_token: Option<u32>,
+    /// Index of the first parameter definition.
+    pub parameter_start: ParameterIndex,
+    /// Index of the generic container, if the method is generic.
+    pub generic_container_index: GenericContainerIndex,
+    /// Metadata token of the method.
+    pub token: u32,
+    /// Method attributes (`METHOD_ATTRIBUTE_*`).
+    pub flags: u16,
+    /// Method implementation attributes (`METHOD_IMPL_ATTRIBUTE_*`).
+    pub iflags: u16,
+    /// Virtual table slot of the method.
+    pub slot: u16,
+    /// Number of parameters.
+    pub parameter_count: u16,
+}
+
+impl MethodDefinition {
+    /// Determines if the method is static.
+    pub fn is_static(&self) -> bool {
+        self.flags & METHOD_ATTRIBUTE_STATIC != 0
+    }
+
+    /// Determines if the method is virtual.
+    pub fn is_virtual(&self) -> bool {
+        self.flags & METHOD_ATTRIBUTE_VIRTUAL != 0
+    }
+
+    /// Determines if the method is abstract.
+    pub fn is_abstract(&self) -> bool {
+        self.flags & METHOD_ATTRIBUTE_ABSTRACT != 0
+    }
+}
+
+impl From<&il2cpp_2022322f1::CIl2Cpp::Il2CppMethodDefinition> for MethodDefinition {
+    /// Converts a version 29 method definition, which has no return parameter token.
+    fn from(method: &il2cpp_2022322f1::CIl2Cpp::Il2CppMethodDefinition) -> Self {
+        MethodDefinition {
+            name_index: method.nameIndex,
+            declaring_type: method.declaringType,
+            return_type: method.returnType,
+            return_parameter_token: None,
+            parameter_start: method.parameterStart,
+            generic_container_index: method.genericContainerIndex,
+            token: method.token,
+            flags: method.flags,
+            iflags: method.iflags,
+            slot: method.slot,
+            parameter_count: method.parameterCount,
+        }
+    }
+}
+
+impl From<&il2cpp_2022356f1::CIl2Cpp::Il2CppMethodDefinition> for MethodDefinition {
+    /// Converts a version 31 method definition.
+    fn from(method: &il2cpp_2022356f1::CIl2Cpp::Il2CppMethodDefinition) -> Self {
+        MethodDefinition {
+            name_index: method.nameIndex,
+            declaring_type: method.declaringType,
+            return_type: method.returnType,
+            return_parameter_token: Some(method.returnParameterToken),
+            parameter_start: method.parameterStart,
+            generic_container_index: method.genericContainerIndex,
+            token: method.token,
+            flags: method.flags,
+            iflags: method.iflags,
+            slot: method.slot,
+            parameter_count: method.parameterCount,
+        }
+    }
+}
+
+/// The method definitions of a global metadata file, kept in the layout of its version.
+///
+/// The layout of `Il2CppMethodDefinition` differs between metadata versions, so the definitions
+/// are stored as read from the file and converted to a [`MethodDefinition`] on access.
+pub enum MethodDefinitions {
+    /// Method definitions of Unity 2022.3.22f1 (global metadata version 29).
+    Unity2022_3_22f1(Vec<il2cpp_2022322f1::CIl2Cpp::Il2CppMethodDefinition>),
+    /// Method definitions of Unity 2022.3.56f1 (global metadata version 31).
+    Unity2022_3_56f1(Vec<il2cpp_2022356f1::CIl2Cpp::Il2CppMethodDefinition>),
+}
+
+impl MethodDefinitions {
+    /// Returns the Unity version whose layout the method definitions are stored in.
+    pub fn unity_version(&self) -> UnityVersion {
+        match self {
+            MethodDefinitions::Unity2022_3_22f1(_) => UnityVersion::Unity2022_3_22f1,
+            MethodDefinitions::Unity2022_3_56f1(_) => UnityVersion::Unity2022_3_56f1,
+        }
+    }
+
+    /// Returns the number of method definitions.
+    pub fn len(&self) -> usize {
+        match self {
+            MethodDefinitions::Unity2022_3_22f1(methods) => methods.len(),
+            MethodDefinitions::Unity2022_3_56f1(methods) => methods.len(),
+        }
+    }
+
+    /// Returns `true` if there are no method definitions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the method definition at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds, like indexing a slice.
+    pub fn get(&self, index: usize) -> MethodDefinition {
+        match self {
+            MethodDefinitions::Unity2022_3_22f1(methods) => (&methods[index]).into(),
+            MethodDefinitions::Unity2022_3_56f1(methods) => (&methods[index]).into(),
+        }
+    }
+
+    /// Returns the method definition at the given index, or `None` if it is out of bounds.
+    pub fn try_get(&self, index: usize) -> Option<MethodDefinition> {
+        match self {
+            MethodDefinitions::Unity2022_3_22f1(methods) => methods.get(index).map(Into::into),
+            MethodDefinitions::Unity2022_3_56f1(methods) => methods.get(index).map(Into::into),
+        }
+    }
+
+    /// Returns an iterator over the method definitions in the given index range, such as the
+    /// methods of a type definition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, like slicing a slice.
+    pub fn range(&self, range: Range<usize>) -> impl Iterator<Item = MethodDefinition> + '_ {
+        match self {
+            MethodDefinitions::Unity2022_3_22f1(methods) => {
+                MethodIter::Unity2022_3_22f1(methods[range].iter())
+            }
+            MethodDefinitions::Unity2022_3_56f1(methods) => {
+                MethodIter::Unity2022_3_56f1(methods[range].iter())
+            }
+        }
+    }
+
+    /// Returns an iterator over all method definitions.
+    pub fn iter(&self) -> impl Iterator<Item = MethodDefinition> + '_ {
+        self.range(0..self.len())
+    }
+
+    /// Returns the raw bytes of the method definitions, in the layout of the metadata version.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            MethodDefinitions::Unity2022_3_22f1(methods) => slice_as_bytes(methods),
+            MethodDefinitions::Unity2022_3_56f1(methods) => slice_as_bytes(methods),
+        }
+    }
+}
+
+/// Iterator over method definitions of any layout, converting them to [`MethodDefinition`].
+enum MethodIter<'a> {
+    /// Iterates over version 29 method definitions.
+    Unity2022_3_22f1(std::slice::Iter<'a, il2cpp_2022322f1::CIl2Cpp::Il2CppMethodDefinition>),
+    /// Iterates over version 31 method definitions.
+    Unity2022_3_56f1(std::slice::Iter<'a, il2cpp_2022356f1::CIl2Cpp::Il2CppMethodDefinition>),
+}
+
+impl Iterator for MethodIter<'_> {
+    type Item = MethodDefinition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            MethodIter::Unity2022_3_22f1(iter) => iter.next().map(MethodDefinition::from),
+            MethodIter::Unity2022_3_56f1(iter) => iter.next().map(MethodDefinition::from),
+        }
+    }
+}
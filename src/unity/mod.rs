@@ -1,10 +1,19 @@
 #![allow(dead_code)]
 
 pub mod blob_value;
+pub mod code_registration;
 pub mod complex_type;
+pub mod custom_attributes;
+pub mod field_layout;
 pub mod generated;
 pub mod global_metadata;
+pub mod hierarchy;
 pub mod il2cpp;
 pub mod il2cpp_type;
 pub mod il2cpp_type_definition;
-pub mod unity_loader;
\ No newline at end of file
+pub mod metadata_usages;
+pub mod metadata_writer;
+pub mod method_definitions;
+pub mod profile;
+pub mod string_literals;
+pub mod unity_loader;
@@ -0,0 +1,86 @@
+use crate::archive::manifest::ManifestInfo;
+use crate::archive::AndroidArchive;
+use crate::unity::generated::UnityVersion;
+use log::{info, warn};
+
+/// Describes a Unity version the game has been built with, starting from a given game version.
+#[derive(Debug)]
+pub struct UnityProfile {
+    /// The Unity version.
+    pub unity_version: UnityVersion,
+    /// The first game version (`versionName`) built with this Unity version.
+    pub first_game_version: [u32; 3],
+}
+
+/// The known Unity profiles, ordered by the game version that introduced them.
+pub const UNITY_PROFILES: &[UnityProfile] = &[
+    UnityProfile {
+        unity_version: UnityVersion::Unity2022_3_22f1,
+        first_game_version: [0, 0, 0],
+    },
+    UnityProfile {
+        unity_version: UnityVersion::Unity2022_3_56f1,
+        first_game_version: [1, 2, 0],
+    },
+];
+
+/// Returns the Unity profile a game version was built with.
+///
+/// # Arguments
+///
+/// * `version_name` - The game `versionName` from the manifest (e.g. `1.2.0`).
+///
+/// # Returns
+///
+/// - `Some(&UnityProfile)` for the latest profile introduced at or before the game version.
+/// - `None` if the version name is not a dotted version number.
+pub fn profile_for_game_version(version_name: &str) -> Option<&'static UnityProfile> {
+    let mut version = [0u32; 3];
+    for (i, part) in version_name.split('.').take(3).enumerate() {
+        // Ignore suffixes such as `1.2.0-beta`.
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        version[i] = digits.parse().ok()?;
+    }
+    UNITY_PROFILES
+        .iter()
+        .rev()
+        .find(|profile| profile.first_game_version <= version)
+}
+
+/// Logs the build contained in an archive and the Unity version it is expected to use.
+///
+/// The Unity bindings are selected from the global metadata version once the metadata is loaded
+/// (see [`UnityVersion::from_metadata_version`]), so the game version only serves as an early
+/// indication. Archives whose manifest cannot be decoded are still accepted, since the manifest
+/// is only used for identification.
+///
+/// # Arguments
+///
+/// * `archive` - The archive to identify.
+pub fn log_archive_build(archive: &mut dyn AndroidArchive) {
+    let manifest = match archive.manifest_info() {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            warn!("Could not read AndroidManifest.xml: {}", err);
+            return;
+        }
+    };
+    manifest.log_summary();
+    log_expected_unity_version(&manifest);
+}
+
+/// Logs the Unity version a build is expected to use, based on its game version.
+fn log_expected_unity_version(manifest: &ManifestInfo) {
+    match manifest
+        .version_name
+        .as_deref()
+        .and_then(profile_for_game_version)
+    {
+        Some(profile) => info!(
+            "Expected Unity version: {} (global metadata v{})",
+            profile.unity_version,
+            profile.unity_version.metadata_version()
+        ),
+        None => warn!("Could not determine the Unity version from the game version"),
+    }
+}
@@ -0,0 +1,106 @@
+use crate::unity::generated::CIl2Cpp::Il2CppStringLiteral;
+use crate::unity::global_metadata::Metadata;
+use anyhow::{anyhow, bail, Result};
+use std::borrow::Cow;
+
+impl Metadata {
+    /// Returns the number of string literals in the metadata.
+    pub fn string_literal_count(&self) -> usize {
+        self.string_literal.len()
+    }
+
+    /// Returns the raw UTF-8 bytes of a string literal.
+    ///
+    /// # Errors
+    /// Returns an error if the index is out of bounds, or if the literal points outside of the
+    /// string literal data.
+    pub fn string_literal_bytes(&self, index: usize) -> Result<&[u8]> {
+        let literal = self
+            .string_literal
+            .get(index)
+            .ok_or_else(|| anyhow!("String literal {} does not exist", index))?;
+        let start = literal.dataIndex as usize;
+        self.string_literal_data
+            .get(start..start + literal.length as usize)
+            .ok_or_else(|| {
+                anyhow!(
+                    "String literal {} points outside of the literal data",
+                    index
+                )
+            })
+    }
+
+    /// Returns a string literal decoded as UTF-8, replacing invalid sequences.
+    ///
+    /// # Errors
+    /// Returns an error if the literal cannot be read (see [`Metadata::string_literal_bytes`]).
+    pub fn string_literal(&self, index: usize) -> Result<Cow<'_, str>> {
+        Ok(String::from_utf8_lossy(self.string_literal_bytes(index)?))
+    }
+
+    /// Replaces string literals and rebuilds the string literal table.
+    ///
+    /// Edits that do not change a literal, as decoded by [`Metadata::string_literal`], are
+    /// ignored, so literals that are not valid UTF-8 are kept as is when exported and imported
+    /// again. Later edits of the same literal win.
+    /// When any literal changes, the literal data is rebuilt in index order, keeping the original
+    /// bytes of every unchanged literal, and the table is updated to point at the new data. The
+    /// header offsets are rebuilt when the metadata is written (see [`Metadata::to_bytes`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `edits` - Pairs of literal index and new value.
+    ///
+    /// # Returns
+    ///
+    /// The number of literals that changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an index does not exist, or if a literal cannot be read.
+    pub fn set_string_literals(&mut self, edits: &[(usize, String)]) -> Result<usize> {
+        let mut values: Vec<Option<&str>> = vec![None; self.string_literal.len()];
+        for (index, value) in edits {
+            if *index >= values.len() {
+                bail!(
+                    "String literal {} does not exist, the metadata has {} string literals",
+                    index,
+                    values.len()
+                );
+            }
+            values[*index] = Some(value);
+        }
+
+        // Only keep the edits that change a literal; later edits of the same index win.
+        let mut changed = 0;
+        for (index, value) in values.iter_mut().enumerate() {
+            if let Some(new_value) = *value {
+                if self.string_literal(index)? == new_value {
+                    *value = None;
+                } else {
+                    changed += 1;
+                }
+            }
+        }
+        if changed == 0 {
+            return Ok(0);
+        }
+
+        let mut string_literal = Vec::with_capacity(self.string_literal.len());
+        let mut string_literal_data = Vec::with_capacity(self.string_literal_data.len());
+        for (index, value) in values.into_iter().enumerate() {
+            let bytes = match value {
+                Some(value) => value.as_bytes(),
+                None => self.string_literal_bytes(index)?,
+            };
+            string_literal.push(Il2CppStringLiteral {
+                length: u32::try_from(bytes.len())?,
+                dataIndex: i32::try_from(string_literal_data.len())?,
+            });
+            string_literal_data.extend_from_slice(bytes);
+        }
+        self.string_literal = string_literal;
+        self.string_literal_data = string_literal_data;
+        Ok(changed)
+    }
+}
@@ -1,17 +1,23 @@
+use crate::binary::abi::Abi;
 use crate::crypto::global_metadata;
+use crate::unity::global_metadata::is_plain_global_metadata;
 use crate::unity::il2cpp::Il2Cpp;
 use crate::utils::file_backed_cache::FileBackedCache;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use foldhash::fast::FixedState;
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use std::hash::{BuildHasher, Hasher};
-use log::debug;
+use log::{debug, info, warn};
 use crate::binary::elf::Elf;
+use serde::{Deserialize, Serialize};
 
-type EncryptionKey = [u8; 16];
-type EncryptionKeyXor = u64;
-type EncryptionKeyParts = (EncryptionKey, EncryptionKeyXor);
+/// The AES-128 key the global metadata is encrypted with, before applying the xor.
+pub type EncryptionKey = [u8; 16];
+/// The value the encryption key is xored with before use.
+pub type EncryptionKeyXor = u64;
+/// An encryption key along with the value it is xored with.
+pub type EncryptionKeyParts = (EncryptionKey, EncryptionKeyXor);
 
 /// Static cache that maps the foldhash of IL2CPP data to its corresponding
 /// (metadata_key, metadata_key_xor) pair. This file-backed cache allows the
@@ -19,61 +25,299 @@ type EncryptionKeyParts = (EncryptionKey, EncryptionKeyXor);
 static KEY_CACHE: Lazy<Mutex<FileBackedCache<u64, EncryptionKeyParts>>> =
     Lazy::new(|| Mutex::new(FileBackedCache::new("il2cpp_keys")));
 
-/// Loads and decrypts IL2CPP data using the provided global metadata.
+/// A key cache entry in the exchange format written by [`export_metadata_keys`] and read by
+/// [`import_metadata_keys`], with the key parts in the notation accepted on the command line.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedMetadataKey {
+    /// The foldhash of the encrypted global metadata the key belongs to.
+    pub metadata_hash: u64,
+    /// The encryption key as 32 hexadecimal digits.
+    pub key: String,
+    /// The key xor as a `0x`-prefixed hexadecimal number.
+    pub key_xor: String,
+}
+
+/// Global metadata decrypted with the key recovered from its IL2CPP binary.
+pub struct DecryptedGlobalMetadata {
+    /// The decrypted global metadata, starting with
+    /// [`GLOBAL_METADATA_MAGIC`](crate::unity::global_metadata::GLOBAL_METADATA_MAGIC).
+    pub data: Vec<u8>,
+    /// The encryption key recovered from the IL2CPP binary.
+    pub key: EncryptionKey,
+    /// The key xor recovered from the IL2CPP binary.
+    pub key_xor: EncryptionKeyXor,
+}
+
+/// Decrypts the global metadata with the key embedded in its IL2CPP binary.
 ///
 /// This function performs the following steps:
 /// 1. Computes a unique foldhash from the provided global metadata data.
-/// 2. Attempts to retrieve the corresponding metadata key and its XOR obfuscation from a
-///    file-backed cache. If not found, it extracts these keys from the IL2CPP binary.
-/// 3. Caches the extracted keys for future reuse.
-/// 4. Uses the keys to decrypt the global metadata.
-/// 5. Loads and returns the IL2CPP binary along with its decrypted metadata.
+/// 2. Uses the key override if one is given, caching it once it is known to be correct.
+/// 3. Otherwise, attempts to retrieve the corresponding metadata key and its XOR obfuscation from a
+///    file-backed cache, and uses them if they decrypt the metadata to a valid header.
+/// 4. Otherwise, extracts these keys from the IL2CPP binary and uses them to decrypt the metadata.
+///    On x86_64, only a candidate key that decrypts the metadata header is accepted.
+/// 5. Checks the sanity value of the decrypted metadata, and caches newly extracted keys only
+///    once they are known to be correct.
+///
+/// # Arguments
+///
+/// * `elf` - The IL2CPP binary the metadata belongs to.
+/// * `global_metadata_data` - The encrypted global metadata.
+/// * `key_override` - A key and key xor to use instead of the cached or extracted ones, for
+///   builds the key extraction does not support yet.
+///
+/// # Errors
+///
+/// Returns an error if the data is already decrypted or is not encrypted global metadata, the
+/// keys cannot be extracted, the key override does not match, or the decrypted data does not start with
+/// [`GLOBAL_METADATA_MAGIC`](crate::unity::global_metadata::GLOBAL_METADATA_MAGIC).
+pub fn decrypt_global_metadata(
+    elf: &Elf,
+    global_metadata_data: &[u8],
+    key_override: Option<EncryptionKeyParts>,
+) -> Result<DecryptedGlobalMetadata> {
+    if is_plain_global_metadata(global_metadata_data) {
+        bail!("The global metadata is already decrypted");
+    }
+
+    // Encrypted metadata starts with the length of the ciphertext that follows it.
+    let ciphertext_len = global_metadata_data
+        .get(..4)
+        .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize);
+    if ciphertext_len != Some(global_metadata_data.len().saturating_sub(4)) {
+        bail!("The global metadata is not encrypted or is truncated");
+    }
+
+    let hash_key = metadata_cache_key(global_metadata_data);
+
+    if let Some((key, key_xor)) = key_override {
+        info!("Decrypting global metadata with the provided metadata key...");
+        let data = global_metadata::decrypt(global_metadata_data, key, key_xor);
+        if !is_plain_global_metadata(&data) {
+            bail!("The provided metadata key and key xor do not decrypt the global metadata");
+        }
+        KEY_CACHE.lock().insert(hash_key, (key, key_xor))?;
+        return Ok(DecryptedGlobalMetadata { data, key, key_xor });
+    }
+
+    // Use the cached keys if they produce a valid header, as extracting them is expensive.
+    let cached_keys = KEY_CACHE.lock().get(&hash_key).copied();
+    if let Some((key, key_xor)) = cached_keys {
+        debug!("Decrypting global metadata with cached keys...");
+        let data = global_metadata::decrypt(global_metadata_data, key, key_xor);
+        if is_plain_global_metadata(&data) {
+            return Ok(DecryptedGlobalMetadata { data, key, key_xor });
+        }
+        warn!("The cached metadata key does not decrypt the global metadata, extracting it again");
+    }
+
+    let (key, key_xor) = if elf.abi == Abi::X86_64 {
+        extract_metadata_key_x86_64(elf, global_metadata_data)?
+    } else {
+        let (key_xor_offset, key_xor) = Il2Cpp::extract_metadata_key_xor(elf)?
+            .ok_or_else(|| anyhow!("Could not extract global metadata key xor data"))?;
+        let key = Il2Cpp::extract_metadata_key(elf, key_xor_offset)?
+            .ok_or_else(|| anyhow!("Could not extract global metadata encryption key"))?;
+        (key, key_xor)
+    };
+
+    debug!("Metadata key: {:X?}", key);
+    debug!("Metadata key xor: {:X}", key_xor);
+
+    debug!("Decrypting global metadata...");
+    let data = global_metadata::decrypt(global_metadata_data, key, key_xor);
+    if !is_plain_global_metadata(&data) {
+        bail!(
+            "Decrypted global metadata has an invalid sanity value, the recovered key does not match the metadata"
+        );
+    }
+
+    KEY_CACHE.lock().insert(hash_key, (key, key_xor))?;
+
+    Ok(DecryptedGlobalMetadata { data, key, key_xor })
+}
+
+/// Extracts the metadata key of an x86_64 IL2CPP binary.
+///
+/// The key candidates found in the code are tried in order, and the first one that decrypts the
+/// metadata header to a valid sanity value is used.
+///
+/// # Errors
+///
+/// Returns an error if no candidate decrypts the metadata header.
+fn extract_metadata_key_x86_64(
+    elf: &Elf,
+    global_metadata_data: &[u8],
+) -> Result<EncryptionKeyParts> {
+    Il2Cpp::extract_metadata_key_candidates_x86_64(elf)
+        .into_iter()
+        .find(|&(key, key_xor)| {
+            is_plain_global_metadata(&global_metadata::decrypt_prefix(
+                global_metadata_data,
+                4,
+                key,
+                key_xor,
+            ))
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "x86_64 key extraction unsupported for this build, pass --metadata-key and --metadata-key-xor"
+            )
+        })
+}
+
+/// Records the key of encrypted global metadata in the key cache, so it is not extracted from
+/// the IL2CPP binary again when the metadata is loaded, e.g. after re-encrypting edited metadata.
+///
+/// # Arguments
+///
+/// * `global_metadata_data` - The encrypted global metadata.
+/// * `key` - The encryption key the metadata was encrypted with.
+/// * `key_xor` - The key xor the metadata was encrypted with.
+pub fn cache_global_metadata_key(
+    global_metadata_data: &[u8],
+    key: EncryptionKey,
+    key_xor: EncryptionKeyXor,
+) -> Result<()> {
+    let hash_key = metadata_cache_key(global_metadata_data);
+    KEY_CACHE.lock().insert(hash_key, (key, key_xor))
+}
+
+/// Returns all entries of the key cache in the exchange format, ordered by metadata hash.
+pub fn export_metadata_keys() -> Vec<ExportedMetadataKey> {
+    let mut entries: Vec<ExportedMetadataKey> = KEY_CACHE
+        .lock()
+        .iter()
+        .map(|(&metadata_hash, &(key, key_xor))| ExportedMetadataKey {
+            metadata_hash,
+            key: format_encryption_key(&key),
+            key_xor: format_encryption_key_xor(key_xor),
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.metadata_hash);
+    entries
+}
+
+/// Adds entries in the exchange format to the key cache, replacing the keys of metadata that is
+/// already cached.
+///
+/// All entries are parsed before any of them is added, so invalid input leaves the cache unchanged.
+///
+/// # Returns
+///
+/// The number of entries for metadata that was not cached before.
+///
+/// # Errors
+///
+/// Returns an error if an entry has an invalid key or key xor, or if saving the cache fails.
+pub fn import_metadata_keys(entries: &[ExportedMetadataKey]) -> Result<usize> {
+    let parsed = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let key = parse_encryption_key(&entry.key)
+                .map_err(|err| anyhow!("Entry {}: {}", i + 1, err))?;
+            let key_xor = parse_encryption_key_xor(&entry.key_xor)
+                .map_err(|err| anyhow!("Entry {}: {}", i + 1, err))?;
+            Ok((entry.metadata_hash, (key, key_xor)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut cache = KEY_CACHE.lock();
+    let new_entries = parsed
+        .iter()
+        .filter(|(metadata_hash, _)| cache.get(metadata_hash).is_none())
+        .count();
+    cache.extend(parsed)?;
+    Ok(new_entries)
+}
+
+/// Formats an encryption key as 32 hexadecimal digits, as accepted by [`parse_encryption_key`].
+pub fn format_encryption_key(key: &EncryptionKey) -> String {
+    key.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// Formats a key xor as a `0x`-prefixed hexadecimal number, as accepted by
+/// [`parse_encryption_key_xor`].
+pub fn format_encryption_key_xor(key_xor: EncryptionKeyXor) -> String {
+    format!("0x{:016X}", key_xor)
+}
+
+/// Parses an encryption key from 32 hexadecimal digits, optionally prefixed with `0x` and
+/// separated by spaces.
+///
+/// # Errors
+///
+/// Returns an error if the text is not exactly 16 bytes of hexadecimal digits.
+pub fn parse_encryption_key(text: &str) -> Result<EncryptionKey> {
+    let digits: String = text.split_whitespace().collect();
+    let digits = digits.strip_prefix("0x").unwrap_or(&digits);
+    if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("Metadata key must be 32 hexadecimal digits (16 bytes), got \"{}\"", text);
+    }
+    let mut key = EncryptionKey::default();
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16)?;
+    }
+    Ok(key)
+}
+
+/// Parses a key xor from a `0x`-prefixed hexadecimal number or a decimal number.
+///
+/// # Errors
+///
+/// Returns an error if the text is not a valid 64-bit number.
+pub fn parse_encryption_key_xor(text: &str) -> Result<EncryptionKeyXor> {
+    let text = text.trim();
+    let key_xor = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(digits) => EncryptionKeyXor::from_str_radix(digits, 16),
+        None => text.parse(),
+    };
+    key_xor.map_err(|_| {
+        anyhow!(
+            "Metadata key xor must be a 64-bit hexadecimal (0x...) or decimal number, got \"{}\"",
+            text
+        )
+    })
+}
+
+/// Computes a unique foldhash of the encrypted global metadata to use as the cache key.
+fn metadata_cache_key(global_metadata_data: &[u8]) -> u64 {
+    let mut hasher = FixedState::default().build_hasher();
+    hasher.write(global_metadata_data);
+    hasher.finish()
+}
+
+/// Loads IL2CPP data along with its global metadata, decrypting the metadata if needed.
+///
+/// Global metadata that already starts with the sanity value is used as is. Otherwise, it is
+/// decrypted with the key embedded in the IL2CPP binary, or with the key override if one is
+/// given (see [`decrypt_global_metadata`]).
 ///
 /// # Arguments
 ///
-/// * `il2cpp_data` - A vector of bytes representing the encrypted IL2CPP binary.
-/// * `global_metadata_data` - A vector of bytes representing the encrypted global metadata.
+/// * `il2cpp_data` - A vector of bytes representing the IL2CPP binary.
+/// * `global_metadata_data` - A vector of bytes representing the encrypted or plain global metadata.
+/// * `key_override` - A key and key xor to use instead of the cached or extracted ones.
 ///
 /// # Returns
 ///
 /// * `Result<Il2Cpp>` - On success, returns an `Il2Cpp` instance that encapsulates the loaded
 ///   IL2CPP binary and its decrypted metadata; on failure, returns an error indicating the issue.
-pub fn load_encrypted_il2cpp<'a>(
+pub fn load_il2cpp<'a>(
     il2cpp_data: Vec<u8>,
     global_metadata_data: Vec<u8>,
+    key_override: Option<EncryptionKeyParts>,
 ) -> Result<Il2Cpp<'a>> {
-    // Compute a unique foldhash of the global metadata to use as the cache key.
-    let mut hasher = FixedState::default().build_hasher();
-    hasher.write(&global_metadata_data);
-    let hash_key = hasher.finish();
-    
     let elf = Elf::new(il2cpp_data)?;
-
-    // Retrieve keys from the cache using double-checked locking.
-    let (metadata_key, metadata_key_xor) = {
-        if let Some(&(key, key_xor)) = KEY_CACHE.lock().get(&hash_key) {
-            // Found in cache; copy the values.
-            (key, key_xor)
-        } else {
-            let (key_xor_offset, key_xor) = Il2Cpp::extract_metadata_key_xor(&elf)
-                .ok_or_else(|| anyhow!("Could not extract global metadata key xor data"))?;
-            // Not in cache; extract the keys and store
-            let key = Il2Cpp::extract_metadata_key(&elf, key_xor_offset)
-                .ok_or_else(|| anyhow!("Could not extract global metadata encryption key"))?;
-
-            let new_keys = (key, key_xor);
-            KEY_CACHE.lock().insert(hash_key, new_keys)?;
-            new_keys
-        }
+    let global_metadata_data = if is_plain_global_metadata(&global_metadata_data) {
+        info!("Global metadata is already decrypted, skipping decryption");
+        global_metadata_data
+    } else {
+        decrypt_global_metadata(&elf, &global_metadata_data, key_override)?.data
     };
 
-    debug!("Metadata key: {:X?}", metadata_key);
-    debug!("Metadata key xor: {:X}", metadata_key_xor);
-
-    debug!("Decrypting global metadata...");
-    let decrypted_global_metadata =
-        global_metadata::decrypt(&global_metadata_data, metadata_key, metadata_key_xor);
-
     // Load and return the IL2CPP binary along with its decrypted metadata.
-    Il2Cpp::load_from_vec(elf, decrypted_global_metadata)
+    Il2Cpp::load_from_vec(elf, global_metadata_data)
 }
@@ -1,5 +1,10 @@
 pub const GLOBAL_METADATA_PATH: &str = "assets/bin/Data/Managed/Metadata/global-metadata.dat";
-pub const IL2CPP_PATH: &str = "lib/arm64-v8a/libil2cpp.so";
-pub const APK_FILTER: (&str, &[&str]) = ("APK Files", &["xapk", "apk"]);
+pub const APK_FILTER: (&str, &[&str]) = ("APK Files", &["xapk", "apk", "apks", "apkm"]);
 pub const IL2CPP_FILTER: (&str, &[&str]) = ("Il2Cpp Files", &["so"]);
 pub const METADATA_FILTER: (&str, &[&str]) = ("Global Metadata Files", &["dat"]);
+pub const SIGNING_KEY_FILTER: (&str, &[&str]) = ("Key Files", &["p12", "pfx", "pem"]);
+pub const KEY_CACHE_FILTER: (&str, &[&str]) = ("Key Cache Files", &["json"]);
+pub const STRING_LITERALS_FILTER: (&str, &[&str]) = ("String Literal Files", &["json", "tsv"]);
+pub const DUMP_FILTER: (&str, &[&str]) = ("C# Files", &["cs"]);
+pub const TEXT_FILTER: (&str, &[&str]) = ("Text Files", &["txt"]);
+pub const CONSTANTS_FILTER: (&str, &[&str]) = ("JSON Files", &["json"]);
@@ -84,6 +84,31 @@ where
         Ok(())
     }
 
+    /// Returns an iterator over all key-value pairs in the cache, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.cache.iter()
+    }
+
+    /// Inserts multiple key-value pairs into the cache and saves the updated cache to disk once.
+    ///
+    /// Existing values are replaced for keys that are already present.
+    ///
+    /// # Parameters
+    ///
+    /// - `entries`: The key-value pairs to insert.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the insertion and save operation are successful.
+    /// - An error if saving to disk fails.
+    pub fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, entries: I) -> Result<()> {
+        // Update the in-memory cache.
+        self.cache.extend(entries);
+        // Persist the updated cache to the disk.
+        self.save()?;
+        Ok(())
+    }
+
     /// Persists the current state of the cache to the associated file in JSON format.
     ///
     /// # Returns
@@ -1,5 +1,5 @@
 #![allow(dead_code)]
 
 pub mod consts;
-pub mod read_only;
 pub mod file_backed_cache;
+pub mod read_only;